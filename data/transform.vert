@@ -0,0 +1,12 @@
+#version 330 core
+in vec4 position;
+in vec2 texture;
+
+uniform mat4 transform;
+
+out vec2 uv;
+
+void main() {
+  uv = texture;
+  gl_Position = transform * position;
+}
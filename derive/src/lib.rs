@@ -0,0 +1,287 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+#[cfg(feature = "naga")]
+use std::env;
+#[cfg(feature = "naga")]
+use std::fs;
+#[cfg(feature = "naga")]
+use std::path::Path;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+#[cfg(feature = "naga")]
+use syn::parse::Parse;
+#[cfg(feature = "naga")]
+use syn::parse::ParseStream;
+use syn::parse_macro_input;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Field;
+use syn::Fields;
+use syn::GenericArgument;
+#[cfg(feature = "naga")]
+use syn::Ident;
+use syn::Index;
+use syn::LitStr;
+use syn::PathArguments;
+use syn::Result;
+#[cfg(feature = "naga")]
+use syn::Token;
+use syn::Type;
+#[cfg(feature = "naga")]
+use xgl_glsl::validate;
+#[cfg(feature = "naga")]
+use xgl_glsl::Stage;
+
+
+/// Derive `xgl::Uniforms` for a struct with named fields.
+///
+/// Each field is mapped to the uniform of the same name. The following
+/// field attributes are supported:
+/// - `#[uniform(name = "...")]`: map the field to the uniform with the
+///   given name instead
+/// - `#[uniform(array)]`: map an array or `Vec` field to a uniform
+///   array, as opposed to a single (e.g., vector) uniform
+#[proc_macro_derive(Uniforms, attributes(uniform))]
+pub fn derive_uniforms(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as DeriveInput);
+  expand_uniforms(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+fn expand_uniforms(input: DeriveInput) -> Result<TokenStream2> {
+  let Data::Struct(data) = &input.data else {
+    return Err(Error::new_spanned(
+      &input.ident,
+      "`Uniforms` can only be derived for structs",
+    ))
+  };
+  let Fields::Named(fields) = &data.fields else {
+    return Err(Error::new_spanned(
+      &input.ident,
+      "`Uniforms` can only be derived for structs with named fields",
+    ))
+  };
+
+  let mut handles = Vec::new();
+  let mut resolves = Vec::new();
+  let mut uploads = Vec::new();
+
+  for (idx, field) in fields.named.iter().enumerate() {
+    let ident = field.ident.as_ref().unwrap();
+    let (name, array) = parse_attrs(field)?;
+    let name = name.unwrap_or_else(|| ident.to_string());
+    let index = Index::from(idx);
+
+    let (ty, value) = if array {
+      let elem = array_element(&field.ty)?;
+      (quote! { [#elem] }, quote! { &self.#ident[..] })
+    } else {
+      let ty = &field.ty;
+      (quote! { #ty }, quote! { &self.#ident })
+    };
+
+    let () = handles.push(quote! { ::xgl::Uniform<#ty> });
+    let () = resolves.push(quote! { program.uniform::<#ty>(#name)? });
+    let () = uploads.push(quote! { let () = handles.#index.set(#value); });
+  }
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let tokens = quote! {
+    impl #impl_generics ::xgl::Uniforms for #ident #ty_generics #where_clause {
+      type Handles = (#(#handles,)*);
+
+      fn resolve(
+        program: &::xgl::Program,
+      ) -> ::xgl::__private::Result<Self::Handles> {
+        ::xgl::__private::Ok((#(#resolves,)*))
+      }
+
+      fn upload(&self, handles: &Self::Handles) {
+        #(#uploads)*
+      }
+    }
+  };
+  Ok(tokens)
+}
+
+/// Parse the `#[uniform(...)]` attributes of a field, returning the
+/// uniform name override, if any, and whether the field maps to a
+/// uniform array.
+fn parse_attrs(field: &Field) -> Result<(Option<String>, bool)> {
+  let mut name = None;
+  let mut array = false;
+
+  for attr in field
+    .attrs
+    .iter()
+    .filter(|attr| attr.path().is_ident("uniform"))
+  {
+    let () = attr.parse_nested_meta(|meta| {
+      if meta.path.is_ident("name") {
+        name = Some(meta.value()?.parse::<LitStr>()?.value());
+        Ok(())
+      } else if meta.path.is_ident("array") {
+        array = true;
+        Ok(())
+      } else {
+        Err(meta.error("unsupported `uniform` attribute"))
+      }
+    })?;
+  }
+  Ok((name, array))
+}
+
+/// Retrieve the element type of an array or `Vec` type.
+fn array_element(ty: &Type) -> Result<&Type> {
+  match ty {
+    Type::Array(array) => return Ok(&array.elem),
+    Type::Path(path) if path.qself.is_none() => {
+      if let Some(segment) = path.path.segments.last() {
+        if segment.ident == "Vec" {
+          if let PathArguments::AngleBracketed(args) = &segment.arguments {
+            if let Some(GenericArgument::Type(elem)) = args.args.first() {
+              return Ok(elem)
+            }
+          }
+        }
+      }
+    },
+    _ => (),
+  }
+
+  Err(Error::new_spanned(
+    ty,
+    "uniform arrays have to be of array or `Vec` type",
+  ))
+}
+
+
+/// The arguments to `include_shader!`.
+#[cfg(feature = "naga")]
+struct IncludeShader {
+  /// The shader stage.
+  stage: Stage,
+  /// The path to the shader, relative to the crate's manifest
+  /// directory.
+  path: LitStr,
+}
+
+#[cfg(feature = "naga")]
+impl Parse for IncludeShader {
+  fn parse(input: ParseStream<'_>) -> Result<Self> {
+    let stage = input.parse::<Ident>()?;
+    let stage = match stage.to_string().as_str() {
+      "vertex" => Stage::Vertex,
+      "fragment" => Stage::Fragment,
+      _ => {
+        return Err(Error::new_spanned(
+          stage,
+          "expected shader stage `vertex` or `fragment`",
+        ))
+      },
+    };
+    let _comma = input.parse::<Token![,]>()?;
+    let path = input.parse::<LitStr>()?;
+    let _comma = input.parse::<Option<Token![,]>>()?;
+    Ok(Self { stage, path })
+  }
+}
+
+/// Include a shader file as a string, validating it at compile time.
+///
+/// The first argument is the shader stage (`vertex` or `fragment`), the
+/// second the path to the shader, relative to the crate's manifest
+/// directory. Invalid shaders cause a compile error listing all
+/// diagnostics.
+#[cfg(feature = "naga")]
+#[proc_macro]
+pub fn include_shader(input: TokenStream) -> TokenStream {
+  let input = parse_macro_input!(input as IncludeShader);
+  expand_include_shader(input)
+    .unwrap_or_else(Error::into_compile_error)
+    .into()
+}
+
+#[cfg(feature = "naga")]
+fn expand_include_shader(input: IncludeShader) -> Result<TokenStream2> {
+  let IncludeShader { stage, path } = input;
+  let dir = env::var("CARGO_MANIFEST_DIR").map_err(|err| {
+    Error::new_spanned(&path, format!("failed to find manifest directory: {err}"))
+  })?;
+  let file = Path::new(&dir).join(path.value());
+  let code = fs::read_to_string(&file).map_err(|err| {
+    Error::new_spanned(
+      &path,
+      format!("failed to read shader `{}`: {err}", file.display()),
+    )
+  })?;
+
+  if let Err(diagnostics) = validate(&code, stage) {
+    let message = diagnostics
+      .into_iter()
+      .map(|diagnostic| {
+        let mut location = path.value();
+        if let Some(line) = diagnostic.line {
+          location = format!("{location}:{line}");
+          if let Some(column) = diagnostic.column {
+            location = format!("{location}:{column}");
+          }
+        }
+        format!("{location}: error: {}", diagnostic.message)
+      })
+      .collect::<Vec<_>>()
+      .join("\n");
+    return Err(Error::new_spanned(
+      &path,
+      format!("invalid shader:\n{message}"),
+    ))
+  }
+
+  // Going through `include_str!` makes sure that the crate gets rebuilt
+  // when the shader changes.
+  let tokens = quote! {
+    ::core::include_str!(::core::concat!(::core::env!("CARGO_MANIFEST_DIR"), "/", #path))
+  };
+  Ok(tokens)
+}
+
+
+#[cfg(all(test, feature = "naga"))]
+mod tests {
+  use super::*;
+
+  use syn::parse_quote;
+
+
+  /// Check that `include_shader!` rejects invalid shaders.
+  #[test]
+  fn invalid_shader_inclusion() {
+    let input = parse_quote! { fragment, "data/invalid.frag" };
+    let err = expand_include_shader(input).unwrap_err();
+    let message = err.to_string();
+    assert!(message.starts_with("invalid shader:\n"), "{message}");
+    assert!(message.contains("data/invalid.frag:5"), "{message}");
+  }
+
+  /// Check that `include_shader!` reports missing files.
+  #[test]
+  fn missing_shader_inclusion() {
+    let input = parse_quote! { vertex, "data/missing.vert" };
+    let err = expand_include_shader(input).unwrap_err();
+    assert!(err.to_string().contains("failed to read shader"), "{err}");
+  }
+
+  /// Check that `include_shader!` rejects unknown shader stages.
+  #[test]
+  fn invalid_stage() {
+    let result = syn::parse2::<IncludeShader>(quote! { compute, "data/invalid.frag" });
+    let err = result.err().unwrap();
+    assert!(err.to_string().contains("expected shader stage"), "{err}");
+  }
+}
@@ -0,0 +1,751 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::collections::HashSet;
+use std::error::Error as StdError;
+
+use naga::front::glsl::Frontend;
+use naga::front::glsl::Options;
+use naga::valid::Capabilities;
+use naga::valid::ValidationFlags;
+use naga::valid::Validator;
+use naga::AddressSpace;
+use naga::ArraySize;
+use naga::Binding;
+use naga::Handle;
+use naga::Module;
+use naga::ScalarKind;
+use naga::ShaderStage;
+use naga::SourceLocation;
+use naga::Type;
+use naga::TypeInner;
+
+
+/// The name prefix of the blocks that plain uniforms get wrapped in.
+const UNIFORM_BLOCK_PREFIX: &str = "_xgl_uniforms_";
+/// The name suffix of the texture part of a split up sampler.
+const TEXTURE_SUFFIX: &str = "_xgl_texture";
+/// The name suffix of the sampler part of a split up sampler.
+const SAMPLER_SUFFIX: &str = "_xgl_sampler";
+
+
+/// A shader stage supported by offline validation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Stage {
+  /// A vertex shader.
+  Vertex,
+  /// A fragment shader.
+  Fragment,
+}
+
+
+/// An error found in a shader.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+  /// The (1-based) line the error refers to, if known.
+  pub line: Option<usize>,
+  /// The (1-based) column the error refers to, if known.
+  pub column: Option<usize>,
+  /// The error message.
+  pub message: String,
+}
+
+
+/// A variable declared by a shader.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Variable {
+  /// The variable's name.
+  pub name: String,
+  /// The variable's GLSL type, e.g., `vec3` or `sampler2D`.
+  pub type_: String,
+  /// The variable's array size (`1` for non-array variables).
+  pub size: usize,
+  /// The variable's location, for shader inputs and outputs.
+  pub location: Option<u32>,
+}
+
+
+/// A uniform block declared by a shader.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Block {
+  /// The block's name.
+  pub name: String,
+  /// The binding point explicitly assigned to the block, if any.
+  pub binding: Option<u32>,
+  /// The size of the block's data, in bytes.
+  pub size: usize,
+  /// The block's members.
+  pub members: Vec<Variable>,
+}
+
+
+/// Reflection information about a shader.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Reflection {
+  /// The shader's inputs.
+  ///
+  /// Inputs without an explicit location are assigned consecutive
+  /// unused locations, in declaration order.
+  pub inputs: Vec<Variable>,
+  /// The shader's outputs, with locations assigned as for inputs.
+  pub outputs: Vec<Variable>,
+  /// Uniforms not contained in uniform blocks.
+  pub uniforms: Vec<Variable>,
+  /// Uniform blocks.
+  pub blocks: Vec<Block>,
+}
+
+
+/// The result of translating a shader into the GLSL dialect understood
+/// by `naga`.
+#[derive(Debug, Default)]
+struct Translation {
+  /// The translated code.
+  code: String,
+  /// Whether a `#version` directive had to be prepended.
+  prepended: bool,
+  /// The (0-based) indices of all lines that got rewritten.
+  modified: HashSet<usize>,
+  /// All samplers along with their GLSL type.
+  samplers: Vec<(String, String)>,
+  /// All uniform blocks along with their explicit binding, if any.
+  blocks: Vec<(String, Option<u32>)>,
+}
+
+
+/// Split the layout qualifier off a declaration, returning its
+/// contents, if present, along with the remainder.
+fn split_layout(line: &str) -> (Option<&str>, &str) {
+  let line = line.trim_start();
+  line
+    .strip_prefix("layout")
+    .and_then(|rest| rest.trim_start().strip_prefix('('))
+    .and_then(|rest| rest.split_once(')'))
+    .map(|(layout, rest)| (Some(layout), rest.trim_start()))
+    .unwrap_or((None, line))
+}
+
+/// Retrieve the value of the layout qualifier `key`, if present.
+fn layout_value(layout: Option<&str>, key: &str) -> Option<u32> {
+  layout?.split(',').find_map(|qualifier| {
+    let (name, value) = qualifier.split_once('=')?;
+    (name.trim() == key).then(|| value.trim().parse().ok())?
+  })
+}
+
+/// Format a declaration with `qualifier` added to its layout.
+fn with_layout(layout: Option<&str>, qualifier: &str, rest: &str) -> String {
+  match layout {
+    Some(layout) => format!("layout({layout}, {qualifier}) {rest}"),
+    None => format!("layout({qualifier}) {rest}"),
+  }
+}
+
+/// Split a declaration into its storage qualifier (e.g., `in`) and the
+/// remainder, skipping over interpolation and invariance qualifiers.
+fn split_storage(decl: &str) -> Option<(&str, &str)> {
+  let mut rest = decl;
+  loop {
+    let (word, remainder) = rest.split_once(char::is_whitespace)?;
+    match word {
+      "flat" | "smooth" | "noperspective" | "centroid" | "invariant" => {
+        rest = remainder.trim_start();
+      },
+      _ => return Some((word, remainder.trim_start())),
+    }
+  }
+}
+
+/// Split a variable declaration (without qualifiers) into its type and
+/// the declarators, skipping over precision qualifiers.
+fn split_type(decl: &str) -> Option<(&str, &str)> {
+  let mut rest = decl;
+  loop {
+    let (word, remainder) = rest.split_once(char::is_whitespace)?;
+    match word {
+      "highp" | "mediump" | "lowp" => rest = remainder.trim_start(),
+      _ => return Some((word, remainder.trim_start())),
+    }
+  }
+}
+
+/// Retrieve the names of all variables declared by `declarators`, e.g.
+/// `a, b[2];`, along with their array sizes, if constant.
+fn declarators(declarators: &str) -> Vec<(&str, Option<usize>)> {
+  let declarators = declarators
+    .split_once(';')
+    .map_or(declarators, |(declarators, _)| declarators);
+  declarators
+    .split(',')
+    .map(|declarator| {
+      let declarator = declarator.trim();
+      match declarator.split_once('[') {
+        Some((name, size)) => {
+          let size = size.trim_end_matches(']').trim().parse().ok();
+          (name.trim(), size)
+        },
+        None => (declarator, Some(1)),
+      }
+    })
+    .collect()
+}
+
+/// Retrieve the number of locations occupied by a variable of the given
+/// type.
+fn location_count(type_: &str) -> usize {
+  type_
+    .strip_prefix("mat")
+    .and_then(|dims| dims.bytes().next())
+    .map_or(1, |columns| usize::from(columns - b'0'))
+}
+
+/// Map a GLSL sampler type to the `naga` texture and sampler types it
+/// gets split into.
+fn split_sampler(type_: &str) -> Option<(String, &'static str)> {
+  let (prefix, base) = ["", "i", "u"]
+    .into_iter()
+    .find_map(|prefix| Some((prefix, type_.strip_prefix(prefix)?.strip_prefix("sampler")?)))?;
+  let (dim, sampler) = match base.strip_suffix("Shadow") {
+    Some(dim) if prefix.is_empty() => (dim, "samplerShadow"),
+    Some(_) => return None,
+    None => (base, "sampler"),
+  };
+
+  if !matches!(dim, "2D" | "3D" | "Cube" | "2DArray") {
+    return None
+  }
+  Some((format!("{prefix}texture{dim}"), sampler))
+}
+
+/// Allocate `count` consecutive locations not yet in `used`.
+fn allocate(used: &mut HashSet<u32>, count: usize) -> u32 {
+  let count = u32::try_from(count).unwrap_or(1);
+  let start = (0..)
+    .find(|start| (*start..start + count).all(|location| !used.contains(&location)))
+    .unwrap();
+  let () = used.extend(start..start + count);
+  start
+}
+
+/// Rewrite identifiers in `line` that `naga` understands differently.
+fn rewrite_identifiers(line: &str, samplers: &[(String, String)]) -> String {
+  let bytes = line.as_bytes();
+  let mut rewritten = String::with_capacity(line.len());
+  let mut pos = 0;
+
+  while pos < bytes.len() {
+    let start = pos;
+    let c = bytes[pos];
+
+    if c.is_ascii_alphanumeric() || c == b'_' {
+      pos += bytes[pos..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == b'_')
+        .count();
+      let ident = &line[start..pos];
+      if let Some((name, type_)) = samplers.iter().find(|(name, _)| name == ident) {
+        rewritten.push_str(&format!(
+          "{type_}({name}{TEXTURE_SUFFIX}, {name}{SAMPLER_SUFFIX})"
+        ));
+      } else {
+        let ident = match ident {
+          // GLSL 1.x texture lookup functions, which are accepted by
+          // the runtime as well.
+          "texture2D" | "texture3D" | "textureCube" | "shadow2D" => "texture",
+          "texture2DProj" | "texture3DProj" | "shadow2DProj" => "textureProj",
+          "texture2DLod" | "texture3DLod" | "textureCubeLod" | "shadow2DLod" => "textureLod",
+          "texture2DProjLod" | "texture3DProjLod" => "textureProjLod",
+          "gl_VertexID" => "int(gl_VertexIndex)",
+          "gl_InstanceID" => "int(gl_InstanceIndex)",
+          ident => ident,
+        };
+        let () = rewritten.push_str(ident);
+      }
+    } else {
+      pos += line[pos..].chars().next().map_or(1, char::len_utf8);
+      let () = rewritten.push_str(&line[start..pos]);
+    }
+  }
+  rewritten
+}
+
+/// Translate GLSL 3.30 or GLSL ES 3.00 code into the Vulkan flavored
+/// GLSL 4.50 understood by `naga`, preserving line numbers.
+///
+/// Specifically:
+/// - plain uniforms are wrapped in uniform blocks
+/// - samplers are split into textures and samplers
+/// - all resources are assigned bindings
+/// - all shader inputs and outputs are assigned locations
+fn translate(code: &str) -> Translation {
+  let mut translation = Translation::default();
+  let mut lines = code.lines().map(str::to_string).collect::<Vec<_>>();
+
+  match lines.iter().position(|line| !line.trim().is_empty()) {
+    Some(idx) if lines[idx].trim_start().starts_with("#version") => {
+      lines[idx] = "#version 450 core".to_string();
+      let _inserted = translation.modified.insert(idx);
+    },
+    _ => translation.prepended = true,
+  }
+
+  // Collect explicitly assigned locations first, so that we do not
+  // hand them out again.
+  let mut inputs = HashSet::new();
+  let mut outputs = HashSet::new();
+  for line in &lines {
+    let (layout, decl) = split_layout(line);
+    if let (Some(location), Some((storage, _))) =
+      (layout_value(layout, "location"), split_storage(decl))
+    {
+      match storage {
+        "in" => inputs.insert(location),
+        "out" => outputs.insert(location),
+        _ => false,
+      };
+    }
+  }
+
+  let mut depth = 0usize;
+  let mut binding = 0;
+  // The lines containing the split up sampler declarations.
+  let mut declarations = HashSet::new();
+  for (idx, line) in lines.iter_mut().enumerate() {
+    let code = line
+      .split_once("//")
+      .map_or(line.as_str(), |(code, _)| code);
+    let opening = code.matches('{').count();
+    let closing = code.matches('}').count();
+
+    if depth == 0 {
+      let (layout, decl) = split_layout(line);
+      let rewritten = if decl.starts_with("precision ") {
+        // Default precision qualifiers are meaningless here and `naga`
+        // does not understand all of them.
+        Some(String::new())
+      } else if let Some((storage, rest)) = split_storage(decl) {
+        match storage {
+          "uniform" if rest.contains('{') || !rest.contains(';') => {
+            // A uniform block.
+            let name = rest
+              .split(|c: char| !c.is_alphanumeric() && c != '_')
+              .next();
+            let explicit = layout_value(layout, "binding");
+            let () = translation
+              .blocks
+              .push((name.unwrap_or_default().to_string(), explicit));
+            if explicit.is_none() {
+              binding += 1;
+              Some(with_layout(layout, &format!("binding = {binding}"), decl))
+            } else {
+              None
+            }
+          },
+          "uniform" if !rest.contains('=') => {
+            let (type_, declarators_) = split_type(rest).unwrap_or((rest, ""));
+            if let Some((texture, sampler)) = split_sampler(type_) {
+              let names = declarators(declarators_);
+              if names.iter().all(|(_, size)| *size == Some(1)) {
+                let rewritten = names
+                  .into_iter()
+                  .map(|(name, _)| {
+                    let () = translation
+                      .samplers
+                      .push((name.to_string(), type_.to_string()));
+                    binding += 2;
+                    format!(
+                      "layout(binding = {}) uniform {texture} {name}{TEXTURE_SUFFIX}; \
+                       layout(binding = {binding}) uniform {sampler} {name}{SAMPLER_SUFFIX};",
+                      binding - 1,
+                    )
+                  })
+                  .collect::<Vec<_>>()
+                  .join(" ");
+                let _inserted = declarations.insert(idx);
+                Some(rewritten)
+              } else {
+                None
+              }
+            } else {
+              binding += 1;
+              Some(format!(
+                "layout(binding = {binding}) uniform {UNIFORM_BLOCK_PREFIX}{binding} {{ {rest} }};"
+              ))
+            }
+          },
+          storage @ ("in" | "out")
+            if !rest.contains('{') && layout_value(layout, "location").is_none() =>
+          {
+            let used = if storage == "in" {
+              &mut inputs
+            } else {
+              &mut outputs
+            };
+            let (type_, declarators_) = split_type(rest).unwrap_or((rest, ""));
+            let count = declarators(declarators_)
+              .iter()
+              .map(|(_, size)| size.unwrap_or(1) * location_count(type_))
+              .sum();
+            let location = allocate(used, count);
+            Some(with_layout(layout, &format!("location = {location}"), decl))
+          },
+          _ => None,
+        }
+      } else {
+        None
+      };
+
+      if let Some(rewritten) = rewritten {
+        *line = rewritten;
+        let _inserted = translation.modified.insert(idx);
+      }
+    }
+
+    depth = (depth + opening).saturating_sub(closing);
+  }
+
+  // Sampler variables are now split in two and have to be combined
+  // again where they are used.
+  let () = lines
+    .iter_mut()
+    .enumerate()
+    .filter(|(idx, _line)| !declarations.contains(idx))
+    .for_each(|(idx, line)| {
+      let rewritten = rewrite_identifiers(line, &translation.samplers);
+      if rewritten != *line {
+        *line = rewritten;
+        let _inserted = translation.modified.insert(idx);
+      }
+    });
+
+  if translation.prepended {
+    translation.code = "#version 450 core\n".to_string();
+  }
+  for line in lines {
+    let () = translation.code.push_str(&line);
+    let () = translation.code.push('\n');
+  }
+
+  translation
+}
+
+/// Convert a location in translated code into a diagnostic.
+fn diagnostic(
+  location: Option<SourceLocation>,
+  message: String,
+  translation: &Translation,
+) -> Diagnostic {
+  let line = location
+    .map(|location| location.line_number as usize)
+    .and_then(|line| line.checked_sub(usize::from(translation.prepended)))
+    .filter(|line| *line > 0);
+  // Columns on rewritten lines are meaningless.
+  let column = line
+    .filter(|line| !translation.modified.contains(&(line - 1)))
+    .and(location)
+    .map(|location| location.line_position as usize);
+
+  Diagnostic {
+    line,
+    column,
+    message,
+  }
+}
+
+/// Retrieve the GLSL name of a scalar kind's vector prefix.
+fn vector_prefix(kind: ScalarKind) -> Option<&'static str> {
+  match kind {
+    ScalarKind::Float => Some(""),
+    ScalarKind::Sint => Some("i"),
+    ScalarKind::Uint => Some("u"),
+    ScalarKind::Bool => Some("b"),
+    _ => None,
+  }
+}
+
+/// Retrieve the GLSL type name and array size of a `naga` type.
+fn glsl_type(module: &Module, ty: Handle<Type>) -> Option<(String, usize)> {
+  let type_ = match module.types[ty].inner {
+    TypeInner::Array {
+      base,
+      size: ArraySize::Constant(size),
+      ..
+    } => {
+      let (type_, _) = glsl_type(module, base)?;
+      return Some((type_, size.get() as usize))
+    },
+    TypeInner::Scalar(scalar) => match scalar.kind {
+      ScalarKind::Float => "float".to_string(),
+      ScalarKind::Sint => "int".to_string(),
+      ScalarKind::Uint => "uint".to_string(),
+      ScalarKind::Bool => "bool".to_string(),
+      _ => return None,
+    },
+    TypeInner::Vector { size, scalar } => {
+      format!("{}vec{}", vector_prefix(scalar.kind)?, size as u8)
+    },
+    TypeInner::Matrix { columns, rows, .. } => {
+      if columns == rows {
+        format!("mat{}", columns as u8)
+      } else {
+        format!("mat{}x{}", columns as u8, rows as u8)
+      }
+    },
+    _ => return None,
+  };
+  Some((type_, 1))
+}
+
+/// Create a [`Variable`] for a `naga` variable, if it is of a type
+/// expressible in GLSL.
+fn variable(
+  module: &Module,
+  name: Option<&String>,
+  ty: Handle<Type>,
+  location: Option<u32>,
+) -> Option<Variable> {
+  let (type_, size) = glsl_type(module, ty)?;
+  let variable = Variable {
+    name: name?.clone(),
+    type_,
+    size,
+    location,
+  };
+  Some(variable)
+}
+
+/// Gather reflection information from a translated module.
+fn reflect(module: &Module, translation: &Translation) -> Reflection {
+  let mut reflection = Reflection::default();
+
+  if let Some(entry_point) = module.entry_points.first() {
+    let function = &entry_point.function;
+    reflection.inputs = function
+      .arguments
+      .iter()
+      .filter_map(|argument| match argument.binding {
+        Some(Binding::Location { location, .. }) => {
+          variable(module, argument.name.as_ref(), argument.ty, Some(location))
+        },
+        _ => None,
+      })
+      .collect();
+
+    if let Some(result) = &function.result {
+      if let TypeInner::Struct { members, .. } = &module.types[result.ty].inner {
+        reflection.outputs = members
+          .iter()
+          .filter_map(|member| match member.binding {
+            Some(Binding::Location { location, .. }) => {
+              variable(module, member.name.as_ref(), member.ty, Some(location))
+            },
+            _ => None,
+          })
+          .collect();
+      }
+    }
+  }
+
+  for (_, global) in module.global_variables.iter() {
+    let type_ = &module.types[global.ty];
+    match (global.space, &type_.inner) {
+      (AddressSpace::Uniform, TypeInner::Struct { members, span }) => {
+        let members = members
+          .iter()
+          .filter_map(|member| variable(module, member.name.as_ref(), member.ty, None));
+        let name = type_.name.clone().unwrap_or_default();
+
+        if name.starts_with(UNIFORM_BLOCK_PREFIX) {
+          let () = reflection.uniforms.extend(members);
+        } else {
+          let binding = translation
+            .blocks
+            .iter()
+            .find_map(|(block, binding)| (*block == name).then_some(*binding))
+            .flatten();
+          let block = Block {
+            name,
+            binding,
+            size: *span as usize,
+            members: members.collect(),
+          };
+          let () = reflection.blocks.push(block);
+        }
+      },
+      (AddressSpace::Handle, _) => {
+        let sampler = global
+          .name
+          .as_deref()
+          .and_then(|name| name.strip_suffix(TEXTURE_SUFFIX))
+          .and_then(|name| {
+            translation
+              .samplers
+              .iter()
+              .find(|(sampler, _)| sampler == name)
+          });
+        if let Some((name, type_)) = sampler {
+          let variable = Variable {
+            name: name.clone(),
+            type_: type_.clone(),
+            size: 1,
+            location: None,
+          };
+          let () = reflection.uniforms.push(variable);
+        }
+      },
+      _ => (),
+    }
+  }
+  reflection
+}
+
+/// Validate a GLSL 3.30 or GLSL ES 3.00 shader, returning reflection
+/// information on success.
+///
+/// Line numbers in diagnostics refer to `code`. Uniforms are validated
+/// as declared, i.e., including unused ones.
+pub fn validate(code: &str, stage: Stage) -> Result<Reflection, Vec<Diagnostic>> {
+  let translation = translate(code);
+  let shader_stage = match stage {
+    Stage::Vertex => ShaderStage::Vertex,
+    Stage::Fragment => ShaderStage::Fragment,
+  };
+
+  let module = Frontend::default()
+    .parse(&Options::from(shader_stage), &translation.code)
+    .map_err(|errors| {
+      errors
+        .errors
+        .into_iter()
+        .map(|error| {
+          let location = error.location(&translation.code);
+          diagnostic(location, error.kind.to_string(), &translation)
+        })
+        .collect::<Vec<_>>()
+    })?;
+
+  let _info = Validator::new(ValidationFlags::all(), Capabilities::all())
+    .validate(&module)
+    .map_err(|error| {
+      let mut message = error.as_inner().to_string();
+      let mut source = error.as_inner().source();
+      while let Some(err) = source {
+        message.push_str(": ");
+        message.push_str(&err.to_string());
+        source = err.source();
+      }
+      let location = error.location(&translation.code);
+      vec![diagnostic(location, message, &translation)]
+    })?;
+
+  Ok(reflect(&module, &translation))
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that we can validate and reflect on a vertex shader.
+  #[test]
+  fn vertex_reflection() {
+    let code = r#"#version 330 core
+in vec4 position;
+layout(location = 4) in vec2 texture;
+in vec3 normal;
+
+uniform mat4 transform;
+uniform vec3 colors[2];
+
+layout(std140) uniform Light {
+  vec4 direction;
+  float intensity;
+};
+
+out vec2 uv;
+flat out int index;
+
+void main() {
+  uv = texture * intensity * normal.xy;
+  index = gl_VertexID;
+  gl_Position = transform * position + vec4(colors[0] + colors[1], 0.0f) * direction;
+}
+"#;
+    let reflection = validate(code, Stage::Vertex).unwrap();
+    let inputs = reflection
+      .inputs
+      .iter()
+      .map(|input| (input.name.as_str(), input.type_.as_str(), input.location))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      inputs,
+      [
+        ("position", "vec4", Some(0)),
+        ("texture", "vec2", Some(4)),
+        ("normal", "vec3", Some(1)),
+      ]
+    );
+    assert_eq!(reflection.outputs.len(), 2);
+
+    let uniforms = reflection
+      .uniforms
+      .iter()
+      .map(|uniform| (uniform.name.as_str(), uniform.type_.as_str(), uniform.size))
+      .collect::<Vec<_>>();
+    assert_eq!(uniforms, [("transform", "mat4", 1), ("colors", "vec3", 2)]);
+
+    assert_eq!(reflection.blocks.len(), 1);
+    let block = &reflection.blocks[0];
+    assert_eq!(block.name, "Light");
+    assert_eq!(block.binding, None);
+    assert_eq!(block.size, 32);
+    assert_eq!(block.members.len(), 2);
+  }
+
+  /// Check that we handle samplers and GLSL ES code.
+  #[test]
+  fn sampler_reflection() {
+    let code = r#"#version 300 es
+precision mediump float;
+precision highp sampler2DShadow;
+
+in vec2 uv;
+uniform sampler2D sampler;
+uniform highp sampler2DShadow shadow;
+out vec4 color;
+
+void main() {
+  color = texture(sampler, uv) * texture(shadow, vec3(uv, 0.5));
+}
+"#;
+    let reflection = validate(code, Stage::Fragment).unwrap();
+    let uniforms = reflection
+      .uniforms
+      .iter()
+      .map(|uniform| (uniform.name.as_str(), uniform.type_.as_str()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      uniforms,
+      [("sampler", "sampler2D"), ("shadow", "sampler2DShadow")]
+    );
+    assert_eq!(reflection.outputs[0].name, "color");
+  }
+
+  /// Check that errors are reported with line numbers referring to the
+  /// original code.
+  #[test]
+  fn error_reporting() {
+    let code = "#version 330 core\nout vec4 color;\nvoid main() {\n  color = foo;\n}\n";
+    let diagnostics = validate(code, Stage::Fragment).unwrap_err();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].line, Some(4));
+    assert_eq!(diagnostics[0].column, Some(11));
+    assert!(diagnostics[0].message.contains("foo"));
+
+    // Without a `#version` directive.
+    let code = "out vec4 color;\nvoid main() {\n  color = bar;\n}\n";
+    let diagnostics = validate(code, Stage::Fragment).unwrap_err();
+    assert_eq!(diagnostics[0].line, Some(3));
+  }
+}
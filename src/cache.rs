@@ -0,0 +1,259 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::sys;
+use crate::Program;
+use crate::Shader;
+use crate::ShaderSource;
+
+
+/// A set of `#define`s identifying a program permutation, normalized
+/// such that the order in which they were provided does not matter.
+type Permutation = BTreeMap<String, String>;
+
+fn permutation(defines: &[(&str, &str)]) -> Permutation {
+  defines
+    .iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect()
+}
+
+/// The key identifying a compiled shader: its type and preprocessed
+/// source code.
+type ShaderKey = (u32, String);
+
+/// The key identifying a linked program: the keys of the shaders it is
+/// made up of.
+type ProgramKey = Vec<ShaderKey>;
+
+
+/// A cache of [`Program`] permutations built from the same set of
+/// shader sources, but with different `#define`s.
+///
+/// Each permutation is compiled and linked on first use. Permutations
+/// are identified by their set of `#define`s, irrespective of order;
+/// if the same name is provided more than once, the last value wins.
+/// Defines replace those of the same name configured on the individual
+/// [`ShaderSource`] objects.
+///
+/// Shaders and programs are deduplicated by their preprocessed source
+/// code, meaning that permutations resulting in identical code
+/// share the same compiled shaders and linked program.
+#[derive(Debug)]
+pub struct ProgramCache<'r> {
+  /// The GL context.
+  context: sys::Context,
+  /// The type and source of each shader.
+  shaders: Vec<(sys::ShaderType, ShaderSource<'r>)>,
+  /// The key of the program of each permutation used so far.
+  permutations: HashMap<Permutation, ProgramKey>,
+  /// The shaders compiled so far, keyed by their type and preprocessed
+  /// source code.
+  compiled: HashMap<ShaderKey, Shader>,
+  /// The programs built so far, keyed by the keys of the shaders they
+  /// are made up of.
+  programs: HashMap<ProgramKey, Program>,
+}
+
+impl<'r> ProgramCache<'r> {
+  /// Create a new program cache for the provided shader sources.
+  ///
+  /// No shaders are compiled at this point.
+  pub fn new(shaders: Vec<(sys::ShaderType, ShaderSource<'r>)>, context: &sys::Context) -> Self {
+    Self {
+      context: context.clone(),
+      shaders,
+      permutations: HashMap::new(),
+      compiled: HashMap::new(),
+      programs: HashMap::new(),
+    }
+  }
+
+  /// Retrieve the program for the permutation with the provided
+  /// `#define`s, building it if it has not been used before.
+  pub fn program(&mut self, defines: &[(&str, &str)]) -> Result<&Program> {
+    let permutation = permutation(defines);
+    if !self.permutations.contains_key(&permutation) {
+      let key = self.build(&permutation)?;
+      let _prev = self.permutations.insert(permutation.clone(), key);
+    }
+    // SANITY: We made sure that the permutation is known above and we
+    //         only ever remember keys of programs we have built.
+    let key = self.permutations.get(&permutation).unwrap();
+    Ok(self.programs.get(key).unwrap())
+  }
+
+  /// Build the programs for all provided permutations up front, to
+  /// avoid stalls when they are first used.
+  pub fn precompile(&mut self, permutations: &[&[(&str, &str)]]) -> Result<()> {
+    for defines in permutations {
+      let _program = self.program(defines)?;
+    }
+    Ok(())
+  }
+
+  /// Retrieve the number of distinct programs built so far.
+  #[inline]
+  pub fn len(&self) -> usize {
+    self.programs.len()
+  }
+
+  /// Check whether no programs have been built so far.
+  #[inline]
+  pub fn is_empty(&self) -> bool {
+    self.programs.is_empty()
+  }
+
+  /// Build the program for the given permutation, unless one with the
+  /// same code exists already, returning its key.
+  fn build(&mut self, permutation: &Permutation) -> Result<ProgramKey> {
+    let describe = || {
+      permutation
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    };
+
+    let keys = self
+      .shaders
+      .iter()
+      .map(|(shader_type, source)| {
+        let source = permutation
+          .iter()
+          .fold(source.clone(), |source, (name, value)| {
+            source.set_define(name, value)
+          });
+        let preprocessed = source
+          .preprocess()
+          .with_context(|| format!("failed to preprocess shader `{}`", source.name()))?;
+
+        let key = (*shader_type as u32, preprocessed.source().to_string());
+        if let Entry::Vacant(entry) = self.compiled.entry(key.clone()) {
+          let shader = Shader::from_preprocessed(*shader_type, &preprocessed, &self.context)?;
+          let _shader = entry.insert(shader);
+        }
+        Ok(key)
+      })
+      .collect::<Result<Vec<_>>>()
+      .with_context(|| format!("failed to build program permutation `{}`", describe()))?;
+
+    if let Entry::Vacant(entry) = self.programs.entry(keys.clone()) {
+      // SANITY: All shaders have been compiled above.
+      let shaders = keys
+        .iter()
+        .map(|key| self.compiled.get(key).unwrap())
+        .collect::<Vec<_>>();
+      let program = Program::builder()
+        .set_context(&self.context)
+        .build_from(&shaders)
+        .with_context(|| format!("failed to build program permutation `{}`", describe()))?;
+      let _program = entry.insert(program);
+    }
+    Ok(keys)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use test_fork::fork;
+
+  use crate::winit::with_opengl_context;
+
+
+  /// Check that we build each program permutation only once.
+  #[fork]
+  #[test]
+  fn permutation_caching() {
+    let vertex = ShaderSource::new(
+      "vertex.glsl",
+      "in vec4 position;\nvoid main() { gl_Position = position * SCALE; }\n",
+    );
+    let fragment = ShaderSource::new(
+      "fragment.glsl",
+      r#"
+out vec4 color;
+void main() {
+#ifdef SHADOWED
+  color = vec4(0.5f);
+#else
+  color = vec4(1.0f);
+#endif
+}
+"#,
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let shaders = vec![
+        (sys::ShaderType::Vertex, vertex),
+        (sys::ShaderType::Fragment, fragment),
+      ];
+      let mut cache = ProgramCache::new(shaders, &gl_context);
+      assert!(cache.is_empty());
+
+      let () = cache
+        .precompile(&[
+          &[("SCALE", "1.0f")],
+          &[("SCALE", "1.0f"), ("SHADOWED", "1")],
+        ])
+        .unwrap();
+      assert_eq!(cache.len(), 2);
+
+      let () = cache
+        .program(&[("SHADOWED", "1"), ("SCALE", "1.0f")])
+        .unwrap()
+        .bind();
+      assert_eq!(cache.len(), 2);
+
+      let err = cache.program(&[]).unwrap_err();
+      assert!(err.to_string().contains("program permutation"), "{err:#}");
+      assert_eq!(cache.len(), 2);
+    })
+  }
+
+  /// Check that permutations resulting in the same code share a
+  /// program and that cache defines replace source defines.
+  #[fork]
+  #[test]
+  fn permutation_deduplication() {
+    let vertex = ShaderSource::new(
+      "vertex.glsl",
+      "in vec4 position;\nvoid main() { gl_Position = position * SCALE; }\n",
+    )
+    .set_define("SCALE", "1.0f");
+    let fragment = ShaderSource::new(
+      "fragment.glsl",
+      "out vec4 color;\nvoid main() { color = vec4(1.0f); }\n",
+    )
+    .set_define("SCALE", "1.0f");
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let shaders = vec![
+        (sys::ShaderType::Vertex, vertex),
+        (sys::ShaderType::Fragment, fragment),
+      ];
+      let mut cache = ProgramCache::new(shaders, &gl_context);
+
+      let () = cache.program(&[]).unwrap().bind();
+      let () = cache.program(&[("SCALE", "1.0f")]).unwrap().bind();
+      assert_eq!(cache.len(), 1);
+      assert_eq!(cache.compiled.len(), 2);
+
+      let () = cache.program(&[("SCALE", "2.0f")]).unwrap().bind();
+      assert_eq!(cache.len(), 2);
+      assert_eq!(cache.compiled.len(), 4);
+    })
+  }
+}
@@ -0,0 +1,663 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use anyhow::bail;
+use anyhow::ensure;
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::sys;
+use crate::TextureInfo;
+
+
+/// The identifier every KTX2 container starts with.
+const KTX2_IDENTIFIER: [u8; 12] = [
+  0xab, b'K', b'T', b'X', b' ', b'2', b'0', 0xbb, b'\r', b'\n', 0x1a, b'\n',
+];
+/// The size of the KTX2 header, including the index, in bytes.
+const KTX2_HEADER_SIZE: usize = 80;
+/// The size of a single KTX2 level index entry, in bytes.
+const KTX2_LEVEL_SIZE: usize = 24;
+
+/// The magic number every DDS container starts with.
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+/// The size of the DDS header, including the magic number, in bytes.
+const DDS_HEADER_SIZE: usize = 128;
+/// The size of the DX10 DDS header extension, in bytes.
+const DDS_DX10_HEADER_SIZE: usize = 20;
+/// The DDS pixel format flag indicating a `FourCC` code.
+const DDS_FOURCC: u32 = 0x4;
+/// The DDS capability flag indicating a cube map.
+const DDS_CUBEMAP: u32 = 0x200;
+/// The DDS capability flag indicating a volume texture.
+const DDS_VOLUME: u32 = 0x20_0000;
+/// The DX10 resource dimension of 2D textures.
+const DDS_DIMENSION_TEXTURE2D: u32 = 3;
+/// The DX10 misc flag indicating a cube map.
+const DDS_MISC_TEXTURECUBE: u32 = 0x4;
+
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+  let bytes = data
+    .get(offset..offset + 4)
+    .context("container data is truncated")?;
+  Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+  let bytes = data
+    .get(offset..offset + 8)
+    .context("container data is truncated")?;
+  Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+
+/// Retrieve the block width, block height, and block size (in bytes)
+/// of a compressed texture format.
+fn block_layout(format: sys::CompressedTextureFormat) -> (u32, u32, usize) {
+  use sys::CompressedTextureFormat as F;
+
+  match format {
+    F::BC1RGB
+    | F::BC1SRGB
+    | F::BC1RGBA
+    | F::BC1SRGBA
+    | F::BC4
+    | F::BC4Signed
+    | F::ETC2RGB8
+    | F::ETC2SRGB8
+    | F::ETC2RGB8A1
+    | F::ETC2SRGB8A1
+    | F::EACR11
+    | F::EACR11Signed => (4, 4, 8),
+    F::BC2
+    | F::BC2SRGB
+    | F::BC3
+    | F::BC3SRGB
+    | F::BC5
+    | F::BC5Signed
+    | F::BC6H
+    | F::BC6HSigned
+    | F::BC7
+    | F::BC7SRGB
+    | F::ETC2RGBA8
+    | F::ETC2SRGBA8
+    | F::EACRG11
+    | F::EACRG11Signed
+    | F::ASTC4x4
+    | F::ASTC4x4SRGB => (4, 4, 16),
+    F::ASTC5x5 | F::ASTC5x5SRGB => (5, 5, 16),
+    F::ASTC6x6 | F::ASTC6x6SRGB => (6, 6, 16),
+    F::ASTC8x8 | F::ASTC8x8SRGB => (8, 8, 16),
+  }
+}
+
+/// Retrieve the size of a compressed image of the given size, in
+/// bytes, if it can be represented.
+pub(crate) fn compressed_size(
+  format: sys::CompressedTextureFormat,
+  width: u32,
+  height: u32,
+) -> Option<usize> {
+  let (block_width, block_height, block_size) = block_layout(format);
+  usize::try_from(width.div_ceil(block_width))
+    .ok()?
+    .checked_mul(usize::try_from(height.div_ceil(block_height)).ok()?)?
+    .checked_mul(block_size)
+}
+
+/// Retrieve the maximum number of mipmap levels of an image of the
+/// given size.
+fn max_levels(width: u32, height: u32) -> u32 {
+  width.max(height).max(1).ilog2() + 1
+}
+
+/// Check that a container's level count is possible for an image of
+/// the given size.
+fn validate_level_count(level_count: u32, width: u32, height: u32) -> Result<()> {
+  let max_levels = max_levels(width, height);
+  ensure!(
+    level_count <= max_levels,
+    "image of size {width}x{height} has {level_count} levels, but at most {max_levels} are possible"
+  );
+  Ok(())
+}
+
+/// Retrieve a [`TextureInfo`] describing the uncompressed data that
+/// reading back a texture of the given compressed format produces.
+pub(crate) fn decompressed_info(
+  format: sys::CompressedTextureFormat,
+  width: u32,
+  height: u32,
+) -> TextureInfo {
+  use sys::CompressedTextureFormat as F;
+  use sys::TextureInternalFormat as I;
+  use sys::TexturePixelFormat as P;
+
+  let (intern_format, pixel_format, color_format) = match format {
+    F::BC4 | F::BC4Signed | F::EACR11 | F::EACR11Signed => {
+      (I::Gray8, P::Gray, sys::Type::UnsignedByte)
+    },
+    F::BC5 | F::BC5Signed | F::EACRG11 | F::EACRG11Signed => {
+      (I::RG8, P::RG, sys::Type::UnsignedByte)
+    },
+    F::BC6H | F::BC6HSigned => (I::RGB16F, P::RGB, sys::Type::Float),
+    F::BC1RGB | F::ETC2RGB8 => (I::RGB8, P::RGB, sys::Type::UnsignedByte),
+    F::BC1SRGB | F::ETC2SRGB8 => (I::SRGB8, P::RGB, sys::Type::UnsignedByte),
+    F::BC1SRGBA
+    | F::BC2SRGB
+    | F::BC3SRGB
+    | F::BC7SRGB
+    | F::ETC2SRGB8A1
+    | F::ETC2SRGBA8
+    | F::ASTC4x4SRGB
+    | F::ASTC5x5SRGB
+    | F::ASTC6x6SRGB
+    | F::ASTC8x8SRGB => (I::SRGBA8, P::RGBA, sys::Type::UnsignedByte),
+    F::BC1RGBA
+    | F::BC2
+    | F::BC3
+    | F::BC7
+    | F::ETC2RGB8A1
+    | F::ETC2RGBA8
+    | F::ASTC4x4
+    | F::ASTC5x5
+    | F::ASTC6x6
+    | F::ASTC8x8 => (I::RGBA8, P::RGBA, sys::Type::UnsignedByte),
+  };
+
+  TextureInfo::new(width, height, intern_format, pixel_format, color_format)
+}
+
+/// Map a Vulkan format, as used by KTX2 containers, to a compressed
+/// texture format.
+fn ktx2_format(vk_format: u32) -> Option<sys::CompressedTextureFormat> {
+  use sys::CompressedTextureFormat as F;
+
+  let format = match vk_format {
+    131 => F::BC1RGB,
+    132 => F::BC1SRGB,
+    133 => F::BC1RGBA,
+    134 => F::BC1SRGBA,
+    135 => F::BC2,
+    136 => F::BC2SRGB,
+    137 => F::BC3,
+    138 => F::BC3SRGB,
+    139 => F::BC4,
+    140 => F::BC4Signed,
+    141 => F::BC5,
+    142 => F::BC5Signed,
+    143 => F::BC6H,
+    144 => F::BC6HSigned,
+    145 => F::BC7,
+    146 => F::BC7SRGB,
+    147 => F::ETC2RGB8,
+    148 => F::ETC2SRGB8,
+    149 => F::ETC2RGB8A1,
+    150 => F::ETC2SRGB8A1,
+    151 => F::ETC2RGBA8,
+    152 => F::ETC2SRGBA8,
+    153 => F::EACR11,
+    154 => F::EACR11Signed,
+    155 => F::EACRG11,
+    156 => F::EACRG11Signed,
+    157 => F::ASTC4x4,
+    158 => F::ASTC4x4SRGB,
+    161 => F::ASTC5x5,
+    162 => F::ASTC5x5SRGB,
+    165 => F::ASTC6x6,
+    166 => F::ASTC6x6SRGB,
+    171 => F::ASTC8x8,
+    172 => F::ASTC8x8SRGB,
+    _ => return None,
+  };
+  Some(format)
+}
+
+/// Map a DXGI format, as used by DDS containers with DX10 header, to a
+/// compressed texture format.
+fn dxgi_format(dxgi_format: u32) -> Option<sys::CompressedTextureFormat> {
+  use sys::CompressedTextureFormat as F;
+
+  let format = match dxgi_format {
+    71 => F::BC1RGBA,
+    72 => F::BC1SRGBA,
+    74 => F::BC2,
+    75 => F::BC2SRGB,
+    77 => F::BC3,
+    78 => F::BC3SRGB,
+    80 => F::BC4,
+    81 => F::BC4Signed,
+    83 => F::BC5,
+    84 => F::BC5Signed,
+    95 => F::BC6H,
+    96 => F::BC6HSigned,
+    98 => F::BC7,
+    99 => F::BC7SRGB,
+    _ => return None,
+  };
+  Some(format)
+}
+
+
+/// A block-compressed image, including its mipmap levels.
+///
+/// Images are typically loaded from KTX2 or DDS containers and turned
+/// into a [`Texture`][crate::Texture] using
+/// [`TextureBuilder::from_compressed`][crate::TextureBuilder::from_compressed].
+#[derive(Clone, Debug)]
+pub struct CompressedImage {
+  /// The image's compressed format.
+  format: sys::CompressedTextureFormat,
+  /// The width of the base level.
+  width: u32,
+  /// The height of the base level.
+  height: u32,
+  /// The number of array layers.
+  layers: u32,
+  /// The data of each mipmap level, starting with the base level.
+  levels: Vec<Vec<u8>>,
+}
+
+impl CompressedImage {
+  /// Create a new compressed image from raw block data.
+  ///
+  /// `levels` contains the data of each mipmap level, starting with
+  /// the base level. Each level contains the data of all `layers`
+  /// back-to-back. Levels beyond the base level are optional, but have
+  /// to form a prefix of the full mipmap chain.
+  pub fn new(
+    format: sys::CompressedTextureFormat,
+    width: u32,
+    height: u32,
+    layers: u32,
+    levels: Vec<Vec<u8>>,
+  ) -> Result<Self> {
+    ensure!(
+      width > 0 && height > 0 && layers > 0,
+      "compressed image of size {width}x{height} with {layers} layers is empty"
+    );
+    ensure!(!levels.is_empty(), "compressed image has no levels");
+
+    let max_levels = max_levels(width, height) as usize;
+    ensure!(
+      levels.len() <= max_levels,
+      "compressed image of size {width}x{height} has {} levels, but at most {max_levels} are possible",
+      levels.len()
+    );
+
+    let () = levels.iter().enumerate().try_for_each(|(level, data)| {
+      let w = (width >> level).max(1);
+      let h = (height >> level).max(1);
+      let size = compressed_size(format, w, h)
+        .and_then(|size| size.checked_mul(layers as usize))
+        .with_context(|| format!("level {level} of {w}x{h}x{layers} {format:?} image is too large"))?;
+      ensure!(
+        data.len() == size,
+        "level {level} data of {} bytes does not match {w}x{h}x{layers} {format:?} image of {size} bytes",
+        data.len()
+      );
+      Ok(())
+    })?;
+
+    let slf = Self {
+      format,
+      width,
+      height,
+      layers,
+      levels,
+    };
+    Ok(slf)
+  }
+
+  /// Load a compressed image from the contents of a KTX2 container.
+  ///
+  /// Only 2D images and 2D array images without supercompression are
+  /// supported.
+  pub fn from_ktx2(data: &[u8]) -> Result<Self> {
+    ensure!(
+      data.starts_with(&KTX2_IDENTIFIER),
+      "data is not a KTX2 container"
+    );
+
+    let vk_format = read_u32(data, 12)?;
+    let width = read_u32(data, 20)?;
+    let height = read_u32(data, 24)?;
+    let depth = read_u32(data, 28)?;
+    let layers = read_u32(data, 32)?;
+    let faces = read_u32(data, 36)?;
+    let level_count = read_u32(data, 40)?;
+    let supercompression = read_u32(data, 44)?;
+
+    ensure!(
+      supercompression == 0,
+      "supercompressed KTX2 containers are not supported (scheme {supercompression})"
+    );
+    ensure!(depth <= 1, "3D KTX2 images are not supported");
+    ensure!(faces == 1, "KTX2 cube maps are not supported");
+    let format = ktx2_format(vk_format)
+      .with_context(|| format!("KTX2 format {vk_format} is not supported"))?;
+    let () = validate_level_count(level_count, width, height)?;
+
+    // A level count of zero requests mipmap generation, which is not
+    // possible for compressed images. Just use the base level then.
+    let levels = (0..level_count.max(1) as usize)
+      .map(|level| {
+        let index = KTX2_HEADER_SIZE + level * KTX2_LEVEL_SIZE;
+        let offset = usize::try_from(read_u64(data, index)?)
+          .with_context(|| format!("KTX2 level {level} offset is out of range"))?;
+        let length = usize::try_from(read_u64(data, index + 8)?)
+          .with_context(|| format!("KTX2 level {level} length is out of range"))?;
+        let data = offset
+          .checked_add(length)
+          .and_then(|end| data.get(offset..end))
+          .with_context(|| format!("KTX2 level {level} exceeds container data"))?;
+        Ok(data.to_vec())
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Self::new(format, width, height, layers.max(1), levels)
+      .context("KTX2 container contains invalid image data")
+  }
+
+  /// Load a compressed image from the contents of a DDS container.
+  ///
+  /// Only block-compressed 2D images and 2D array images are
+  /// supported.
+  pub fn from_dds(data: &[u8]) -> Result<Self> {
+    ensure!(data.starts_with(DDS_MAGIC), "data is not a DDS container");
+    ensure!(read_u32(data, 4)? == 124, "DDS header is invalid");
+
+    let height = read_u32(data, 12)?;
+    let width = read_u32(data, 16)?;
+    let level_count = read_u32(data, 28)?.max(1);
+    let pixel_flags = read_u32(data, 80)?;
+    let fourcc = data.get(84..88).context("container data is truncated")?;
+    let caps2 = read_u32(data, 112)?;
+
+    ensure!(
+      pixel_flags & DDS_FOURCC != 0,
+      "uncompressed DDS images are not supported"
+    );
+    ensure!(caps2 & DDS_CUBEMAP == 0, "DDS cube maps are not supported");
+    ensure!(caps2 & DDS_VOLUME == 0, "3D DDS images are not supported");
+
+    let (format, layers, mut offset) = match fourcc {
+      b"DX10" => {
+        let dxgi = read_u32(data, DDS_HEADER_SIZE)?;
+        let dimension = read_u32(data, DDS_HEADER_SIZE + 4)?;
+        let misc = read_u32(data, DDS_HEADER_SIZE + 8)?;
+        let layers = read_u32(data, DDS_HEADER_SIZE + 12)?;
+        ensure!(
+          dimension == DDS_DIMENSION_TEXTURE2D,
+          "DDS resource dimension {dimension} is not supported"
+        );
+        ensure!(
+          misc & DDS_MISC_TEXTURECUBE == 0,
+          "DDS cube maps are not supported"
+        );
+        let format =
+          dxgi_format(dxgi).with_context(|| format!("DXGI format {dxgi} is not supported"))?;
+        (
+          format,
+          layers.max(1),
+          DDS_HEADER_SIZE + DDS_DX10_HEADER_SIZE,
+        )
+      },
+      b"DXT1" => (sys::CompressedTextureFormat::BC1RGBA, 1, DDS_HEADER_SIZE),
+      b"DXT2" | b"DXT3" => (sys::CompressedTextureFormat::BC2, 1, DDS_HEADER_SIZE),
+      b"DXT4" | b"DXT5" => (sys::CompressedTextureFormat::BC3, 1, DDS_HEADER_SIZE),
+      b"ATI1" | b"BC4U" => (sys::CompressedTextureFormat::BC4, 1, DDS_HEADER_SIZE),
+      b"BC4S" => (sys::CompressedTextureFormat::BC4Signed, 1, DDS_HEADER_SIZE),
+      b"ATI2" | b"BC5U" => (sys::CompressedTextureFormat::BC5, 1, DDS_HEADER_SIZE),
+      b"BC5S" => (sys::CompressedTextureFormat::BC5Signed, 1, DDS_HEADER_SIZE),
+      _ => bail!(
+        "DDS format `{}` is not supported",
+        String::from_utf8_lossy(fourcc)
+      ),
+    };
+
+    let () = validate_level_count(level_count, width, height)?;
+
+    // DDS stores all levels of a layer before the next layer, whereas
+    // we keep all layers of a level together.
+    let mut levels = vec![Vec::new(); level_count as usize];
+    for layer in 0..layers {
+      for (level, level_data) in levels.iter_mut().enumerate() {
+        let w = (width >> level).max(1);
+        let h = (height >> level).max(1);
+        let image = compressed_size(format, w, h)
+          .and_then(|size| offset.checked_add(size))
+          .and_then(|end| data.get(offset..end))
+          .with_context(|| format!("DDS data for layer {layer} level {level} is truncated"))?;
+        let () = level_data.extend_from_slice(image);
+        offset += image.len();
+      }
+    }
+
+    Self::new(format, width, height, layers, levels)
+      .context("DDS container contains invalid image data")
+  }
+
+  /// Retrieve the image's compressed format.
+  #[inline]
+  pub fn format(&self) -> sys::CompressedTextureFormat {
+    self.format
+  }
+
+  /// Retrieve the width of the base level.
+  #[inline]
+  pub fn width(&self) -> u32 {
+    self.width
+  }
+
+  /// Retrieve the height of the base level.
+  #[inline]
+  pub fn height(&self) -> u32 {
+    self.height
+  }
+
+  /// Retrieve the number of array layers.
+  #[inline]
+  pub fn layers(&self) -> u32 {
+    self.layers
+  }
+
+  /// Retrieve the data of each mipmap level, starting with the base
+  /// level.
+  #[inline]
+  pub fn levels(&self) -> &[Vec<u8>] {
+    &self.levels
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Create a KTX2 container with the given properties.
+  fn ktx2(vk_format: u32, width: u32, height: u32, layers: u32, levels: &[&[u8]]) -> Vec<u8> {
+    let mut data = KTX2_IDENTIFIER.to_vec();
+    for value in [
+      vk_format,
+      1,
+      width,
+      height,
+      0,
+      layers,
+      1,
+      levels.len() as u32,
+      0,
+    ] {
+      let () = data.extend_from_slice(&value.to_le_bytes());
+    }
+    // Data format descriptor, key/value data, and supercompression
+    // global data.
+    let () = data.extend_from_slice(&[0; 32]);
+    assert_eq!(data.len(), KTX2_HEADER_SIZE);
+
+    let mut offset = KTX2_HEADER_SIZE + levels.len() * KTX2_LEVEL_SIZE;
+    for level in levels {
+      for value in [offset, level.len(), level.len()] {
+        let () = data.extend_from_slice(&(value as u64).to_le_bytes());
+      }
+      offset += level.len();
+    }
+    for level in levels {
+      let () = data.extend_from_slice(level);
+    }
+    data
+  }
+
+  /// Create a DDS container with the given `FourCC` code.
+  fn dds(fourcc: &[u8; 4], width: u32, height: u32, levels: u32, data: &[u8]) -> Vec<u8> {
+    let mut header = vec![0; DDS_HEADER_SIZE];
+    let () = header[..4].copy_from_slice(DDS_MAGIC);
+    let () = header[4..8].copy_from_slice(&124u32.to_le_bytes());
+    let () = header[12..16].copy_from_slice(&height.to_le_bytes());
+    let () = header[16..20].copy_from_slice(&width.to_le_bytes());
+    let () = header[28..32].copy_from_slice(&levels.to_le_bytes());
+    let () = header[80..84].copy_from_slice(&DDS_FOURCC.to_le_bytes());
+    let () = header[84..88].copy_from_slice(fourcc);
+    let () = header.extend_from_slice(data);
+    header
+  }
+
+
+  /// Check that we compute compressed image sizes correctly.
+  #[test]
+  fn compressed_sizes() {
+    use sys::CompressedTextureFormat as F;
+
+    assert_eq!(compressed_size(F::BC1RGB, 4, 4), Some(8));
+    assert_eq!(compressed_size(F::BC1RGB, 5, 4), Some(16));
+    assert_eq!(compressed_size(F::BC1RGB, 1, 1), Some(8));
+    assert_eq!(compressed_size(F::BC7, 8, 8), Some(64));
+    assert_eq!(compressed_size(F::ETC2RGBA8, 2, 2), Some(16));
+    assert_eq!(compressed_size(F::ASTC6x6, 12, 7), Some(64));
+  }
+
+  /// Check that we can load KTX2 containers.
+  #[test]
+  fn ktx2_loading() {
+    let levels = [&[1; 32][..], &[2; 8], &[3; 8]];
+    let data = ktx2(139, 8, 8, 0, &levels);
+    let image = CompressedImage::from_ktx2(&data).unwrap();
+    assert!(matches!(image.format(), sys::CompressedTextureFormat::BC4));
+    assert_eq!((image.width(), image.height(), image.layers()), (8, 8, 1));
+    assert_eq!(image.levels(), levels);
+
+    let levels = [&[1; 256][..]];
+    let data = ktx2(157, 8, 8, 4, &levels);
+    let image = CompressedImage::from_ktx2(&data).unwrap();
+    assert_eq!(image.layers(), 4);
+
+    let data = ktx2(139, 8, 8, 0, &[&[0; 31]]);
+    let err = CompressedImage::from_ktx2(&data).unwrap_err();
+    assert!(format!("{err:#}").contains("does not match"), "{err:#}");
+
+    let data = ktx2(37, 8, 8, 0, &[&[0; 256]]);
+    let err = CompressedImage::from_ktx2(&data).unwrap_err();
+    assert!(err.to_string().contains("not supported"), "{err:#}");
+
+    let err = CompressedImage::from_ktx2(&data[..40]).unwrap_err();
+    assert!(err.to_string().contains("truncated"), "{err:#}");
+    let err = CompressedImage::from_ktx2(b"foobar").unwrap_err();
+    assert!(err.to_string().contains("not a KTX2 container"), "{err:#}");
+  }
+
+  /// Check that we can load DDS containers.
+  #[test]
+  fn dds_loading() {
+    let data = dds(b"DXT5", 4, 4, 3, &[[1; 16], [2; 16], [3; 16]].concat());
+    let image = CompressedImage::from_dds(&data).unwrap();
+    assert!(matches!(image.format(), sys::CompressedTextureFormat::BC3));
+    assert_eq!((image.width(), image.height()), (4, 4));
+    assert_eq!(image.levels(), [[1; 16], [2; 16], [3; 16]]);
+
+    let data = dds(b"DXT1", 4, 4, 3, &[0; 23]);
+    let err = CompressedImage::from_dds(&data).unwrap_err();
+    assert!(err.to_string().contains("truncated"), "{err:#}");
+
+    let data = dds(b"ABCD", 4, 4, 1, &[0; 8]);
+    let err = CompressedImage::from_dds(&data).unwrap_err();
+    assert!(
+      err.to_string().contains("`ABCD` is not supported"),
+      "{err:#}"
+    );
+
+    let mut header = dds(b"DX10", 4, 4, 2, &[]);
+    for value in [98, DDS_DIMENSION_TEXTURE2D, 0, 2, 0] {
+      let () = header.extend_from_slice(&u32::to_le_bytes(value));
+    }
+    // Two layers with two levels each.
+    let data = [header, vec![1; 16], vec![2; 16], vec![3; 16], vec![4; 16]].concat();
+    let image = CompressedImage::from_dds(&data).unwrap();
+    assert!(matches!(image.format(), sys::CompressedTextureFormat::BC7));
+    assert_eq!(image.layers(), 2);
+    assert_eq!(
+      image.levels(),
+      [[[1; 16], [3; 16]].concat(), [[2; 16], [4; 16]].concat()]
+    );
+  }
+
+  /// Make sure that hostile container headers are reported as errors
+  /// instead of causing panics or excessive allocations.
+  #[test]
+  fn hostile_headers() {
+    let data = dds(b"DXT1", 4, 4, u32::MAX, &[0; 8]);
+    let err = CompressedImage::from_dds(&data).unwrap_err();
+    assert!(
+      err.to_string().contains("at most 3 are possible"),
+      "{err:#}"
+    );
+
+    let data = dds(b"DXT1", u32::MAX, u32::MAX, 32, &[0; 8]);
+    let err = CompressedImage::from_dds(&data).unwrap_err();
+    assert!(err.to_string().contains("truncated"), "{err:#}");
+
+    let mut data = ktx2(139, 8, 8, 0, &[&[0; 32]]);
+    let () = data[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = CompressedImage::from_ktx2(&data).unwrap_err();
+    assert!(
+      err.to_string().contains("at most 4 are possible"),
+      "{err:#}"
+    );
+
+    let mut data = ktx2(139, 8, 8, 0, &[&[0; 32]]);
+    let () = data[KTX2_HEADER_SIZE..KTX2_HEADER_SIZE + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+    let err = CompressedImage::from_ktx2(&data).unwrap_err();
+    assert!(err.to_string().contains("KTX2 level 0"), "{err:#}");
+
+    let data = ktx2(139, u32::MAX, u32::MAX, u32::MAX, &[&[0; 32]]);
+    let err = CompressedImage::from_ktx2(&data).unwrap_err();
+    assert!(format!("{err:#}").contains("too large"), "{err:#}");
+
+    // Randomly corrupt the headers of valid containers and make sure
+    // that nothing panics.
+    let ktx2 = ktx2(139, 8, 8, 0, &[&[1; 32], &[2; 8], &[3; 8]]);
+    let dds = dds(b"DXT5", 4, 4, 3, &[[1; 16], [2; 16], [3; 16]].concat());
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut random = || {
+      state ^= state << 13;
+      state ^= state >> 7;
+      state ^= state << 17;
+      state
+    };
+
+    for _ in 0..10_000 {
+      let mut ktx2 = ktx2.clone();
+      let mut dds = dds.clone();
+      for _ in 0..4 {
+        let index = random() as usize % (KTX2_HEADER_SIZE + 3 * KTX2_LEVEL_SIZE);
+        ktx2[index] = random() as u8;
+        let index = random() as usize % DDS_HEADER_SIZE;
+        dds[index] = random() as u8;
+      }
+      let _result = CompressedImage::from_ktx2(&ktx2);
+      let _result = CompressedImage::from_dds(&dds);
+    }
+  }
+}
@@ -0,0 +1,375 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::error::Error as StdError;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+
+/// The severity of a [`ShaderDiagnostic`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DiagnosticSeverity {
+  /// An error, causing compilation or linking to fail.
+  Error,
+  /// A warning.
+  Warning,
+  /// Any other message emitted by the driver.
+  Info,
+}
+
+impl DiagnosticSeverity {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Error => "error",
+      Self::Warning => "warning",
+      Self::Info => "info",
+    }
+  }
+}
+
+
+/// A diagnostic reported by the driver when compiling a shader or
+/// linking a program.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShaderDiagnostic {
+  /// The diagnostic's severity.
+  pub severity: DiagnosticSeverity,
+  /// The name of the file the diagnostic refers to.
+  ///
+  /// This is only known for shaders created from a
+  /// [`ShaderSource`][crate::ShaderSource].
+  pub file: Option<String>,
+  /// The (1-based) line the diagnostic refers to, if reported.
+  pub line: Option<usize>,
+  /// The (1-based) column the diagnostic refers to, if reported.
+  pub column: Option<usize>,
+  /// The diagnostic's message.
+  pub message: String,
+}
+
+impl ShaderDiagnostic {
+  /// Parse an info log as retrieved after compiling a shader or linking
+  /// a program.
+  ///
+  /// Mesa (`0:12(3): error: ...`), NVIDIA (`0(12) : error C0000:
+  /// ...`), and ANGLE (`ERROR: 0:12: ...`) formats are understood, as
+  /// are location-less `error: ...` lines. Lines in any other format
+  /// are attached to the preceding diagnostic or, if there is none,
+  /// reported with [`DiagnosticSeverity::Info`].
+  pub fn parse_log(log: &str) -> Vec<Self> {
+    let mut diagnostics = Vec::<Self>::new();
+
+    for line in log.lines() {
+      let line = line.trim_end_matches('\0').trim_end();
+      if line.is_empty() {
+        continue
+      }
+
+      if let Some(diagnostic) = Self::parse_line(line) {
+        let () = diagnostics.push(diagnostic);
+      } else if let Some(last) = diagnostics.last_mut() {
+        let () = last.message.push('\n');
+        let () = last.message.push_str(line);
+      } else {
+        let diagnostic = Self {
+          severity: DiagnosticSeverity::Info,
+          file: None,
+          line: None,
+          column: None,
+          message: line.to_string(),
+        };
+        let () = diagnostics.push(diagnostic);
+      }
+    }
+    diagnostics
+  }
+
+  fn parse_line(line: &str) -> Option<Self> {
+    // ANGLE: `ERROR: 0:12: message`
+    for (prefix, severity) in [
+      ("ERROR: ", DiagnosticSeverity::Error),
+      ("WARNING: ", DiagnosticSeverity::Warning),
+    ] {
+      if let Some(rest) = line.strip_prefix(prefix) {
+        let (line, rest) = parse_number(rest)
+          .and_then(|(_string, rest)| rest.strip_prefix(':'))
+          .and_then(parse_number)
+          .and_then(|(line, rest)| Some((Some(line), rest.strip_prefix(':')?)))
+          .unwrap_or((None, rest));
+
+        let diagnostic = Self {
+          severity,
+          file: None,
+          line,
+          column: None,
+          message: rest.trim().to_string(),
+        };
+        return Some(diagnostic)
+      }
+    }
+
+    let (line, column, rest) = if let Some((_string, rest)) = parse_number(line) {
+      if let Some(rest) = rest.strip_prefix(':') {
+        // Mesa: `0:12(3): error: message`
+        let (line, rest) = parse_number(rest)?;
+        let (column, rest) = rest
+          .strip_prefix('(')
+          .and_then(parse_number)
+          .and_then(|(column, rest)| Some((Some(column), rest.strip_prefix(')')?)))
+          .unwrap_or((None, rest));
+        (Some(line), column, rest.strip_prefix(':')?)
+      } else {
+        // NVIDIA: `0(12) : error C0000: message`
+        let (line, rest) = rest.strip_prefix('(').and_then(parse_number)?;
+        let rest = rest.strip_prefix(')')?.trim_start().strip_prefix(':')?;
+        (Some(line), None, rest)
+      }
+    } else {
+      (None, None, line)
+    };
+
+    let rest = rest.trim_start();
+    let (severity, rest) = [
+      ("error", DiagnosticSeverity::Error),
+      ("warning", DiagnosticSeverity::Warning),
+    ]
+    .into_iter()
+    .find_map(|(prefix, severity)| {
+      let rest = rest.strip_prefix(prefix)?;
+      // Skip over NVIDIA style error codes (e.g., `C0000`).
+      let rest = rest
+        .strip_prefix(' ')
+        .filter(|rest| rest.starts_with(|c: char| c.is_ascii_alphanumeric()))
+        .and_then(|rest| rest.split_once(':').map(|(_code, rest)| rest))
+        .or_else(|| rest.strip_prefix(':'))?;
+      Some((severity, rest))
+    })?;
+
+    let diagnostic = Self {
+      severity,
+      file: None,
+      line,
+      column,
+      message: rest.trim().to_string(),
+    };
+    Some(diagnostic)
+  }
+}
+
+impl Display for ShaderDiagnostic {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    let Self {
+      severity,
+      file,
+      line,
+      column,
+      message,
+    } = self;
+
+    if let Some(file) = file {
+      write!(f, "{file}:")?;
+    }
+    if let Some(line) = line {
+      write!(f, "{line}:")?;
+      if let Some(column) = column {
+        write!(f, "{column}:")?;
+      }
+    }
+    if file.is_some() || line.is_some() {
+      write!(f, " ")?;
+    }
+    write!(f, "{}: {message}", severity.as_str())
+  }
+}
+
+
+/// Parse a decimal number at the start of `s`, returning it along with
+/// the remainder of the string.
+fn parse_number(s: &str) -> Option<(usize, &str)> {
+  let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+  let number = s[..digits].parse().ok()?;
+  Some((number, &s[digits..]))
+}
+
+
+/// An error compiling a shader or linking a program.
+///
+/// Errors of this type are wrapped in the [`anyhow::Error`] reported by
+/// [`Shader`][crate::Shader] and [`Program`][crate::Program]
+/// constructors and can be retrieved via
+/// [`downcast_ref`][anyhow::Error::downcast_ref].
+#[derive(Clone, Debug)]
+pub struct ShaderError {
+  /// A description of the operation that failed.
+  operation: String,
+  /// The diagnostics reported by the driver.
+  diagnostics: Vec<ShaderDiagnostic>,
+  /// The source code line each diagnostic refers to, if known.
+  excerpts: Vec<Option<String>>,
+}
+
+impl ShaderError {
+  pub(crate) fn new(
+    operation: String,
+    diagnostics: Vec<ShaderDiagnostic>,
+    excerpts: Vec<Option<String>>,
+  ) -> Self {
+    debug_assert_eq!(diagnostics.len(), excerpts.len());
+    Self {
+      operation,
+      diagnostics,
+      excerpts,
+    }
+  }
+
+  /// Retrieve the diagnostics reported by the driver.
+  #[inline]
+  pub fn diagnostics(&self) -> &[ShaderDiagnostic] {
+    &self.diagnostics
+  }
+}
+
+impl Display for ShaderError {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    write!(f, "{}:", self.operation)?;
+
+    for (diagnostic, excerpt) in self.diagnostics.iter().zip(&self.excerpts) {
+      write!(f, "\n{diagnostic}")?;
+
+      if let (Some(excerpt), Some(line)) = (excerpt, diagnostic.line) {
+        let number = line.to_string();
+        let pad = " ".repeat(number.len());
+        // Point at the reported column, if any, or underline the
+        // line's content otherwise.
+        let (offset, len) = if let Some(column) = diagnostic.column {
+          (column.saturating_sub(1), 1)
+        } else {
+          let content = excerpt.trim_start();
+          (
+            excerpt.len() - content.len(),
+            content.trim_end().len().max(1),
+          )
+        };
+        write!(f, "\n {number} | {}", excerpt.trim_end())?;
+        write!(f, "\n {pad} | {}{}", " ".repeat(offset), "^".repeat(len))?;
+      }
+    }
+    Ok(())
+  }
+}
+
+impl StdError for ShaderError {}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that we can parse info logs in the common driver formats.
+  #[test]
+  fn log_parsing() {
+    let log = "\
+0:12(3): error: syntax error, unexpected IDENTIFIER
+0:4(10): warning: `foo' used uninitialized
+0(7) : error C0000: syntax error, unexpected identifier
+0(8) : warning C7011: implicit cast from \"int\" to \"float\"
+ERROR: 0:5: 'bar' : undeclared identifier
+WARNING: 0:6: extension not supported
+ERROR: 1 compilation errors.  No code generated.
+error: vertex shader output `uv' not read
+  additional context
+";
+    let diagnostics = ShaderDiagnostic::parse_log(log);
+    let expected = [
+      (
+        DiagnosticSeverity::Error,
+        Some(12),
+        Some(3),
+        "syntax error, unexpected IDENTIFIER",
+      ),
+      (
+        DiagnosticSeverity::Warning,
+        Some(4),
+        Some(10),
+        "`foo' used uninitialized",
+      ),
+      (
+        DiagnosticSeverity::Error,
+        Some(7),
+        None,
+        "syntax error, unexpected identifier",
+      ),
+      (
+        DiagnosticSeverity::Warning,
+        Some(8),
+        None,
+        "implicit cast from \"int\" to \"float\"",
+      ),
+      (
+        DiagnosticSeverity::Error,
+        Some(5),
+        None,
+        "'bar' : undeclared identifier",
+      ),
+      (
+        DiagnosticSeverity::Warning,
+        Some(6),
+        None,
+        "extension not supported",
+      ),
+      (
+        DiagnosticSeverity::Error,
+        None,
+        None,
+        "1 compilation errors.  No code generated.",
+      ),
+      (
+        DiagnosticSeverity::Error,
+        None,
+        None,
+        "vertex shader output `uv' not read\n  additional context",
+      ),
+    ];
+    assert_eq!(diagnostics.len(), expected.len());
+    for (diagnostic, (severity, line, column, message)) in diagnostics.iter().zip(expected) {
+      assert_eq!(diagnostic.severity, severity);
+      assert_eq!(diagnostic.file, None);
+      assert_eq!(diagnostic.line, line);
+      assert_eq!(diagnostic.column, column);
+      assert_eq!(diagnostic.message, message);
+    }
+
+    let diagnostics = ShaderDiagnostic::parse_log("something unexpected\0");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Info);
+    assert_eq!(diagnostics[0].message, "something unexpected");
+  }
+
+  /// Check that we annotate source lines when formatting a
+  /// `ShaderError`.
+  #[test]
+  fn error_formatting() {
+    let diagnostics =
+      ShaderDiagnostic::parse_log("0:12(5): error: syntax error\n0(3) : error C0000: oops");
+    let excerpts = vec![
+      Some("  not a valid identifier".to_string()),
+      Some("  foo();  ".to_string()),
+    ];
+    let err = ShaderError::new(
+      "failed to compile vertex shader".to_string(),
+      diagnostics,
+      excerpts,
+    );
+    let expected = "\
+failed to compile vertex shader:
+12:5: error: syntax error
+ 12 |   not a valid identifier
+    |     ^
+3: error: oops
+ 3 |   foo();
+   |   ^^^^^^";
+    assert_eq!(err.to_string(), expected);
+  }
+}
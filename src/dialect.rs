@@ -0,0 +1,302 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::borrow::Cow;
+
+use crate::source::extension_block_end;
+use crate::source::strip_comments;
+use crate::source::ES_PRECISION;
+use crate::sys;
+
+
+/// Translate shader code written for either GLSL 3.30 core or GLSL ES
+/// 3.00 into the dialect used by the current backend.
+///
+/// Only code starting with a `#version` directive for one of these
+/// dialects is touched. Line numbers are preserved.
+pub(crate) fn translate(code: &str, shader_type: sys::ShaderType) -> Cow<'_, str> {
+  let (.., suffix) = sys::version();
+  translate_to(code, shader_type, suffix == Some("es"))
+}
+
+/// Translate shader code into GLSL ES 3.00, if `es` is set, or GLSL
+/// 3.30 core otherwise.
+pub(crate) fn translate_to(code: &str, shader_type: sys::ShaderType, es: bool) -> Cow<'_, str> {
+  let mut lines = code.lines();
+  let Some(idx) = code.lines().position(|line| !line.trim().is_empty()) else {
+    return Cow::Borrowed(code)
+  };
+  let Some(version) = code
+    .lines()
+    .nth(idx)
+    .and_then(|line| line.trim_start().strip_prefix("#version"))
+  else {
+    return Cow::Borrowed(code)
+  };
+
+  let desktop = match version.split_whitespace().collect::<Vec<_>>().as_slice() {
+    ["330"] | ["330", "core"] => true,
+    ["300", "es"] => false,
+    _ => return Cow::Borrowed(code),
+  };
+
+  let mut translated = String::with_capacity(code.len());
+  let () = lines
+    .by_ref()
+    .take(idx)
+    .for_each(|_line| translated.push('\n'));
+  let _version = lines.next();
+  let lines = lines.collect::<Vec<_>>();
+
+  // Code written for desktop GLSL lacks default precision qualifiers,
+  // which have to follow any `#extension` directives.
+  let precision = if es && desktop && !has_float_precision(code) {
+    Some(extension_block_end(lines.iter().copied()))
+  } else {
+    None
+  };
+
+  if es {
+    let () = translated.push_str("#version 300 es\n");
+  } else {
+    let () = translated.push_str("#version 330 core\n");
+  }
+
+  for (i, line) in lines.iter().map(Some).chain([None]).enumerate() {
+    if precision == Some(i) {
+      // Report subsequent lines with their original line numbers.
+      let () = translated.push_str(&format!("{ES_PRECISION}\n#line {}\n", idx + 2 + i));
+    }
+    if let Some(line) = line {
+      let () = translate_line(line, shader_type, es, &mut translated);
+      let () = translated.push('\n');
+    }
+  }
+  Cow::Owned(translated)
+}
+
+/// Check whether `code` declares a default precision for `float`.
+fn has_float_precision(code: &str) -> bool {
+  let mut in_comment = false;
+  code.lines().any(|line| {
+    let line = strip_comments(line, &mut in_comment);
+    line.split(';').any(|statement| {
+      matches!(
+        statement.split_whitespace().collect::<Vec<_>>().as_slice(),
+        ["precision", "lowp" | "mediump" | "highp", "float"]
+      )
+    })
+  })
+}
+
+/// Translate a single line of shader code, appending the result to
+/// `translated`.
+fn translate_line(line: &str, shader_type: sys::ShaderType, es: bool, translated: &mut String) {
+  let line = strip_interface_location(line, shader_type);
+  let bytes = line.as_bytes();
+  let mut pos = 0;
+
+  while pos < bytes.len() {
+    let start = pos;
+    let c = bytes[pos];
+
+    if c.is_ascii_alphabetic() || c == b'_' {
+      pos += bytes[pos..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == b'_')
+        .count();
+      let ident = &line[start..pos];
+      // GLSL 1.x texture lookup functions have been replaced with
+      // overloaded versions in both dialects.
+      let ident = match ident {
+        "texture2D" | "texture3D" | "textureCube" | "shadow2D" => "texture",
+        "texture2DProj" | "texture3DProj" | "shadow2DProj" => "textureProj",
+        "texture2DLod" | "texture3DLod" | "textureCubeLod" | "shadow2DLod" => "textureLod",
+        "texture2DProjLod" | "texture3DProjLod" => "textureProjLod",
+        ident => ident,
+      };
+      let () = translated.push_str(ident);
+    } else if c.is_ascii_digit()
+      || (c == b'.' && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit))
+    {
+      let mut float = false;
+      while pos < bytes.len() {
+        match bytes[pos] {
+          b'0'..=b'9' => (),
+          b'.' => float = true,
+          b'e' | b'E' => {
+            float = true;
+            if matches!(bytes.get(pos + 1), Some(b'+' | b'-')) {
+              pos += 1;
+            }
+          },
+          _ => break,
+        }
+        pos += 1;
+      }
+      let number = &line[start..pos];
+      let suffix_len = bytes[pos..]
+        .iter()
+        .take_while(|c| c.is_ascii_alphanumeric() || **c == b'_')
+        .count();
+      let suffix = &line[pos..pos + suffix_len];
+      pos += suffix_len;
+
+      // Some GLSL ES drivers reject the `f` suffix on floating point
+      // literals.
+      if es && float && (suffix == "f" || suffix == "F") {
+        let () = translated.push_str(number);
+      } else {
+        let () = translated.push_str(&line[start..pos]);
+      }
+    } else {
+      pos += line[pos..].chars().next().map_or(1, char::len_utf8);
+      let () = translated.push_str(&line[start..pos]);
+    }
+  }
+}
+
+/// Strip `layout(location = N)` qualifiers from variables passed
+/// between shader stages, which neither dialect supports.
+fn strip_interface_location(line: &str, shader_type: sys::ShaderType) -> Cow<'_, str> {
+  let trimmed = line.trim_start();
+  let Some(layout) = trimmed.strip_prefix("layout") else {
+    return Cow::Borrowed(line)
+  };
+  let Some((qualifiers, rest)) = layout
+    .trim_start()
+    .strip_prefix('(')
+    .and_then(|layout| layout.split_once(')'))
+  else {
+    return Cow::Borrowed(line)
+  };
+
+  let is_location = qualifiers
+    .split_once('=')
+    .is_some_and(|(name, value)| name.trim() == "location" && value.trim().parse::<u32>().is_ok());
+  let storage = rest
+    .split_whitespace()
+    .find(|word| !matches!(*word, "flat" | "smooth" | "centroid"));
+  let interface = match shader_type {
+    sys::ShaderType::Vertex => storage == Some("out"),
+    sys::ShaderType::Fragment => storage == Some("in"),
+    sys::ShaderType::Geometry => matches!(storage, Some("in" | "out")),
+  };
+
+  if is_location && interface {
+    let indent = &line[..line.len() - trimmed.len()];
+    Cow::Owned(format!("{indent}{}", rest.trim_start()))
+  } else {
+    Cow::Borrowed(line)
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that desktop GLSL is translated into GLSL ES.
+  #[test]
+  fn desktop_to_es() {
+    let code = r#"
+#version 330 core
+layout(location = 0) in vec2 position;
+layout(location = 1) out vec2 uv;
+uniform sampler2D sampler;
+void main() {
+  uv = texture2D(sampler, position * 2.0f).xy + vec2(1e-3f, .5F) + vec2(float(1), 0x1F);
+}
+"#;
+    let translated = translate_to(code, sys::ShaderType::Vertex, true);
+    let expected = format!(
+      r#"
+#version 300 es
+{ES_PRECISION}
+#line 3
+layout(location = 0) in vec2 position;
+out vec2 uv;
+uniform sampler2D sampler;
+void main() {{
+  uv = texture(sampler, position * 2.0).xy + vec2(1e-3, .5) + vec2(float(1), 0x1F);
+}}
+"#
+    );
+    assert_eq!(translated, expected);
+  }
+
+  /// Check that GLSL ES is translated into desktop GLSL.
+  #[test]
+  fn es_to_desktop() {
+    let code = r#"#version 300 es
+precision mediump float;
+layout(location = 0) flat in int index;
+layout(location = 0) out vec4 color;
+void main() {
+  color = vec4(1.0f);
+}
+"#;
+    let translated = translate_to(code, sys::ShaderType::Fragment, false);
+    let expected = r#"#version 330 core
+precision mediump float;
+flat in int index;
+layout(location = 0) out vec4 color;
+void main() {
+  color = vec4(1.0f);
+}
+"#;
+    assert_eq!(translated, expected);
+
+    let translated = translate_to(code, sys::ShaderType::Fragment, true);
+    assert!(translated.starts_with("#version 300 es\nprecision mediump float;\n"));
+    assert!(translated.contains("color = vec4(1.0);"));
+  }
+
+  /// Check that default precision qualifiers are injected after any
+  /// `#extension` directives and only if none are present already.
+  #[test]
+  fn es_precision() {
+    let code = "#version 330 core\n#extension GL_EXT_foo : enable\n// precision highp float;\nvoid main() {}\n";
+    let translated = translate_to(code, sys::ShaderType::Fragment, true);
+    let expected = format!(
+      "#version 300 es\n#extension GL_EXT_foo : enable\n{ES_PRECISION}\n#line 3\n// precision highp float;\nvoid main() {{}}\n"
+    );
+    assert_eq!(translated, expected);
+
+    let code = "#version 330 core\n#extension GL_EXT_foo : enable\n";
+    let translated = translate_to(code, sys::ShaderType::Fragment, true);
+    let expected =
+      format!("#version 300 es\n#extension GL_EXT_foo : enable\n{ES_PRECISION}\n#line 3\n");
+    assert_eq!(translated, expected);
+
+    let code = "#version 330 core\nprecision  mediump float ;\nvoid main() {}\n";
+    let translated = translate_to(code, sys::ShaderType::Fragment, true);
+    assert_eq!(
+      translated,
+      "#version 300 es\nprecision  mediump float ;\nvoid main() {}\n"
+    );
+
+    // GLSL ES code is expected to declare precisions itself.
+    let code = "#version 300 es\nvoid main() {}\n";
+    let translated = translate_to(code, sys::ShaderType::Fragment, true);
+    assert_eq!(translated, code);
+  }
+
+  /// Make sure that code without a version directive or for other
+  /// versions is left untouched.
+  #[test]
+  fn no_translation() {
+    let code = "void main() { gl_FragColor = texture2D(s, uv); }\n";
+    assert!(matches!(
+      translate_to(code, sys::ShaderType::Fragment, true),
+      Cow::Borrowed(..)
+    ));
+
+    let code = "#version 450\nvoid main() { color = texture2D(s, uv); }\n";
+    assert!(matches!(
+      translate_to(code, sys::ShaderType::Fragment, false),
+      Cow::Borrowed(..)
+    ));
+  }
+}
@@ -46,6 +46,46 @@ impl Framebuffer {
     Ok(slf)
   }
 
+  /// Create a framebuffer rendering into a single face of a cube map.
+  ///
+  /// Faces are indexed as in [`sys::TextureTarget::CUBE_MAP_FACES`].
+  /// Depth cube maps are attached as depth map (and nothing else), all
+  /// others as the only color attachment.
+  pub fn with_cube_face(texture: &Texture, face: usize, context: &sys::Context) -> Result<Self> {
+    ensure!(
+      matches!(texture.target(), sys::TextureTarget::CubeMap),
+      "texture is not a cube map"
+    );
+    let target = *sys::TextureTarget::CUBE_MAP_FACES
+      .get(face)
+      .with_context(|| format!("cube map face {face} is out of bounds"))?;
+
+    let slf = Self {
+      context: context.clone(),
+      fbo: context
+        .create_framebuffer()
+        .context("failed to create framebuffer object")?,
+    };
+
+    let () = slf.bind();
+    if let sys::TexturePixelFormat::Depth = texture.info().pixel_format {
+      let () = context.set_framebuffer_depth_texture(target, texture);
+      let () = context.unset_draw_buffer();
+      let () = context.unset_read_buffer();
+    } else {
+      let level = 0;
+      let () = context.set_framebuffer_color_texture(0, target, texture, level);
+    }
+    let status = context.check_framebuffer_status();
+    let () = slf.unbind();
+
+    ensure!(
+      status == sys::FramebufferStatus::Complete,
+      "failed to complete framebuffer: status: {status}"
+    );
+    Ok(slf)
+  }
+
   /// Bind the framebuffer.
   pub fn bind(&self) {
     let () = self.context.bind_framebuffer(Some(&self.fbo));
@@ -87,4 +127,22 @@ mod tests {
       let _framebuffer = Framebuffer::with_depth_map(&depth_map, &gl_context).unwrap();
     })
   }
+
+  /// Check that we can render into individual cube map faces.
+  #[fork]
+  #[test]
+  fn cube_face_framebuffer_creation() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let depth_map = Texture::builder()
+        .set_seamless_cube_maps(true)
+        .set_context(&gl_context)
+        .new_depth_cube_map(256)
+        .unwrap();
+      for face in 0..6 {
+        let _framebuffer = Framebuffer::with_cube_face(&depth_map, face, &gl_context).unwrap();
+      }
+      assert!(Framebuffer::with_cube_face(&depth_map, 6, &gl_context).is_err());
+    })
+  }
 }
@@ -1,27 +1,75 @@
 // Copyright (C) 2025 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+mod cache;
+mod compressed;
+mod diagnostic;
+mod dialect;
 mod framebuffer;
 mod matrices;
+mod mesh;
+#[cfg(feature = "naga")]
+mod offline;
 mod program;
+#[cfg(not(target_arch = "wasm32"))]
+mod reload;
+mod sampler;
 mod shader;
+mod source;
 mod stack;
 mod texture;
+mod typed;
+mod uniform;
 mod vertices;
 #[cfg(test)]
 mod winit;
 
 pub mod sys;
 
+// Allow generated code to refer to this crate as `xgl` in tests.
+#[cfg(test)]
+extern crate self as xgl;
+
+pub use crate::cache::ProgramCache;
+pub use crate::compressed::CompressedImage;
+pub use crate::diagnostic::DiagnosticSeverity;
+pub use crate::diagnostic::ShaderDiagnostic;
+pub use crate::diagnostic::ShaderError;
 pub use crate::framebuffer::Framebuffer;
 pub use crate::matrices::MatrixStack;
+pub use crate::mesh::Mesh;
+#[cfg(feature = "naga")]
+pub use crate::offline::OfflineProgram;
+#[cfg(feature = "naga")]
+pub use crate::offline::OfflineShader;
+pub use crate::program::ActiveAttrib;
+pub use crate::program::ActiveUniform;
+pub use crate::program::ActiveUniformBlock;
+pub use crate::program::Builder as ProgramBuilder;
 pub use crate::program::Program;
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::reload::ReloadableProgram;
+pub use crate::sampler::Builder as SamplerBuilder;
+pub use crate::sampler::Sampler;
 pub use crate::shader::Shader;
+pub use crate::source::FsIncludeResolver;
+pub use crate::source::IncludeResolver;
+pub use crate::source::PreprocessedSource;
+pub use crate::source::ShaderSource;
 pub use crate::texture::Builder as TextureBuilder;
 pub use crate::texture::Texture;
 pub use crate::texture::TextureInfo;
+pub use crate::typed::TypedProgram;
+pub use crate::uniform::Uniform;
+pub use crate::uniform::UniformData;
+pub use crate::uniform::UniformElement;
+pub use crate::uniform::Uniforms;
 pub use crate::vertices::VertexArray;
 pub use crate::vertices::VertexBuffer;
+#[cfg(feature = "naga")]
+pub use xgl_derive::include_shader;
+#[cfg(feature = "derive")]
+pub use xgl_derive::Uniforms;
 
 pub mod vertex {
   pub use crate::vertices::Attrib;
@@ -32,3 +80,9 @@ pub mod vertex {
   pub use crate::vertices::VertexP3fT2f as P3fT2f;
   pub use crate::vertices::VertexP3fT2fN3f as P3fT2fN3f;
 }
+
+#[doc(hidden)]
+pub mod __private {
+  pub use anyhow::Result;
+  pub use core::result::Result::Ok;
+}
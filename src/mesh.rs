@@ -0,0 +1,346 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use anyhow::ensure;
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::sys;
+use crate::sys::BuiltinType;
+use crate::sys::Gl as _;
+use crate::vertex::AttribType;
+use crate::vertex::Attribs;
+use crate::VertexArray;
+use crate::VertexBuffer;
+
+
+/// A mesh, bundling vertex data, optional indices into it, and the
+/// primitive used for drawing them.
+#[derive(Debug)]
+pub struct Mesh<V, I = u16> {
+  /// The GL context.
+  context: sys::Context,
+  /// The buffer containing the mesh's vertices.
+  vertices: VertexBuffer<V>,
+  /// The buffer containing indices into `vertices`, if any.
+  indices: Option<VertexBuffer<I>>,
+  /// The vertex array object referencing `vertices` (and `indices`).
+  vertex_array: VertexArray,
+  /// The shader attribute index of each vertex attribute.
+  attrib_indices: Vec<(u32, AttribType)>,
+  /// The primitive to draw.
+  primitive: sys::Primitive,
+}
+
+impl<V, I> Mesh<V, I>
+where
+  V: Attribs,
+  I: BuiltinType<sys::Context>,
+{
+  /// Create a new mesh from the provided vertex and (optional) index
+  /// buffers.
+  ///
+  /// `attrib_indices` maps vertex attributes to shader attribute
+  /// indices, just as for [`VertexArray::new`].
+  pub fn new(
+    vertices: VertexBuffer<V>,
+    indices: Option<VertexBuffer<I>>,
+    primitive: sys::Primitive,
+    attrib_indices: &[(u32, AttribType)],
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let vertex_array = VertexArray::new(&vertices, attrib_indices, context)?;
+
+    if let Some(indices) = &indices {
+      // The element array buffer binding is part of the vertex array
+      // object's state. So bind it while the latter is bound and make
+      // sure to not unbind it before the vertex array object.
+      let () = vertex_array.bind();
+      let () = indices.bind();
+      let () = vertex_array.unbind();
+    }
+
+    let slf = Self {
+      context: context.clone(),
+      vertices,
+      indices,
+      vertex_array,
+      attrib_indices: attrib_indices.to_vec(),
+      primitive,
+    };
+    Ok(slf)
+  }
+
+  /// Retrieve the number of items to draw, i.e., the number of indices
+  /// for an indexed mesh and the number of vertices otherwise.
+  #[inline]
+  pub fn item_count(&self) -> usize {
+    self
+      .indices
+      .as_ref()
+      .map(VertexBuffer::item_count)
+      .unwrap_or_else(|| self.vertices.item_count())
+  }
+
+  /// Draw the entire mesh.
+  #[inline]
+  pub fn draw(&self) -> Result<()> {
+    self.draw_range(0, self.item_count())
+  }
+
+  /// Check that `count` items starting at item `start` are part of the
+  /// mesh and convert the range into the first item (or index byte
+  /// offset, for indexed meshes) and count expected by GL.
+  fn gl_range(&self, start: usize, count: usize) -> Result<(i32, i32)> {
+    let item_count = self.item_count();
+    ensure!(
+      start
+        .checked_add(count)
+        .is_some_and(|end| end <= item_count),
+      "range of {count} items starting at {start} exceeds mesh of {item_count} items"
+    );
+
+    let first = if self.indices.is_some() {
+      // The range is within the index buffer, so this multiplication
+      // can't overflow.
+      start * size_of::<I>()
+    } else {
+      start
+    };
+    let first = i32::try_from(first).with_context(|| format!("item {start} is out of range"))?;
+    let count =
+      i32::try_from(count).with_context(|| format!("item count {count} is out of range"))?;
+    Ok((first, count))
+  }
+
+  /// Draw `count` items of the mesh, starting at item `start`.
+  ///
+  /// Items are indices for an indexed mesh and vertices otherwise. An
+  /// error is reported if the range exceeds the mesh.
+  pub fn draw_range(&self, start: usize, count: usize) -> Result<()> {
+    let (first, count) = self.gl_range(start, count)?;
+
+    let () = self.vertex_array.bind();
+    if self.indices.is_some() {
+      let () = self
+        .context
+        .draw_elements::<I>(self.primitive, count, first);
+    } else {
+      let () = self.context.draw_arrays(self.primitive, first, count);
+    }
+    let () = self.vertex_array.unbind();
+    Ok(())
+  }
+
+  /// Draw multiple ranges of the mesh, each given as a `(start, count)`
+  /// pair, using a single draw call where supported.
+  ///
+  /// An error is reported if any of the ranges exceeds the mesh.
+  pub fn draw_ranges(&self, ranges: &[(usize, usize)]) -> Result<()> {
+    let (firsts, counts) = ranges
+      .iter()
+      .map(|(start, count)| self.gl_range(*start, *count))
+      .collect::<Result<(Vec<_>, Vec<_>)>>()?;
+
+    let () = self.vertex_array.bind();
+    if self.indices.is_some() {
+      let () = self
+        .context
+        .multi_draw_elements::<I>(self.primitive, &counts, &firsts);
+    } else {
+      let () = self
+        .context
+        .multi_draw_arrays(self.primitive, &firsts, &counts);
+    }
+    let () = self.vertex_array.unbind();
+    Ok(())
+  }
+
+  /// Draw `instance_count` instances of the entire mesh.
+  pub fn draw_instanced(&self, instance_count: usize) -> Result<()> {
+    let (_first, count) = self.gl_range(0, self.item_count())?;
+    let instance_count = i32::try_from(instance_count)
+      .with_context(|| format!("instance count {instance_count} is out of range"))?;
+
+    let () = self.vertex_array.bind();
+    if self.indices.is_some() {
+      let () = self
+        .context
+        .draw_elements_instanced::<I>(self.primitive, count, 0, instance_count);
+    } else {
+      let () = self
+        .context
+        .draw_arrays_instanced(self.primitive, 0, count, instance_count);
+    }
+    let () = self.vertex_array.unbind();
+    Ok(())
+  }
+
+  /// Retrieve the buffer containing the mesh's vertices.
+  #[inline]
+  pub fn vertices(&self) -> &VertexBuffer<V> {
+    &self.vertices
+  }
+
+  /// Retrieve the buffer containing the mesh's indices, if any.
+  #[inline]
+  pub fn indices(&self) -> Option<&VertexBuffer<I>> {
+    self.indices.as_ref()
+  }
+
+  /// Retrieve the shader attribute index of each vertex attribute, as
+  /// provided when creating the mesh.
+  #[inline]
+  pub fn attrib_indices(&self) -> &[(u32, AttribType)] {
+    &self.attrib_indices
+  }
+
+  /// Retrieve the primitive used for drawing the mesh.
+  #[inline]
+  pub fn primitive(&self) -> sys::Primitive {
+    self.primitive
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use test_fork::fork;
+
+  use crate::vertex;
+  use crate::winit::with_opengl_context;
+  use crate::Program;
+  use crate::Shader;
+
+
+  /// Check that we can create and draw indexed and non-indexed meshes.
+  #[fork]
+  #[test]
+  fn mesh_drawing() {
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec3 position;
+
+void main() {{
+   gl_Position = vec4(position, 1.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+out vec4 color;
+
+void main() {{
+   color = vec4(1.0f, 1.0f, 1.0f, 1.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let vertex_shader =
+        Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let fragment_shader =
+        Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let program = Program::new(&[vertex_shader, fragment_shader], &gl_context).unwrap();
+      let position = program.query_attrib_location("position").unwrap();
+      let () = program.bind();
+
+      let vertices = [
+        vertex::P3f {
+          x: 0.0,
+          y: 0.0,
+          z: 0.0,
+        },
+        vertex::P3f {
+          x: 1.0,
+          y: 0.0,
+          z: 0.0,
+        },
+        vertex::P3f {
+          x: 0.0,
+          y: 1.0,
+          z: 0.0,
+        },
+      ];
+      let usage = sys::VertexBufferUsage::StaticDraw;
+      let attrib_indices = [(position, AttribType::Position)];
+
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let mesh = Mesh::<_, u16>::new(
+        buffer,
+        None,
+        sys::Primitive::Triangles,
+        &attrib_indices,
+        &gl_context,
+      )
+      .unwrap();
+      assert_eq!(mesh.item_count(), 3);
+      let () = mesh.draw().unwrap();
+      let () = mesh.draw_range(1, 2).unwrap();
+      let () = mesh.draw_ranges(&[(0, 3), (1, 2)]).unwrap();
+      let () = mesh.draw_instanced(2).unwrap();
+
+      let err = mesh.draw_range(2, 2).unwrap_err();
+      assert!(err.to_string().contains("exceeds mesh"), "{err:#}");
+      let err = mesh.draw_ranges(&[(0, 3), (usize::MAX, 2)]).unwrap_err();
+      assert!(err.to_string().contains("exceeds mesh"), "{err:#}");
+      let err = mesh.draw_instanced(usize::MAX).unwrap_err();
+      assert!(err.to_string().contains("out of range"), "{err:#}");
+
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let indices = VertexBuffer::from_indices(&[0u32, 1, 2, 2, 1, 0], usage, &gl_context).unwrap();
+      let mesh = Mesh::new(
+        buffer,
+        Some(indices),
+        sys::Primitive::Triangles,
+        &attrib_indices,
+        &gl_context,
+      )
+      .unwrap();
+      assert_eq!(mesh.item_count(), 6);
+      let () = mesh.draw().unwrap();
+      let () = mesh.draw_range(3, 3).unwrap();
+      let () = mesh.draw_ranges(&[(0, 3), (3, 3)]).unwrap();
+      let () = mesh.draw_instanced(2).unwrap();
+
+      // Two strips in a single index buffer, separated by a restart
+      // index.
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let indices =
+        VertexBuffer::from_indices(&[0u16, 1, 2, u16::MAX, 2, 1, 0], usage, &gl_context).unwrap();
+      let mesh = Mesh::new(
+        buffer,
+        Some(indices),
+        sys::Primitive::TriangleStrip,
+        &attrib_indices,
+        &gl_context,
+      )
+      .unwrap();
+      let () = gl_context.set_primitive_restart::<u16>(true);
+      let () = mesh.draw().unwrap();
+      let () = gl_context.set_primitive_restart::<u16>(false);
+
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let mesh = Mesh::<_, u16>::new(
+        buffer,
+        None,
+        sys::Primitive::Points,
+        &attrib_indices,
+        &gl_context,
+      )
+      .unwrap();
+      let () = gl_context.set_point_size(4.0);
+      let () = mesh.draw().unwrap();
+    })
+  }
+}
@@ -0,0 +1,436 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use anyhow::bail;
+use anyhow::ensure;
+use anyhow::Context as _;
+use anyhow::Result;
+
+use xgl_glsl::validate;
+use xgl_glsl::Block;
+use xgl_glsl::Reflection;
+use xgl_glsl::Stage;
+use xgl_glsl::Variable;
+
+use crate::sys;
+use crate::ActiveAttrib;
+use crate::ActiveUniform;
+use crate::ActiveUniformBlock;
+use crate::DiagnosticSeverity;
+use crate::PreprocessedSource;
+use crate::ShaderDiagnostic;
+use crate::ShaderError;
+use crate::ShaderSource;
+
+
+fn attrib(variable: &Variable) -> Option<ActiveAttrib> {
+  let attrib = ActiveAttrib {
+    name: variable.name.clone(),
+    type_: sys::GlslType::from_name(&variable.type_)?,
+    size: variable.size,
+    location: variable.location?,
+  };
+  Some(attrib)
+}
+
+fn uniform(variable: &Variable) -> Option<ActiveUniform> {
+  // Arrays are reported by the name of their first element, as done
+  // by the driver.
+  let name = if variable.size > 1 {
+    format!("{}[0]", variable.name)
+  } else {
+    variable.name.clone()
+  };
+
+  let uniform = ActiveUniform {
+    name,
+    type_: sys::GlslType::from_name(&variable.type_)?,
+    size: variable.size,
+    location: None,
+  };
+  Some(uniform)
+}
+
+
+/// A shader validated without a GL context.
+///
+/// Shaders are parsed and validated by a pure Rust GLSL front end,
+/// making this type usable in build scripts and tests without access
+/// to a GPU. Only vertex and fragment shaders are supported.
+///
+/// Unlike the driver, the front end does not eliminate unused
+/// variables, meaning that all declared inputs and uniforms are
+/// reported. Inputs without explicit location are assigned consecutive
+/// locations in declaration order.
+#[derive(Clone, Debug)]
+pub struct OfflineShader {
+  /// The shader's type.
+  shader_type: sys::ShaderType,
+  /// The shader's stage, as understood by the front end.
+  stage: Stage,
+  /// Reflection information about the shader.
+  reflection: Reflection,
+}
+
+impl OfflineShader {
+  /// Validate a shader of the given type.
+  ///
+  /// On failure, the reported error wraps a [`ShaderError`] providing
+  /// access to the individual diagnostics, just like [`Shader::new`][crate::Shader::new].
+  pub fn new(shader_type: sys::ShaderType, code: &str) -> Result<Self> {
+    Self::validate(shader_type, code, None)
+  }
+
+  /// Validate a shader from a [`ShaderSource`], preprocessing it first.
+  ///
+  /// Source locations in diagnostics refer to the original (included)
+  /// files and lines.
+  pub fn from_source(shader_type: sys::ShaderType, source: &ShaderSource<'_>) -> Result<Self> {
+    let preprocessed = source
+      .preprocess()
+      .with_context(|| format!("failed to preprocess shader `{}`", source.name()))?;
+    Self::validate(shader_type, preprocessed.source(), Some(&preprocessed))
+  }
+
+  fn validate(
+    shader_type: sys::ShaderType,
+    code: &str,
+    preprocessed: Option<&PreprocessedSource>,
+  ) -> Result<Self> {
+    let stage = match shader_type {
+      sys::ShaderType::Vertex => Stage::Vertex,
+      sys::ShaderType::Fragment => Stage::Fragment,
+      sys::ShaderType::Geometry => bail!(
+        "{} shaders are not supported by offline validation",
+        shader_type.as_str()
+      ),
+    };
+
+    let reflection = validate(code, stage).map_err(|diagnostics| {
+      let excerpts = diagnostics
+        .iter()
+        .map(|diagnostic| {
+          let line = diagnostic.line?.checked_sub(1)?;
+          code.lines().nth(line).map(str::to_string)
+        })
+        .collect();
+      let diagnostics = diagnostics
+        .into_iter()
+        .map(|diagnostic| {
+          let mut diagnostic = ShaderDiagnostic {
+            severity: DiagnosticSeverity::Error,
+            file: None,
+            line: diagnostic.line,
+            column: diagnostic.column,
+            message: diagnostic.message,
+          };
+          if let Some(preprocessed) = preprocessed {
+            let () = preprocessed.remap(&mut diagnostic);
+          }
+          diagnostic
+        })
+        .collect();
+
+      let operation = format!("failed to compile {} shader", shader_type.as_str());
+      ShaderError::new(operation, diagnostics, excerpts)
+    })?;
+
+    let slf = Self {
+      shader_type,
+      stage,
+      reflection,
+    };
+    Ok(slf)
+  }
+
+  /// Retrieve the shader's type.
+  #[inline]
+  pub fn shader_type(&self) -> sys::ShaderType {
+    self.shader_type
+  }
+
+  /// Retrieve information about the shader's inputs.
+  ///
+  /// For vertex shaders these are the attributes.
+  pub fn inputs(&self) -> Vec<ActiveAttrib> {
+    self.reflection.inputs.iter().filter_map(attrib).collect()
+  }
+
+  /// Retrieve information about the shader's outputs.
+  pub fn outputs(&self) -> Vec<ActiveAttrib> {
+    self.reflection.outputs.iter().filter_map(attrib).collect()
+  }
+
+  /// Retrieve information about all uniforms of the shader, including
+  /// those contained in uniform blocks.
+  ///
+  /// No uniform has a location, as none is assigned without a GL
+  /// context.
+  pub fn uniforms(&self) -> Vec<ActiveUniform> {
+    let blocks = self
+      .reflection
+      .blocks
+      .iter()
+      .flat_map(|block| &block.members);
+
+    self
+      .reflection
+      .uniforms
+      .iter()
+      .chain(blocks)
+      .filter_map(uniform)
+      .collect()
+  }
+
+  /// Retrieve information about all uniform blocks of the shader.
+  ///
+  /// Blocks without explicit binding are reported as bound to binding
+  /// point zero, as is the default.
+  pub fn uniform_blocks(&self) -> Vec<ActiveUniformBlock> {
+    self
+      .reflection
+      .blocks
+      .iter()
+      .enumerate()
+      .map(|(idx, block)| {
+        let Block {
+          name,
+          binding,
+          size,
+          members,
+        } = block;
+
+        ActiveUniformBlock {
+          name: name.clone(),
+          index: idx as _,
+          binding: binding.unwrap_or(0),
+          size: *size,
+          uniforms: members
+            .iter()
+            .filter_map(uniform)
+            .map(|uniform| uniform.name)
+            .collect(),
+        }
+      })
+      .collect()
+  }
+}
+
+
+/// A program whose shaders were validated and linked without a GL
+/// context.
+///
+/// Linking checks that every input of the fragment shader is provided
+/// by the vertex shader with the same type.
+#[derive(Clone, Debug)]
+pub struct OfflineProgram {
+  /// The vertex shader.
+  vertex: OfflineShader,
+  /// The fragment shader.
+  fragment: OfflineShader,
+}
+
+impl OfflineProgram {
+  /// Link a vertex and a fragment shader.
+  pub fn new(shaders: &[OfflineShader]) -> Result<Self> {
+    let find = |stage, shader_type: sys::ShaderType| {
+      let mut shaders = shaders.iter().filter(|shader| shader.stage == stage);
+      let shader = shaders.next();
+      ensure!(
+        shaders.next().is_none(),
+        "only a single {} shader is supported",
+        shader_type.as_str()
+      );
+      shader.with_context(|| format!("no {} shader provided", shader_type.as_str()))
+    };
+    let vertex = find(Stage::Vertex, sys::ShaderType::Vertex)?;
+    let fragment = find(Stage::Fragment, sys::ShaderType::Fragment)?;
+
+    let outputs = vertex.outputs();
+    let diagnostics = fragment
+      .inputs()
+      .into_iter()
+      .filter_map(|input| {
+        let message = match outputs.iter().find(|output| output.name == input.name) {
+          None => format!(
+            "fragment shader input `{}` is not written by the vertex shader",
+            input.name
+          ),
+          Some(output) if output.type_ != input.type_ || output.size != input.size => format!(
+            "type mismatch for `{}`: vertex shader output of type `{}` vs. fragment shader input of type `{}`",
+            input.name, output.type_, input.type_
+          ),
+          Some(_) => return None,
+        };
+
+        let diagnostic = ShaderDiagnostic {
+          severity: DiagnosticSeverity::Error,
+          file: None,
+          line: None,
+          column: None,
+          message,
+        };
+        Some(diagnostic)
+      })
+      .collect::<Vec<_>>();
+
+    if !diagnostics.is_empty() {
+      let excerpts = vec![None; diagnostics.len()];
+      let err = ShaderError::new("failed to link program".to_string(), diagnostics, excerpts);
+      return Err(err.into())
+    }
+
+    let slf = Self {
+      vertex: vertex.clone(),
+      fragment: fragment.clone(),
+    };
+    Ok(slf)
+  }
+
+  /// Retrieve information about all attributes of the program.
+  #[inline]
+  pub fn attributes(&self) -> Vec<ActiveAttrib> {
+    self.vertex.inputs()
+  }
+
+  /// Retrieve information about all uniforms of the program, including
+  /// those contained in uniform blocks.
+  ///
+  /// Uniforms declared in both shaders are reported once.
+  pub fn uniforms(&self) -> Vec<ActiveUniform> {
+    let mut uniforms = self.vertex.uniforms();
+    for uniform in self.fragment.uniforms() {
+      if !uniforms.iter().any(|other| other.name == uniform.name) {
+        let () = uniforms.push(uniform);
+      }
+    }
+    uniforms
+  }
+
+  /// Retrieve information about all uniform blocks of the program.
+  ///
+  /// Blocks declared in both shaders are reported once.
+  pub fn uniform_blocks(&self) -> Vec<ActiveUniformBlock> {
+    let mut blocks = self.vertex.uniform_blocks();
+    for mut block in self.fragment.uniform_blocks() {
+      if !blocks.iter().any(|other| other.name == block.name) {
+        block.index = blocks.len() as _;
+        let () = blocks.push(block);
+      }
+    }
+    blocks
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  const VERTEX_SHADER: &str = r#"#version 330 core
+in vec4 position;
+in vec2 texture;
+
+uniform mat4 transform;
+
+out vec2 uv;
+
+void main() {
+  uv = texture;
+  gl_Position = transform * position;
+}
+"#;
+
+
+  /// Check that we can validate and reflect on shaders without a GL
+  /// context.
+  #[test]
+  fn offline_validation() {
+    let fragment = r#"#version 300 es
+precision mediump float;
+
+in vec2 uv;
+uniform sampler2D sampler;
+uniform Material {
+  vec4 tint;
+};
+out vec4 color;
+
+void main() {
+  color = texture2D(sampler, uv) * tint;
+}
+"#;
+
+    let vertex = OfflineShader::new(sys::ShaderType::Vertex, VERTEX_SHADER).unwrap();
+    let fragment = OfflineShader::new(sys::ShaderType::Fragment, fragment).unwrap();
+    let program = OfflineProgram::new(&[vertex, fragment]).unwrap();
+
+    let attribs = program
+      .attributes()
+      .into_iter()
+      .map(|attrib| (attrib.name, attrib.type_, attrib.location))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      attribs,
+      [
+        ("position".to_string(), sys::GlslType::Vec4, 0),
+        ("texture".to_string(), sys::GlslType::Vec2, 1),
+      ]
+    );
+
+    let uniforms = program
+      .uniforms()
+      .into_iter()
+      .map(|uniform| (uniform.name, uniform.type_))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      uniforms,
+      [
+        ("transform".to_string(), sys::GlslType::Mat4),
+        ("sampler".to_string(), sys::GlslType::Sampler2D),
+        ("tint".to_string(), sys::GlslType::Vec4),
+      ]
+    );
+
+    let blocks = program.uniform_blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].name, "Material");
+    assert_eq!(blocks[0].size, 16);
+    assert_eq!(blocks[0].uniforms, ["tint"]);
+  }
+
+  /// Check that validation errors are reported as `ShaderError`s.
+  #[test]
+  fn offline_errors() {
+    let source = ShaderSource::new(
+      "fragment.glsl",
+      "#version 330 core\nout vec4 color;\nvoid main() {\n  color = undefined;\n}\n",
+    )
+    .set_define("FOO", "1");
+    let err = OfflineShader::from_source(sys::ShaderType::Fragment, &source).unwrap_err();
+    let shader_err = err.downcast_ref::<ShaderError>().unwrap();
+    let diagnostic = &shader_err.diagnostics()[0];
+    assert_eq!(diagnostic.file.as_deref(), Some("fragment.glsl"));
+    assert_eq!(diagnostic.line, Some(4));
+    assert!(err.to_string().contains("color = undefined;"), "{err:#}");
+
+    let fragment = "#version 330 core\nin vec3 uv;\nout vec4 color;\nvoid main() {\n  color = vec4(uv, 1.0);\n}\n";
+    let vertex = OfflineShader::new(sys::ShaderType::Vertex, VERTEX_SHADER).unwrap();
+    let fragment = OfflineShader::new(sys::ShaderType::Fragment, fragment).unwrap();
+    let err = OfflineProgram::new(&[vertex, fragment]).unwrap_err();
+    assert!(
+      err.to_string().contains("type mismatch for `uv`"),
+      "{err:#}"
+    );
+  }
+
+  /// Check that `include_shader!` embeds a validated shader.
+  #[test]
+  fn shader_inclusion() {
+    let vertex = crate::include_shader!(vertex, "data/transform.vert");
+    assert_eq!(vertex, VERTEX_SHADER);
+
+    let _shader = OfflineShader::new(sys::ShaderType::Vertex, vertex).unwrap();
+  }
+}
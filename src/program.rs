@@ -1,61 +1,298 @@
 // Copyright (C) 2025 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use std::any::type_name;
+use std::any::Any;
+use std::any::TypeId;
+use std::cell::OnceCell;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::ops::Deref;
+use std::rc::Rc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 
-use anyhow::anyhow;
+use anyhow::ensure;
 use anyhow::Context as _;
 use anyhow::Result;
 
 use crate::sys;
 use crate::sys::Gl as _;
+use crate::uniform::UniformData;
+use crate::uniform::UniformElement;
+use crate::uniform::Uniforms;
+use crate::vertex::AttribType;
 use crate::Shader;
+use crate::ShaderDiagnostic;
+use crate::ShaderError;
+use crate::Uniform;
 
 
-/// A program object.
+/// The ID to assign to the next [`Program`] created.
+static NEXT_PROGRAM_ID: AtomicU64 = AtomicU64::new(0);
+
+
+/// Information about an active attribute of a [`Program`].
+#[derive(Clone, Debug)]
+pub struct ActiveAttrib {
+  /// The attribute's name.
+  pub name: String,
+  /// The attribute's GLSL type.
+  pub type_: sys::GlslType,
+  /// The attribute's array size (`1` for non-array attributes).
+  pub size: usize,
+  /// The attribute's location.
+  pub location: u32,
+}
+
+
+/// Information about an active uniform of a [`Program`].
 #[derive(Debug)]
-pub struct Program {
+pub struct ActiveUniform {
+  /// The uniform's name.
+  ///
+  /// For arrays this is the name of the first element, e.g.,
+  /// `lights[0]`.
+  pub name: String,
+  /// The uniform's GLSL type.
+  pub type_: sys::GlslType,
+  /// The uniform's array size (`1` for non-array uniforms).
+  pub size: usize,
+  /// The uniform's location, or `None` if the uniform is a member of a
+  /// uniform block.
+  pub location: Option<sys::UniformLocation>,
+}
+
+
+/// Information about an active uniform block of a [`Program`].
+#[derive(Clone, Debug)]
+pub struct ActiveUniformBlock {
+  /// The block's name.
+  pub name: String,
+  /// The block's index.
+  pub index: u32,
+  /// The uniform buffer binding point the block is associated with.
+  pub binding: u32,
+  /// The size of the block's data, in bytes.
+  pub size: usize,
+  /// The names of the uniforms contained in the block.
+  pub uniforms: Vec<String>,
+}
+
+
+/// Builder infrastructure for a program.
+#[derive(Debug)]
+pub struct Builder<C = ()> {
   /// The GL context.
-  context: sys::Context,
-  /// The program.
-  program: sys::Program,
+  context: C,
+  /// Vertex attribute names along with the indices to bind them to.
+  attrib_locations: Vec<(String, u32)>,
+  /// Fragment shader output names along with the color numbers to bind
+  /// them to.
+  frag_data_locations: Vec<(String, u32)>,
+  /// The varyings to record in transform feedback mode, if any.
+  transform_feedback: Option<(Vec<String>, sys::TransformFeedbackMode)>,
 }
 
-impl Program {
-  pub fn new(shaders: &[Shader], context: &sys::Context) -> Result<Self> {
+impl Builder<()> {
+  /// Bind the vertex attribute with the given name to the provided
+  /// index.
+  pub fn set_attrib_location(mut self, name: &str, index: u32) -> Self {
+    self.attrib_locations.push((name.to_string(), index));
+    self
+  }
+
+  /// Bind vertex attributes named after each [`AttribType`] (e.g.,
+  /// `position`) to the corresponding [`AttribType::location`].
+  pub fn set_attrib_type_locations(mut self) -> Self {
+    for attrib in [
+      AttribType::Position,
+      AttribType::Normal,
+      AttribType::Texture,
+      AttribType::Color,
+    ] {
+      self = self.set_attrib_location(&attrib.to_string(), attrib.location());
+    }
+    self
+  }
+
+  /// Bind the fragment shader output with the given name to the
+  /// provided color number, for rendering to multiple render targets.
+  ///
+  /// This setting is ignored on WebGL, where output locations have to
+  /// be specified in the shader.
+  pub fn set_frag_data_location(mut self, name: &str, color: u32) -> Self {
+    self.frag_data_locations.push((name.to_string(), color));
+    self
+  }
+
+  /// Set the varyings to record in transform feedback mode.
+  pub fn set_transform_feedback_varyings(
+    mut self,
+    varyings: &[&str],
+    mode: sys::TransformFeedbackMode,
+  ) -> Self {
+    let varyings = varyings.iter().map(ToString::to_string).collect();
+    self.transform_feedback = Some((varyings, mode));
+    self
+  }
+
+  /// Set the program's GL context.
+  pub fn set_context(self, context: &sys::Context) -> Builder<sys::Context> {
+    let Self {
+      context: (),
+      attrib_locations,
+      frag_data_locations,
+      transform_feedback,
+    } = self;
+
+    Builder {
+      context: context.clone(),
+      attrib_locations,
+      frag_data_locations,
+      transform_feedback,
+    }
+  }
+}
+
+impl Builder<sys::Context> {
+  /// Link the provided shaders into a program.
+  #[inline]
+  pub fn build(self, shaders: &[Shader]) -> Result<Program> {
+    self.build_from(&shaders.iter().collect::<Vec<_>>())
+  }
+
+  /// Link the provided shaders into a program.
+  pub(crate) fn build_from(self, shaders: &[&Shader]) -> Result<Program> {
+    let Self {
+      context,
+      attrib_locations,
+      frag_data_locations,
+      transform_feedback,
+    } = self;
+
     let program = context
       .create_program()
       .context("failed to create program object")?;
-    let slf = Self {
+    let mut program = Program {
       context: context.clone(),
+      id: NEXT_PROGRAM_ID.fetch_add(1, Ordering::Relaxed),
       program,
+      diagnostics: Vec::new(),
+      uniforms: OnceCell::new(),
+      uniform_handles: RefCell::new(HashMap::new()),
     };
 
-    let () = shaders
+    let () = attrib_locations
+      .iter()
+      .for_each(|(name, index)| context.bind_attrib_location(&program.program, *index, name));
+    let () = frag_data_locations
       .iter()
-      .for_each(|shader| context.attach_shader(&slf.program, shader));
+      .for_each(|(name, color)| context.bind_frag_data_location(&program.program, *color, name));
+    if let Some((varyings, mode)) = &transform_feedback {
+      let varyings = varyings.iter().map(String::as_str).collect::<Vec<_>>();
+      let () = context.set_transform_feedback_varyings(&program.program, &varyings, *mode);
+    }
 
-    let () = context.link_program(&slf.program).map_err(|log| {
-      // TODO: Should use `String::from_utf8_lossy_owned` once stable.
-      let log = String::from_utf8_lossy(log.as_slice());
-      anyhow!("failed to link program: {log}")
-    })?;
+    let () = shaders
+      .iter()
+      .for_each(|shader| context.attach_shader(&program.program, shader));
 
+    let result = context.link_program(&program.program);
     let () = shaders
       .iter()
-      .for_each(|shader| context.detach_shader(&slf.program, shader));
+      .for_each(|shader| context.detach_shader(&program.program, shader));
+    let () = program.check(result, "failed to link program")?;
 
     // Also perform some basic validation to check whether the program
     // could actually execute. In a more complex program we may want to
     // do that at more points in time (potentially before every "draw"
     // call).
-    let () = context.validate_program(&slf.program).map_err(|log| {
-      // TODO: Should use `String::from_utf8_lossy_owned` once stable.
-      let log = String::from_utf8_lossy(log.as_slice());
-      anyhow!("failed to validate program: {log}")
-    })?;
+    let result = context.validate_program(&program.program);
+    let () = program.check(result, "failed to validate program")?;
 
-    Ok(slf)
+    Ok(program)
+  }
+}
+
+/// Instantiate a "default" [`Builder`].
+///
+/// By default no locations are bound and no transform feedback
+/// varyings are set.
+impl Default for Builder<()> {
+  #[inline]
+  fn default() -> Self {
+    Self {
+      context: (),
+      attrib_locations: Vec::new(),
+      frag_data_locations: Vec::new(),
+      transform_feedback: None,
+    }
+  }
+}
+
+
+/// A program object.
+#[derive(Debug)]
+pub struct Program {
+  /// The GL context.
+  context: sys::Context,
+  /// An ID uniquely identifying the program, used for associating
+  /// [`Uniform`] handles with it.
+  id: u64,
+  /// The program.
+  program: sys::Program,
+  /// Diagnostics reported while linking and validating the program.
+  diagnostics: Vec<ShaderDiagnostic>,
+  /// A cache of the GLSL type, array size, and location of all active
+  /// uniforms not contained in uniform blocks, by name.
+  uniforms: OnceCell<HashMap<String, (sys::GlslType, usize, sys::UniformLocation)>>,
+  /// A cache of resolved [`Uniforms`] handles, by implementing type.
+  uniform_handles: RefCell<HashMap<TypeId, Rc<dyn Any>>>,
+}
+
+impl Program {
+  /// Create a program builder.
+  #[inline]
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
+  /// Link the provided shaders into a program.
+  ///
+  /// This is a shorthand for building a program with a default
+  /// [`Builder`].
+  #[inline]
+  pub fn new(shaders: &[Shader], context: &sys::Context) -> Result<Self> {
+    Self::builder().set_context(context).build(shaders)
+  }
+
+  /// Evaluate the result of a link or validation operation, recording
+  /// reported diagnostics.
+  fn check(&mut self, result: Result<(), Vec<u8>>, operation: &str) -> Result<()> {
+    let log = match &result {
+      Ok(()) => self.context.program_info_log(&self.program),
+      Err(log) => log.clone(),
+    };
+    // TODO: Should use `String::from_utf8_lossy_owned` once stable.
+    let log = String::from_utf8_lossy(log.as_slice());
+    let diagnostics = ShaderDiagnostic::parse_log(&log);
+
+    if result.is_err() {
+      let excerpts = vec![None; diagnostics.len()];
+      let err = ShaderError::new(operation.to_string(), diagnostics, excerpts);
+      return Err(err.into())
+    }
+
+    let () = self.diagnostics.extend(diagnostics);
+    Ok(())
+  }
+
+  /// Retrieve the diagnostics (typically warnings) reported when
+  /// linking and validating the program.
+  #[inline]
+  pub fn diagnostics(&self) -> &[ShaderDiagnostic] {
+    &self.diagnostics
   }
 
   pub fn query_attrib_location(&self, attrib: &str) -> Result<u32> {
@@ -74,6 +311,187 @@ impl Program {
     Ok(location)
   }
 
+  /// Retrieve a type checked handle to the uniform with the given name.
+  ///
+  /// `T` has to be compatible with the uniform's GLSL type. Uniform
+  /// arrays are represented by slices (`[T]`) and may be referred to
+  /// by their name with or without the trailing `[0]`.
+  pub fn uniform<T>(&self, name: &str) -> Result<Uniform<T>>
+  where
+    T: UniformData + ?Sized,
+  {
+    let (type_, size, location) = self
+      .uniform_info(name)
+      .with_context(|| format!("failed to find active uniform `{name}`"))?;
+    ensure!(
+      T::is_compatible(*type_, *size),
+      "uniform `{name}` of type `{type_}{array}` is incompatible with `{ty}`",
+      array = if *size > 1 {
+        format!("[{size}]")
+      } else {
+        String::new()
+      },
+      ty = type_name::<T>(),
+    );
+
+    let uniform = Uniform::new(self.id, location.clone(), *size, &self.context);
+    Ok(uniform)
+  }
+
+  /// Look up the GLSL type, array size, and location of the active
+  /// uniform with the given name.
+  pub(crate) fn uniform_info(
+    &self,
+    name: &str,
+  ) -> Option<&(sys::GlslType, usize, sys::UniformLocation)> {
+    let uniforms = self.uniforms.get_or_init(|| {
+      self
+        .uniforms()
+        .into_iter()
+        .filter_map(|uniform| {
+          let ActiveUniform {
+            name,
+            type_,
+            size,
+            location,
+          } = uniform;
+          let location = location?;
+          let name = if size > 1 {
+            name.strip_suffix("[0]").map(str::to_string).unwrap_or(name)
+          } else {
+            name
+          };
+          Some((name, (type_, size, location)))
+        })
+        .collect()
+    });
+
+    uniforms
+      .get(name)
+      .or_else(|| uniforms.get(name.strip_suffix("[0]")?))
+  }
+
+  /// Read back the current value of a (non-array) uniform.
+  ///
+  /// `uniform` has to have been retrieved from this program via
+  /// [`Program::uniform`], or an error is reported.
+  pub fn uniform_value<T>(&self, uniform: &Uniform<T>) -> Result<T>
+  where
+    T: UniformElement,
+  {
+    debug_assert_eq!(uniform.size(), 1);
+    let location = uniform
+      .location_in(self.id)
+      .context("uniform does not belong to program")?;
+    T::uniform(&self.program, &location, &self.context).context("failed to retrieve uniform value")
+  }
+
+  /// Retrieve the GL context the program belongs to.
+  #[inline]
+  pub(crate) fn context(&self) -> &sys::Context {
+    &self.context
+  }
+
+  /// Retrieve the ID uniquely identifying the program.
+  #[inline]
+  pub(crate) fn id(&self) -> u64 {
+    self.id
+  }
+
+  /// Retrieve the (cached) handles of the uniforms represented by `U`.
+  pub(crate) fn uniform_handles<U>(&self) -> Result<Rc<U::Handles>>
+  where
+    U: Uniforms,
+  {
+    let type_id = TypeId::of::<U>();
+    if let Some(handles) = self.uniform_handles.borrow().get(&type_id) {
+      // SANITY: Handles are always stored under the `TypeId` of the
+      //         type that resolved them.
+      return Ok(Rc::clone(handles).downcast().unwrap())
+    }
+
+    let handles = Rc::new(U::resolve(self)?);
+    let _prev = self
+      .uniform_handles
+      .borrow_mut()
+      .insert(type_id, Rc::clone(&handles) as Rc<dyn Any>);
+    Ok(handles)
+  }
+
+  /// Retrieve information about all active attributes of the program.
+  pub fn attributes(&self) -> Vec<ActiveAttrib> {
+    self
+      .context
+      .active_attribs(&self.program)
+      .into_iter()
+      // Built-in attributes such as `gl_VertexID` may be reported as
+      // active, but they don't have a location.
+      .filter_map(|(name, type_, size)| {
+        let location = self.context.attrib_location(&self.program, &name)?;
+        let attrib = ActiveAttrib {
+          name,
+          type_,
+          size: size as _,
+          location,
+        };
+        Some(attrib)
+      })
+      .collect()
+  }
+
+  /// Retrieve information about all active uniforms of the program,
+  /// including those contained in uniform blocks.
+  pub fn uniforms(&self) -> Vec<ActiveUniform> {
+    let blocks = self.context.active_uniform_blocks(&self.program);
+
+    self
+      .context
+      .active_uniforms(&self.program)
+      .into_iter()
+      .map(|(index, name, type_, size)| {
+        let in_block = blocks
+          .iter()
+          .any(|(_, _, _, uniforms)| uniforms.contains(&index));
+        let location = if in_block {
+          None
+        } else {
+          self.context.uniform_location(&self.program, &name)
+        };
+
+        ActiveUniform {
+          name,
+          type_,
+          size: size as _,
+          location,
+        }
+      })
+      .collect()
+  }
+
+  /// Retrieve information about all active uniform blocks of the
+  /// program.
+  pub fn uniform_blocks(&self) -> Vec<ActiveUniformBlock> {
+    let uniforms = self.context.active_uniforms(&self.program);
+
+    self
+      .context
+      .active_uniform_blocks(&self.program)
+      .into_iter()
+      .enumerate()
+      .map(|(idx, (name, binding, size, indices))| ActiveUniformBlock {
+        name,
+        index: idx as _,
+        binding,
+        size: size as _,
+        uniforms: indices
+          .into_iter()
+          .filter_map(|idx| uniforms.iter().find(|(index, ..)| *index == idx))
+          .map(|(_, name, _, _)| name.clone())
+          .collect(),
+      })
+      .collect()
+  }
+
   #[inline]
   pub fn bind(&self) {
     let () = self.context.use_program(&self.program);
@@ -148,4 +566,434 @@ void main() {{
       assert!(program.query_attrib_location("foobar").is_err());
     })
   }
+
+  /// Check that we can bind locations and set transform feedback
+  /// varyings before linking a program with a geometry shader.
+  #[fork]
+  #[test]
+  fn program_building() {
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 position;
+in vec4 color;
+
+out vec4 vertex_color;
+
+void main() {{
+   vertex_color = color;
+   gl_Position = position;
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let geometry_shader = format!(
+      r#"
+#version {glsl_version}
+
+layout(points) in;
+layout(points, max_vertices = 1) out;
+
+in vec4 vertex_color[];
+out vec4 geometry_color;
+
+void main() {{
+   geometry_color = vertex_color[0];
+   gl_Position = gl_in[0].gl_Position;
+   EmitVertex();
+   EndPrimitive();
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 geometry_color;
+out vec4 diffuse;
+out vec4 specular;
+
+void main() {{
+   diffuse = geometry_color;
+   specular = geometry_color * 0.5f;
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let vertex_shader =
+        Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let geometry_shader =
+        Shader::new(sys::ShaderType::Geometry, &geometry_shader, &gl_context).unwrap();
+      let fragment_shader =
+        Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let program = Program::builder()
+        .set_attrib_type_locations()
+        .set_frag_data_location("diffuse", 0)
+        .set_frag_data_location("specular", 1)
+        .set_transform_feedback_varyings(
+          &["geometry_color"],
+          sys::TransformFeedbackMode::Interleaved,
+        )
+        .set_context(&gl_context)
+        .build(&[vertex_shader, geometry_shader, fragment_shader])
+        .unwrap();
+
+      assert_eq!(
+        program.query_attrib_location("position").unwrap(),
+        AttribType::Position.location()
+      );
+      assert_eq!(
+        program.query_attrib_location("color").unwrap(),
+        AttribType::Color.location()
+      );
+    })
+  }
+
+  /// Check that we can reflect on a program's attributes, uniforms, and
+  /// uniform blocks.
+  #[fork]
+  #[test]
+  fn program_reflection() {
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 position;
+in vec2 texture;
+
+uniform mat4 transform;
+
+out vec2 uv;
+
+void main() {{
+   uv = texture;
+   gl_Position = transform * position;
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+uniform vec3 colors[2];
+
+layout(std140) uniform Light {{
+  vec4 direction;
+  float intensity;
+}};
+
+in vec2 uv;
+out vec4 color;
+
+void main() {{
+   color = vec4(colors[0] + colors[1], 1.0f) * direction * intensity * uv.x;
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let vertex_shader =
+        Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let fragment_shader =
+        Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let program = Program::new(&[vertex_shader, fragment_shader], &gl_context).unwrap();
+
+      let mut attribs = program.attributes();
+      let () = attribs.sort_by(|x, y| x.name.cmp(&y.name));
+      assert_eq!(attribs.len(), 2);
+      assert_eq!(attribs[0].name, "position");
+      assert_eq!(attribs[0].type_, sys::GlslType::Vec4);
+      assert_eq!(attribs[0].size, 1);
+      assert_eq!(
+        attribs[0].location,
+        program.query_attrib_location("position").unwrap()
+      );
+      assert_eq!(attribs[1].name, "texture");
+      assert_eq!(attribs[1].type_, sys::GlslType::Vec2);
+
+      let uniforms = program.uniforms();
+      let colors = uniforms.iter().find(|u| u.name == "colors[0]").unwrap();
+      assert_eq!(colors.type_, sys::GlslType::Vec3);
+      assert_eq!(colors.size, 2);
+      assert!(colors.location.is_some());
+      let transform = uniforms.iter().find(|u| u.name == "transform").unwrap();
+      assert_eq!(transform.type_, sys::GlslType::Mat4);
+      assert_eq!(transform.type_.to_string(), "mat4");
+      let intensity = uniforms.iter().find(|u| u.name == "intensity").unwrap();
+      assert_eq!(intensity.type_, sys::GlslType::Float);
+      assert!(intensity.location.is_none());
+
+      let blocks = program.uniform_blocks();
+      assert_eq!(blocks.len(), 1);
+      assert_eq!(blocks[0].name, "Light");
+      assert_eq!(blocks[0].size, 32);
+      let mut members = blocks[0].uniforms.clone();
+      let () = members.sort();
+      assert_eq!(members, ["direction", "intensity"]);
+    })
+  }
+
+  /// Check that we can retrieve type checked uniform handles and set
+  /// uniform values through them.
+  #[fork]
+  #[test]
+  fn typed_uniforms() {
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 position;
+
+uniform mat4 transform;
+uniform mat2x3 skew;
+
+void main() {{
+   gl_Position = transform * position + vec4(skew * vec2(1.0f), 0.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+uniform vec3 colors[2];
+uniform uint index;
+uniform sampler2D sampler;
+
+out vec4 color;
+
+void main() {{
+   color = vec4(colors[index], 1.0f) * texture(sampler, vec2(0.0f));
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let vertex_shader =
+        Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let fragment_shader =
+        Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let program = Program::new(&[vertex_shader, fragment_shader], &gl_context).unwrap();
+      let () = program.bind();
+
+      let transform = program.uniform::<[f32; 16]>("transform").unwrap();
+      let () = transform.set(&[0.0; 16]);
+      let transform = program.uniform::<[[f32; 4]; 4]>("transform").unwrap();
+      let () = transform.set(&[[1.0; 4]; 4]);
+      let skew = program.uniform::<[[f32; 3]; 2]>("skew").unwrap();
+      let () = skew.set(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+      let colors = program.uniform::<[[f32; 3]]>("colors").unwrap();
+      assert_eq!(colors.size(), 2);
+      let () = colors.set(&[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+      let colors = program.uniform::<[[f32; 3]]>("colors[0]").unwrap();
+      let () = colors.set(&[[0.0, 0.0, 1.0]]);
+      let index = program.uniform::<u32>("index").unwrap();
+      let () = index.set(&1);
+      let sampler = program.uniform::<i32>("sampler").unwrap();
+      let () = sampler.set(&0);
+
+      let location = program.query_uniform_location("skew").unwrap();
+      let skew = gl_context.uniform_fv::<6>(&program, &location).unwrap();
+      assert_eq!(skew, [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+      let err = program.uniform::<[f32; 3]>("colors").unwrap_err();
+      assert_eq!(
+        err.to_string(),
+        "uniform `colors` of type `vec3[2]` is incompatible with `[f32; 3]`"
+      );
+      assert!(program.uniform::<i32>("index").is_err());
+      assert!(program.uniform::<f32>("foobar").is_err());
+    })
+  }
+
+  /// Check that we can read back the values of typed uniforms.
+  #[fork]
+  #[test]
+  fn uniform_readback() {
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 position;
+
+uniform mat3x2 skew;
+uniform ivec2 offset;
+uniform uvec3 counts;
+
+void main() {{
+   vec2 shift = skew * vec3(offset, counts.x + counts.y + counts.z);
+   gl_Position = position + vec4(shift, 0.0f, 0.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+uniform bool enabled;
+uniform bvec3 channels;
+uniform int mode;
+uniform float scale;
+
+out vec4 color;
+
+void main() {{
+   color = enabled ? vec4(vec3(channels) * float(mode) * scale, 1.0f) : vec4(0.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let vertex = Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let fragment = Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let program = Program::new(&[vertex, fragment], &gl_context).unwrap();
+      let () = program.bind();
+
+      let skew = program.uniform::<[[f32; 2]; 3]>("skew").unwrap();
+      let () = skew.set(&[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+      assert_eq!(
+        program.uniform_value(&skew).unwrap(),
+        [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]
+      );
+
+      let offset = program.uniform::<[i32; 2]>("offset").unwrap();
+      let () = offset.set(&[-1, 7]);
+      assert_eq!(program.uniform_value(&offset).unwrap(), [-1, 7]);
+
+      let counts = program.uniform::<[u32; 3]>("counts").unwrap();
+      let () = counts.set(&[1, 2, u32::MAX]);
+      assert_eq!(program.uniform_value(&counts).unwrap(), [1, 2, u32::MAX]);
+
+      let enabled = program.uniform::<bool>("enabled").unwrap();
+      let () = enabled.set(&true);
+      assert!(program.uniform_value(&enabled).unwrap());
+
+      let channels = program.uniform::<[bool; 3]>("channels").unwrap();
+      let () = channels.set(&[true, false, true]);
+      assert_eq!(
+        program.uniform_value(&channels).unwrap(),
+        [true, false, true]
+      );
+
+      let mode = program.uniform::<i32>("mode").unwrap();
+      let () = mode.set(&-3);
+      assert_eq!(program.uniform_value(&mode).unwrap(), -3);
+
+      // Reading back a different number of components than the
+      // uniform has should be rejected.
+      let location = program.query_uniform_location("skew").unwrap();
+      assert!(gl_context.uniform_fv::<2>(&program, &location).is_err());
+      assert!(gl_context.uniform_fv::<16>(&program, &location).is_err());
+      assert!(gl_context.uniform_iv::<4>(&program, &location).is_err());
+
+      let scale = program.uniform::<f32>("scale").unwrap();
+      let () = scale.set(&0.5);
+      assert_eq!(program.uniform_value(&scale).unwrap(), 0.5);
+
+      // Handles of other programs should be rejected.
+      let vertex = Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let fragment = Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let other = Program::new(&[vertex, fragment], &gl_context).unwrap();
+      let err = other.uniform_value(&scale).unwrap_err();
+      assert_eq!(err.to_string(), "uniform does not belong to program");
+    })
+  }
+
+  /// Check that we can apply a struct of uniforms derived using
+  /// `#[derive(Uniforms)]`.
+  #[fork]
+  #[test]
+  fn derived_uniforms() {
+    #[derive(xgl_derive::Uniforms)]
+    struct Material {
+      #[uniform(name = "u_tint")]
+      tint: [f32; 4],
+      #[uniform(array)]
+      weights: [f32; 3],
+      #[uniform(array)]
+      offsets: Vec<[f32; 2]>,
+      enabled: bool,
+    }
+
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 position;
+
+uniform float weights[3];
+uniform vec2 offsets[2];
+
+void main() {{
+   vec2 offset = (offsets[0] + offsets[1]) * (weights[0] + weights[1] + weights[2]);
+   gl_Position = position + vec4(offset, 0.0f, 0.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+uniform vec4 u_tint;
+uniform bool enabled;
+
+out vec4 color;
+
+void main() {{
+   color = enabled ? u_tint : vec4(0.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let vertex_shader =
+        Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+      let fragment_shader =
+        Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+      let program = Program::new(&[vertex_shader, fragment_shader], &gl_context).unwrap();
+      let () = program.bind();
+
+      let material = Material {
+        tint: [1.0, 0.5, 0.25, 1.0],
+        weights: [0.1, 0.2, 0.3],
+        offsets: vec![[1.0, 2.0], [3.0, 4.0]],
+        enabled: true,
+      };
+      let () = material.apply(&program).unwrap();
+      let () = material.apply(&program).unwrap();
+
+      let tint = program.uniform::<[f32; 4]>("u_tint").unwrap();
+      assert_eq!(program.uniform_value(&tint).unwrap(), [1.0, 0.5, 0.25, 1.0]);
+      let enabled = program.uniform::<bool>("enabled").unwrap();
+      assert!(program.uniform_value(&enabled).unwrap());
+      let location = program.query_uniform_location("offsets[1]").unwrap();
+      assert_eq!(
+        gl_context.uniform_fv::<2>(&program, &location).unwrap(),
+        [3.0, 4.0]
+      );
+    })
+  }
 }
@@ -0,0 +1,397 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::cell::RefCell;
+use std::fs::metadata;
+use std::fs::read_to_string;
+use std::ops::Deref;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::rc::Weak;
+use std::time::SystemTime;
+
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::sys;
+use crate::uniform::UniformData;
+use crate::FsIncludeResolver;
+use crate::IncludeResolver as _;
+use crate::Program;
+use crate::Shader;
+use crate::ShaderSource;
+use crate::Uniform;
+
+
+/// Retrieve the modification time of the file at `path`, if it exists.
+fn modified(path: &Path) -> Option<SystemTime> {
+  metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+
+/// A [`Program`] built from shader files on disk that can be rebuilt
+/// when any of them changes.
+///
+/// `#include` directives are resolved relative to the directory
+/// containing the including shader's main file and included files are
+/// watched as well.
+#[derive(Debug)]
+pub struct ReloadableProgram {
+  /// The GL context.
+  context: sys::Context,
+  /// The type and main file path of each shader.
+  shaders: Vec<(sys::ShaderType, PathBuf)>,
+  /// The files the program was built from, along with their
+  /// modification times as of the last build attempt.
+  files: Vec<(PathBuf, Option<SystemTime>)>,
+  /// The most recently successfully built program.
+  program: Program,
+  /// The uniform handles handed out by [`ReloadableProgram::uniform`],
+  /// along with their name and type check, to be re-resolved when the
+  /// program is rebuilt.
+  uniforms: RefCell<
+    Vec<(
+      String,
+      fn(sys::GlslType, usize) -> bool,
+      Weak<RefCell<Option<(u64, sys::UniformLocation)>>>,
+    )>,
+  >,
+}
+
+impl ReloadableProgram {
+  /// Create a new program from the shader files at the provided paths.
+  pub fn new(shaders: &[(sys::ShaderType, &Path)], context: &sys::Context) -> Result<Self> {
+    let shaders = shaders
+      .iter()
+      .map(|(shader_type, path)| (*shader_type, path.to_path_buf()))
+      .collect::<Vec<_>>();
+    let mut files = Vec::new();
+    let program = Self::build(&shaders, &mut files, context)?;
+
+    let slf = Self {
+      context: context.clone(),
+      shaders,
+      files,
+      program,
+      uniforms: RefCell::new(Vec::new()),
+    };
+    Ok(slf)
+  }
+
+  /// Build a program from the provided shader files, recording the
+  /// files it is built from in `files`.
+  ///
+  /// On error, `files` contains the files seen up to that point.
+  fn build(
+    shaders: &[(sys::ShaderType, PathBuf)],
+    files: &mut Vec<(PathBuf, Option<SystemTime>)>,
+    context: &sys::Context,
+  ) -> Result<Program> {
+    let shaders = shaders
+      .iter()
+      .map(|(shader_type, path)| {
+        // Capture the modification time before reading, so that we
+        // err on the side of rebuilding once more if the file is
+        // changed concurrently.
+        let () = files.push((path.clone(), modified(path)));
+        let code = read_to_string(path)
+          .with_context(|| format!("failed to read shader file `{}`", path.display()))?;
+        let root = path.parent().unwrap_or_else(|| Path::new(""));
+        let resolver = FsIncludeResolver::new(root);
+        let source =
+          ShaderSource::new(path.display().to_string(), code).set_include_resolver(&resolver);
+        let preprocessed = source
+          .preprocess()
+          .with_context(|| format!("failed to preprocess shader `{}`", path.display()))?;
+
+        let () = preprocessed
+          .files()
+          .iter()
+          .skip(1)
+          .filter_map(|name| resolver.path(name))
+          .for_each(|path| {
+            let modified = modified(&path);
+            files.push((path, modified))
+          });
+        Shader::from_preprocessed(*shader_type, &preprocessed, context)
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    let program = Program::new(&shaders, context)?;
+    Ok(program)
+  }
+
+  /// Check whether any of the program's source files changed and, if
+  /// so, rebuild the program.
+  ///
+  /// `true` is returned if the program got rebuilt, in which case all
+  /// handles retrieved via [`ReloadableProgram::uniform`] are updated
+  /// to refer to the new program. If rebuilding fails, the previous
+  /// program is retained and the error is reported (potentially
+  /// wrapping a [`ShaderError`][crate::ShaderError] with diagnostics).
+  /// Another rebuild is only attempted once a file changes again.
+  pub fn reload(&mut self) -> Result<bool> {
+    let changed = self
+      .files
+      .iter()
+      .any(|(path, modified_)| modified(path) != *modified_);
+    if !changed {
+      return Ok(false)
+    }
+
+    let mut files = Vec::new();
+    match Self::build(&self.shaders, &mut files, &self.context) {
+      Ok(program) => {
+        self.program = program;
+        self.files = files;
+        let () = self.resolve_uniforms();
+        Ok(true)
+      },
+      Err(err) => {
+        let () = self
+          .files
+          .iter_mut()
+          .for_each(|(path, modified_)| *modified_ = modified(path));
+        // Also watch files seen only during the failed attempt, such as
+        // newly included ones, so that fixing them triggers a rebuild.
+        let () = files.into_iter().for_each(|(path, modified_)| {
+          if !self.files.iter().any(|(path_, _)| *path_ == path) {
+            self.files.push((path, modified_))
+          }
+        });
+        Err(err)
+      },
+    }
+  }
+
+  /// Retrieve a type checked handle to the uniform with the given name.
+  ///
+  /// In contrast to [`Program::uniform`], the handle stays valid across
+  /// rebuilds of the program: its location is updated automatically.
+  /// Should the uniform no longer be present in (or be incompatible
+  /// with) a rebuilt program, setting it has no effect.
+  pub fn uniform<T>(&self, name: &str) -> Result<Uniform<T>>
+  where
+    T: UniformData + ?Sized,
+  {
+    let uniform = self.program.uniform::<T>(name)?;
+    let mut uniforms = self.uniforms.borrow_mut();
+    // Forget about dropped handles, so that retrieving handles
+    // repeatedly does not accumulate entries.
+    let () = uniforms.retain(|(_name, _is_compatible, location)| location.strong_count() > 0);
+    let () = uniforms.push((
+      name.to_string(),
+      T::is_compatible,
+      Rc::downgrade(uniform.shared_location()),
+    ));
+    Ok(uniform)
+  }
+
+  /// Update the locations of all live uniform handles to refer to the
+  /// current program, forgetting about dropped ones.
+  fn resolve_uniforms(&self) {
+    let () = self
+      .uniforms
+      .borrow_mut()
+      .retain(|(name, is_compatible, location)| {
+        let Some(location) = location.upgrade() else {
+          return false
+        };
+
+        *location.borrow_mut() = self
+          .program
+          .uniform_info(name)
+          .filter(|(type_, size, _location)| is_compatible(*type_, *size))
+          .map(|(_type, _size, location)| (self.program.id(), location.clone()));
+        true
+      });
+  }
+
+  /// Retrieve the paths of all files the program is built from.
+  #[inline]
+  pub fn files(&self) -> impl Iterator<Item = &Path> {
+    self.files.iter().map(|(path, _modified)| path.as_path())
+  }
+}
+
+impl Deref for ReloadableProgram {
+  type Target = Program;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.program
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::env::temp_dir;
+  use std::fs::create_dir_all;
+  use std::fs::remove_dir_all;
+  use std::fs::write;
+  use std::fs::File;
+  use std::process;
+  use std::time::Duration;
+
+  use test_fork::fork;
+
+  use crate::sys::Gl as _;
+  use crate::winit::with_opengl_context;
+  use crate::ShaderError;
+
+
+  /// Write `contents` to the file at `path`, making sure that its
+  /// modification time changes.
+  fn update(path: &Path, contents: &str) {
+    let () = write(path, contents).unwrap();
+    let modified = SystemTime::now() + Duration::from_secs(60);
+    let () = File::options()
+      .write(true)
+      .open(path)
+      .unwrap()
+      .set_modified(modified)
+      .unwrap();
+  }
+
+  /// Check that we rebuild a program when one of its source files
+  /// changes and retain the previous one on error.
+  #[fork]
+  #[test]
+  fn program_reloading() {
+    let dir = temp_dir().join(format!("xgl-reload-{}", process::id()));
+    let () = create_dir_all(&dir).unwrap();
+    let vertex = dir.join("vertex.glsl");
+    let fragment = dir.join("fragment.glsl");
+    let color = dir.join("color.glsl");
+
+    let () = write(
+      &vertex,
+      "in vec4 position;\nvoid main() { gl_Position = position; }\n",
+    )
+    .unwrap();
+    let () = write(
+      &fragment,
+      "#include \"color.glsl\"\nout vec4 color;\nvoid main() { color = COLOR; }\n",
+    )
+    .unwrap();
+    let () = write(&color, "#define COLOR vec4(1.0f)\n").unwrap();
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let shaders = [
+        (sys::ShaderType::Vertex, vertex.as_path()),
+        (sys::ShaderType::Fragment, fragment.as_path()),
+      ];
+      let mut program = ReloadableProgram::new(&shaders, &gl_context).unwrap();
+      assert_eq!(program.files().count(), 3);
+      assert!(!program.reload().unwrap());
+
+      let () = update(&color, "#define COLOR not valid\n");
+      let err = program.reload().unwrap_err();
+      let err = err.downcast_ref::<ShaderError>().unwrap();
+      let diagnostic = &err.diagnostics()[0];
+      assert!(diagnostic
+        .file
+        .as_deref()
+        .unwrap()
+        .ends_with("fragment.glsl"));
+      // The previous program should still be usable.
+      let () = program.bind();
+      assert!(!program.reload().unwrap());
+
+      let () = update(&color, "#define COLOR vec4(0.5f)\n");
+      assert!(program.reload().unwrap());
+      assert!(!program.reload().unwrap());
+
+      // A newly included file should be watched even if it causes
+      // the rebuild to fail.
+      let alpha = dir.join("alpha.glsl");
+      let () = write(&alpha, "#define ALPHA not valid\n").unwrap();
+      let () = update(
+        &color,
+        "#include \"alpha.glsl\"\n#define COLOR vec4(ALPHA)\n",
+      );
+      let _err = program.reload().unwrap_err();
+      assert_eq!(program.files().count(), 4);
+      assert!(program.files().any(|path| path == alpha));
+
+      let () = update(&alpha, "#define ALPHA 1.0f\n");
+      assert!(program.reload().unwrap());
+      assert!(!program.reload().unwrap());
+    });
+
+    let () = remove_dir_all(&dir).unwrap();
+  }
+
+  /// Check that uniform handles stay usable across program rebuilds.
+  #[fork]
+  #[test]
+  fn uniform_reloading() {
+    let dir = temp_dir().join(format!("xgl-reload-uniform-{}", process::id()));
+    let () = create_dir_all(&dir).unwrap();
+    let vertex = dir.join("vertex.glsl");
+    let fragment = dir.join("fragment.glsl");
+
+    let () = write(
+      &vertex,
+      "in vec4 position;\nvoid main() { gl_Position = position; }\n",
+    )
+    .unwrap();
+    let () = write(
+      &fragment,
+      "uniform vec4 tint;\nout vec4 color;\nvoid main() { color = tint; }\n",
+    )
+    .unwrap();
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let shaders = [
+        (sys::ShaderType::Vertex, vertex.as_path()),
+        (sys::ShaderType::Fragment, fragment.as_path()),
+      ];
+      let mut program = ReloadableProgram::new(&shaders, &gl_context).unwrap();
+      let tint = program.uniform::<[f32; 4]>("tint").unwrap();
+      let () = program.bind();
+      let () = tint.set(&[1.0, 0.0, 0.0, 1.0]);
+      assert_eq!(program.uniform_value(&tint).unwrap(), [1.0, 0.0, 0.0, 1.0]);
+
+      // Introduce additional uniforms, so that `tint` likely ends up
+      // at a different location.
+      let () = update(
+        &fragment,
+        "uniform vec4 a;\nuniform vec4 b;\nuniform vec4 tint;\nout vec4 color;\nvoid main() { color = a + b + tint; }\n",
+      );
+      assert!(program.reload().unwrap());
+      let () = program.bind();
+      let () = tint.set(&[0.0, 1.0, 0.0, 1.0]);
+      let fresh = program.uniform::<[f32; 4]>("tint").unwrap();
+      assert_eq!(program.uniform_value(&fresh).unwrap(), [0.0, 1.0, 0.0, 1.0]);
+      let a = program.uniform::<[f32; 4]>("a").unwrap();
+      assert_eq!(program.uniform_value(&a).unwrap(), [0.0; 4]);
+
+      // Retrieving handles repeatedly should not accumulate entries
+      // for dropped ones.
+      let count = program.uniforms.borrow().len();
+      for _ in 0..8 {
+        let _tint = program.uniform::<[f32; 4]>("tint").unwrap();
+      }
+      assert_eq!(program.uniforms.borrow().len(), count + 1);
+
+      // Once the uniform is gone, setting it should have no effect.
+      let () = update(
+        &fragment,
+        "out vec4 color;\nvoid main() { color = vec4(1.0f); }\n",
+      );
+      assert!(program.reload().unwrap());
+      assert!(tint.location().is_none());
+      let () = tint.set(&[0.0, 0.0, 1.0, 1.0]);
+      assert_eq!(gl_context.error(), Ok(()));
+    });
+
+    let () = remove_dir_all(&dir).unwrap();
+  }
+}
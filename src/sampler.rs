@@ -0,0 +1,289 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::ops::Deref;
+
+use anyhow::ensure;
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::sys;
+use crate::sys::Gl as _;
+
+
+/// Builder infrastructure for a sampler.
+#[derive(Debug)]
+pub struct Builder<C = ()> {
+  /// The GL context.
+  context: C,
+  /// The filter to use when minimizing.
+  min_filter: sys::TextureFilter,
+  /// The filter to use when magnifying.
+  mag_filter: sys::TextureFilter,
+  /// The wrap modes along the S, T, and R axes.
+  wrap: [sys::TextureWrap; 3],
+  /// The minimum and maximum level of detail.
+  lod_range: (f32, f32),
+  /// The mode used for comparing against depth textures.
+  compare_mode: sys::TextureCompareMode,
+  /// The function used for comparing against depth textures.
+  compare_func: sys::Func,
+  /// The color used for texels outside of the texture.
+  #[cfg(not(target_arch = "wasm32"))]
+  border_color: [f32; 4],
+}
+
+impl Builder<()> {
+  /// Set the filter to use when minimizing textures.
+  pub fn set_min_filter(mut self, filter: sys::TextureFilter) -> Self {
+    self.min_filter = filter;
+    self
+  }
+
+  /// Set the filter to use when magnifying textures.
+  ///
+  /// Only [`Linear`][sys::TextureFilter::Linear] and
+  /// [`Nearest`][sys::TextureFilter::Nearest] are valid magnification
+  /// filters.
+  pub fn set_mag_filter(mut self, filter: sys::TextureFilter) -> Self {
+    self.mag_filter = filter;
+    self
+  }
+
+  /// Set the wrap mode along all axes.
+  pub fn set_wrap_mode(mut self, wrap: sys::TextureWrap) -> Self {
+    self.wrap = [wrap; 3];
+    self
+  }
+
+  /// Set the wrap mode along a single axis.
+  pub fn set_axis_wrap_mode(mut self, axis: sys::TextureWrapAxis, wrap: sys::TextureWrap) -> Self {
+    self.wrap[sys::wrap_axis_index(axis)] = wrap;
+    self
+  }
+
+  /// Set the range of mipmap levels of detail that can be selected.
+  pub fn set_lod_range(mut self, min: f32, max: f32) -> Self {
+    self.lod_range = (min, max);
+    self
+  }
+
+  /// Set the mode used for comparing against depth textures.
+  pub fn set_compare_mode(mut self, mode: sys::TextureCompareMode) -> Self {
+    self.compare_mode = mode;
+    self
+  }
+
+  /// Set the function used for comparing against depth textures, if
+  /// comparison is enabled via [`Builder::set_compare_mode`].
+  pub fn set_compare_func(mut self, func: sys::Func) -> Self {
+    self.compare_func = func;
+    self
+  }
+
+  /// Set the color used for texels outside of the texture when using
+  /// [`ClampToBorder`][sys::TextureWrap::ClampToBorder] wrapping.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn set_border_color(mut self, color: [f32; 4]) -> Self {
+    self.border_color = color;
+    self
+  }
+
+  /// Set the sampler's GL context.
+  pub fn set_context(self, context: &sys::Context) -> Builder<sys::Context> {
+    let Self {
+      context: (),
+      min_filter,
+      mag_filter,
+      wrap,
+      lod_range,
+      compare_mode,
+      compare_func,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color,
+    } = self;
+
+    Builder {
+      context: context.clone(),
+      min_filter,
+      mag_filter,
+      wrap,
+      lod_range,
+      compare_mode,
+      compare_func,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color,
+    }
+  }
+}
+
+impl Builder<sys::Context> {
+  /// Create the sampler.
+  pub fn build(&self) -> Result<Sampler> {
+    let Self {
+      context,
+      min_filter,
+      mag_filter,
+      wrap,
+      lod_range: (min_lod, max_lod),
+      compare_mode,
+      compare_func,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color,
+    } = self;
+
+    ensure!(
+      matches!(
+        mag_filter,
+        sys::TextureFilter::Linear | sys::TextureFilter::Nearest
+      ),
+      "{mag_filter:?} is not a valid magnification filter"
+    );
+
+    let sampler = Sampler {
+      context: context.clone(),
+      sampler: context
+        .create_sampler()
+        .context("failed to create sampler object")?,
+    };
+
+    let () = context.set_sampler_filter(&sampler, sys::TextureFilterType::Minimize, *min_filter);
+    let () = context.set_sampler_filter(&sampler, sys::TextureFilterType::Magnify, *mag_filter);
+    let () = sys::WRAP_AXES
+      .into_iter()
+      .zip(wrap)
+      .for_each(|(axis, wrap)| context.set_sampler_wrap(&sampler, axis, *wrap));
+    let () = context.set_sampler_lod_range(&sampler, *min_lod, *max_lod);
+    let () = context.set_sampler_compare_mode(&sampler, *compare_mode);
+    let () = context.set_sampler_compare_func(&sampler, *compare_func);
+    #[cfg(not(target_arch = "wasm32"))]
+    let () = context.set_sampler_border_color(&sampler, *border_color);
+
+    Ok(sampler)
+  }
+}
+
+/// Instantiate a "default" [`Builder`].
+///
+/// The defaults are as follows:
+/// - filters: [`Linear`][sys::TextureFilter::Linear]
+/// - wrap mode: [`Repeat`][sys::TextureWrap::Repeat]
+/// - level of detail range: `-1000.0` to `1000.0`
+/// - compare mode: [`None`][sys::TextureCompareMode::None]
+/// - compare function: [`LessOrEqual`][sys::Func::LessOrEqual]
+/// - border color (OpenGL only): transparent black
+impl Default for Builder<()> {
+  #[inline]
+  fn default() -> Self {
+    Self {
+      context: (),
+      min_filter: sys::TextureFilter::Linear,
+      mag_filter: sys::TextureFilter::Linear,
+      wrap: [sys::TextureWrap::Repeat; 3],
+      lod_range: (-1000.0, 1000.0),
+      compare_mode: sys::TextureCompareMode::None,
+      compare_func: sys::Func::LessOrEqual,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color: [0.0; 4],
+    }
+  }
+}
+
+
+/// A sampler, encapsulating the state used for sampling textures.
+///
+/// A sampler bound to a texture unit overrides the sampling state of
+/// the texture bound to the same unit, allowing for the same texture to
+/// be sampled in different ways.
+#[derive(Debug)]
+pub struct Sampler {
+  /// The GL context.
+  context: sys::Context,
+  /// The sampler object.
+  sampler: sys::Sampler,
+}
+
+impl Sampler {
+  /// Create a sampler builder.
+  #[inline]
+  pub fn builder() -> Builder {
+    Builder::default()
+  }
+
+  /// Bind the sampler to the given texture unit.
+  #[inline]
+  pub fn bind(&self, unit: u32) {
+    self.context.bind_sampler(unit, Some(&self.sampler))
+  }
+
+  /// Unbind any sampler from the given texture unit, reverting to the
+  /// sampling state of the texture bound to it.
+  #[inline]
+  pub fn unbind(&self, unit: u32) {
+    self.context.bind_sampler(unit, None)
+  }
+}
+
+impl Deref for Sampler {
+  type Target = sys::Sampler;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.sampler
+  }
+}
+
+impl Drop for Sampler {
+  #[inline]
+  fn drop(&mut self) {
+    let () = self.context.delete_sampler(&self.sampler);
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use test_fork::fork;
+
+  use crate::winit::with_opengl_context;
+
+
+  /// Check that we can create and bind samplers.
+  #[fork]
+  #[test]
+  fn sampler_creation() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let sampler = Sampler::builder()
+        .set_min_filter(sys::TextureFilter::NearestMipmapNearest)
+        .set_mag_filter(sys::TextureFilter::Nearest)
+        .set_wrap_mode(sys::TextureWrap::ClampToEdge)
+        .set_axis_wrap_mode(sys::TextureWrapAxis::T, sys::TextureWrap::Repeat)
+        .set_lod_range(0.0, 4.0)
+        .set_compare_mode(sys::TextureCompareMode::RefToTexture)
+        .set_compare_func(sys::Func::Greater)
+        .set_border_color([1.0, 0.0, 0.0, 1.0])
+        .set_context(&gl_context)
+        .build()
+        .unwrap();
+
+      let () = sampler.bind(0);
+      let () = sampler.bind(3);
+      let () = sampler.unbind(3);
+      let () = sampler.unbind(0);
+      assert_eq!(gl_context.error(), Ok(()));
+
+      let err = Sampler::builder()
+        .set_mag_filter(sys::TextureFilter::LinearMipmapLinear)
+        .set_context(&gl_context)
+        .build()
+        .unwrap_err();
+      assert!(
+        err.to_string().contains("not a valid magnification filter"),
+        "{err:#}"
+      );
+    })
+  }
+}
@@ -3,12 +3,17 @@
 
 use std::ops::Deref;
 
-use anyhow::anyhow;
+use anyhow::ensure;
 use anyhow::Context as _;
 use anyhow::Result;
 
+use crate::dialect::translate;
 use crate::sys;
 use crate::sys::Gl as _;
+use crate::PreprocessedSource;
+use crate::ShaderDiagnostic;
+use crate::ShaderError;
+use crate::ShaderSource;
 
 
 /// A shader object.
@@ -18,6 +23,8 @@ pub struct Shader {
   context: sys::Context,
   /// The shader.
   shader: sys::Shader,
+  /// Diagnostics reported while compiling the shader.
+  diagnostics: Vec<ShaderDiagnostic>,
 }
 
 impl Shader {
@@ -44,28 +51,131 @@ impl Shader {
     version
   }
 
+  /// Create a shader of the given type from source code.
+  ///
+  /// Code starting with a `#version 330 core` or `#version 300 es`
+  /// directive is translated into the dialect required by the backend
+  /// in use, covering the version directive, default precision
+  /// qualifiers, `f` suffixes on floating point literals, GLSL 1.x
+  /// texture lookup functions, and `layout(location)` qualifiers on
+  /// variables passed between stages.
+  ///
+  /// On compilation failure, the reported error wraps a
+  /// [`ShaderError`] providing access to the individual diagnostics.
   pub fn new(
     shader_type: sys::ShaderType,
     shader_file: &str,
     context: &sys::Context,
   ) -> Result<Self> {
+    Self::compile(shader_type, shader_file, None, context)
+  }
+
+  /// Create a shader from a [`ShaderSource`], preprocessing it first.
+  ///
+  /// Source locations in diagnostics refer to the original (included)
+  /// files and lines.
+  pub fn from_source(
+    shader_type: sys::ShaderType,
+    source: &ShaderSource<'_>,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let preprocessed = source
+      .preprocess()
+      .with_context(|| format!("failed to preprocess shader `{}`", source.name()))?;
+    Self::from_preprocessed(shader_type, &preprocessed, context)
+  }
+
+  /// Create a shader from already preprocessed source code.
+  pub(crate) fn from_preprocessed(
+    shader_type: sys::ShaderType,
+    preprocessed: &PreprocessedSource,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    Self::compile(
+      shader_type,
+      preprocessed.source(),
+      Some(preprocessed),
+      context,
+    )
+  }
+
+  fn compile(
+    shader_type: sys::ShaderType,
+    shader_file: &str,
+    preprocessed: Option<&PreprocessedSource>,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let translated = translate(shader_file, shader_type);
+    Self::compile_translated(shader_type, shader_file, &translated, preprocessed, context)
+  }
+
+  /// Compile `translated`, the translation of `shader_file` into the
+  /// dialect used by the backend.
+  fn compile_translated(
+    shader_type: sys::ShaderType,
+    shader_file: &str,
+    translated: &str,
+    preprocessed: Option<&PreprocessedSource>,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    ensure!(
+      shader_type.is_supported(),
+      "{} shaders are not supported by the backend in use",
+      shader_type.as_str()
+    );
+
     let shader = context
       .create_shader(shader_type)
       .context("failed to create shader object")?;
-    let slf = Self {
+    let mut slf = Self {
       context: context.clone(),
       shader,
+      diagnostics: Vec::new(),
+    };
+
+    let () = context.set_shader_source(&slf.shader, translated);
+    let result = context.compile_shader(&slf.shader);
+    let log = match &result {
+      Ok(()) => context.shader_info_log(&slf.shader),
+      Err(log) => log.clone(),
     };
+    // TODO: Should use `String::from_utf8_lossy_owned` once stable.
+    let log = String::from_utf8_lossy(log.as_slice());
+    let mut diagnostics = ShaderDiagnostic::parse_log(&log);
+
+    // Source code lines have to be looked up before remapping
+    // diagnostics, as they are relative to `shader_file`. Translation
+    // preserves line numbers, but may add lines of its own, so we
+    // refer to the untranslated code.
+    let excerpts = diagnostics
+      .iter()
+      .map(|diagnostic| {
+        let line = diagnostic.line?.checked_sub(1)?;
+        shader_file.lines().nth(line).map(str::to_string)
+      })
+      .collect();
+    if let Some(preprocessed) = preprocessed {
+      let () = diagnostics
+        .iter_mut()
+        .for_each(|diagnostic| preprocessed.remap(diagnostic));
+    }
 
-    let () = context.set_shader_source(&slf.shader, shader_file);
-    let () = context.compile_shader(&slf.shader).map_err(|log| {
-      // TODO: Should use `String::from_utf8_lossy_owned` once stable.
-      let log = String::from_utf8_lossy(log.as_slice());
-      anyhow!("failed to compile {} shader: {log}", shader_type.as_str())
-    })?;
+    if result.is_err() {
+      let operation = format!("failed to compile {} shader", shader_type.as_str());
+      let err = ShaderError::new(operation, diagnostics, excerpts);
+      return Err(err.into())
+    }
 
+    slf.diagnostics = diagnostics;
     Ok(slf)
   }
+
+  /// Retrieve the diagnostics (typically warnings) reported when
+  /// compiling the shader.
+  #[inline]
+  pub fn diagnostics(&self) -> &[ShaderDiagnostic] {
+    &self.diagnostics
+  }
 }
 
 impl Deref for Shader {
@@ -89,9 +199,13 @@ impl Drop for Shader {
 mod tests {
   use super::*;
 
+  use std::collections::HashMap;
+
   use test_fork::fork;
 
+  use crate::dialect::translate_to;
   use crate::winit::with_opengl_context;
+  use crate::DiagnosticSeverity;
 
 
   /// Check that we can create a vertex shader.
@@ -156,4 +270,69 @@ not a valid identifier here
       assert!(err.to_string().contains("syntax error"), "{err:#}");
     })
   }
+
+  /// Check that compilation errors of shaders created from a
+  /// [`ShaderSource`] refer to the original files.
+  #[fork]
+  #[test]
+  fn shader_source_creation_failure() {
+    let resolver = HashMap::from([(
+      "color.glsl".to_string(),
+      "vec4 color() {\n  not a valid identifier here\n}\n".to_string(),
+    )]);
+    let source = ShaderSource::new(
+      "main.glsl",
+      "#include \"color.glsl\"\nout vec4 color;\nvoid main() { color = color(); }\n",
+    )
+    .set_include_resolver(&resolver);
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let err = Shader::from_source(sys::ShaderType::Fragment, &source, &gl_context).unwrap_err();
+      assert!(err.to_string().contains("color.glsl:2"), "{err:#}");
+
+      let err = err.downcast_ref::<ShaderError>().unwrap();
+      let diagnostic = &err.diagnostics()[0];
+      assert_eq!(diagnostic.severity, DiagnosticSeverity::Error);
+      assert_eq!(diagnostic.file.as_deref(), Some("color.glsl"));
+      assert_eq!(diagnostic.line, Some(2));
+    })
+  }
+
+  /// Check that compilation errors of desktop shaders translated to
+  /// GLSL ES point at the original source code.
+  #[fork]
+  #[test]
+  fn translated_shader_creation_failure() {
+    let shader_code = r#"#version 330 core
+
+out vec4 color;
+
+void main() {
+  color = not_a_valid_identifier;
+}
+"#;
+    let translated = translate_to(shader_code, sys::ShaderType::Fragment, true);
+    assert_ne!(translated, shader_code);
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let err = Shader::compile_translated(
+        sys::ShaderType::Fragment,
+        shader_code,
+        &translated,
+        None,
+        &gl_context,
+      )
+      .unwrap_err();
+      let err = err.downcast_ref::<ShaderError>().unwrap();
+      assert_eq!(err.diagnostics()[0].line, Some(6));
+      assert!(
+        err
+          .to_string()
+          .contains(" 6 |   color = not_a_valid_identifier;"),
+        "{err:#}"
+      );
+    })
+  }
 }
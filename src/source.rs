@@ -0,0 +1,588 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::read_to_string;
+use std::hash::BuildHasher;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use anyhow::bail;
+use anyhow::Context as _;
+use anyhow::Result;
+
+use crate::sys;
+use crate::Shader;
+use crate::ShaderDiagnostic;
+
+
+/// Default precision qualifiers for GLSL ES, covering all types that
+/// lack a default precision in fragment shaders.
+pub(crate) const ES_PRECISION: &str = "precision highp float; precision highp int; \
+precision highp sampler3D; precision highp sampler2DShadow; \
+precision highp samplerCubeShadow; precision highp sampler2DArray; \
+precision highp sampler2DArrayShadow;";
+
+
+/// Strip comments from a line of shader code.
+///
+/// `in_comment` tracks whether a block comment is open at the start of
+/// the line and is updated to reflect the state at its end.
+pub(crate) fn strip_comments<'l>(line: &'l str, in_comment: &mut bool) -> Cow<'l, str> {
+  if !*in_comment && !line.contains("/*") && !line.contains("//") {
+    return Cow::Borrowed(line)
+  }
+
+  let mut stripped = String::with_capacity(line.len());
+  let mut rest = line;
+  loop {
+    if *in_comment {
+      let Some(end) = rest.find("*/") else { break };
+      // A block comment acts as a token separator.
+      let () = stripped.push(' ');
+      *in_comment = false;
+      rest = &rest[end + 2..];
+    } else {
+      match (rest.find("/*"), rest.find("//")) {
+        (Some(block), Some(line)) if line < block => {
+          let () = stripped.push_str(&rest[..line]);
+          break
+        },
+        (Some(block), _) => {
+          let () = stripped.push_str(&rest[..block]);
+          *in_comment = true;
+          rest = &rest[block + 2..];
+        },
+        (None, Some(line)) => {
+          let () = stripped.push_str(&rest[..line]);
+          break
+        },
+        (None, None) => {
+          let () = stripped.push_str(rest);
+          break
+        },
+      }
+    }
+  }
+  Cow::Owned(stripped)
+}
+
+/// Determine the number of leading lines making up the block of
+/// `#extension` directives at the start of `lines`, if any.
+///
+/// Only empty lines, comments, and `#extension` and `#pragma`
+/// directives are considered part of the block. Code that has to
+/// follow all `#extension` directives, such as default precision
+/// qualifiers, can be inserted after the returned number of lines.
+pub(crate) fn extension_block_end<'s>(lines: impl IntoIterator<Item = &'s str>) -> usize {
+  let mut in_comment = false;
+  let mut end = 0;
+
+  for (idx, line) in lines.into_iter().enumerate() {
+    let line = strip_comments(line, &mut in_comment);
+    let line = line.trim();
+    if line.is_empty() {
+      continue
+    }
+
+    let directive = line
+      .strip_prefix('#')
+      .and_then(|directive| directive.split_whitespace().next());
+    match directive {
+      Some("extension") if !in_comment => end = idx + 1,
+      Some("extension" | "pragma") => (),
+      _ => break,
+    }
+  }
+  end
+}
+
+
+/// A trait for resolving `#include` directives to source code.
+pub trait IncludeResolver: Debug {
+  /// Retrieve the source code of the file with the given name, as it
+  /// appears in an `#include "..."` directive.
+  fn resolve(&self, name: &str) -> Result<String>;
+
+  /// Retrieve the file system path of the file with the given name, if
+  /// it is backed by one.
+  fn path(&self, _name: &str) -> Option<PathBuf> {
+    None
+  }
+}
+
+/// An in-memory resolver, mapping names to source code.
+impl<S> IncludeResolver for HashMap<String, String, S>
+where
+  S: BuildHasher,
+{
+  fn resolve(&self, name: &str) -> Result<String> {
+    let source = self
+      .get(name)
+      .with_context(|| format!("include file `{name}` not found"))?;
+    Ok(source.clone())
+  }
+}
+
+
+/// An include resolver reading files relative to a root directory.
+#[derive(Clone, Debug)]
+pub struct FsIncludeResolver {
+  /// The directory to resolve include names relative to.
+  root: PathBuf,
+}
+
+impl FsIncludeResolver {
+  /// Create a new resolver resolving include names relative to `root`.
+  pub fn new(root: impl Into<PathBuf>) -> Self {
+    Self { root: root.into() }
+  }
+}
+
+impl IncludeResolver for FsIncludeResolver {
+  fn resolve(&self, name: &str) -> Result<String> {
+    let path = self.root.join(name);
+    let source = read_to_string(&path)
+      .with_context(|| format!("failed to read include file `{}`", path.display()))?;
+    Ok(source)
+  }
+
+  #[inline]
+  fn path(&self, name: &str) -> Option<PathBuf> {
+    Some(self.root.join(name))
+  }
+}
+
+
+/// Shader source code as produced by [`ShaderSource::preprocess`].
+#[derive(Clone, Debug)]
+pub struct PreprocessedSource {
+  /// The preprocessed source code.
+  source: String,
+  /// The names of all files contributing to `source`.
+  files: Vec<String>,
+  /// The original file (as index into `files`) and line (1-based) of
+  /// each line in `source`, if any.
+  lines: Vec<Option<(usize, usize)>>,
+}
+
+impl PreprocessedSource {
+  /// Retrieve the preprocessed source code.
+  #[inline]
+  pub fn source(&self) -> &str {
+    &self.source
+  }
+
+  /// Retrieve the names of all files contributing to the preprocessed
+  /// source code, starting with the main file.
+  #[inline]
+  pub fn files(&self) -> &[String] {
+    &self.files
+  }
+
+  /// Map a (1-based) line of the preprocessed source code back to the
+  /// name of the file it originated from and the (1-based) line in it.
+  ///
+  /// `None` is returned for out-of-bounds lines as well as for those
+  /// generated during preprocessing.
+  pub fn origin(&self, line: usize) -> Option<(&str, usize)> {
+    let (file, line) = (*self.lines.get(line.checked_sub(1)?)?)?;
+    Some((&self.files[file], line))
+  }
+
+  /// Rewrite the location of a diagnostic reported for the
+  /// preprocessed source code to refer to the original file and line.
+  pub(crate) fn remap(&self, diagnostic: &mut ShaderDiagnostic) {
+    if let Some((file, line)) = diagnostic.line.and_then(|line| self.origin(line)) {
+      diagnostic.file = Some(file.to_string());
+      diagnostic.line = Some(line);
+    }
+  }
+}
+
+
+/// A builder for shader source code, taking care of injecting the
+/// `#version` directive, `#define`s, and resolving `#include`s.
+#[derive(Clone, Debug)]
+pub struct ShaderSource<'r> {
+  /// The name of the shader's main file, used in error messages.
+  name: String,
+  /// The shader's source code.
+  source: String,
+  /// `#define`s to prepend, as name-value pairs.
+  defines: Vec<(String, String)>,
+  /// The resolver to use for `#include` directives.
+  resolver: Option<&'r dyn IncludeResolver>,
+}
+
+impl<'r> ShaderSource<'r> {
+  /// Create a new shader source object from a file name and the
+  /// corresponding source code.
+  pub fn new(name: impl Into<String>, source: impl Into<String>) -> Self {
+    Self {
+      name: name.into(),
+      source: source.into(),
+      defines: Vec::new(),
+      resolver: None,
+    }
+  }
+
+  /// Add a `#define` with the given name and value.
+  ///
+  /// If a `#define` with the same name has been set already, its value
+  /// is replaced.
+  pub fn set_define(mut self, name: &str, value: &str) -> Self {
+    if let Some((_name, value_)) = self.defines.iter_mut().find(|(name_, _)| name_ == name) {
+      *value_ = value.to_string();
+    } else {
+      let () = self.defines.push((name.to_string(), value.to_string()));
+    }
+    self
+  }
+
+  /// Set the resolver to use for `#include` directives.
+  pub fn set_include_resolver(mut self, resolver: &'r dyn IncludeResolver) -> Self {
+    self.resolver = Some(resolver);
+    self
+  }
+
+  /// Retrieve the name of the shader's main file.
+  #[inline]
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  /// Preprocess the shader source code.
+  ///
+  /// A `#version` directive matching [`Shader::glsl_version`] (along
+  /// with default precision qualifiers for GLSL ES, following any
+  /// leading `#extension` directives) is injected, unless the source
+  /// already starts with one. Afterwards all configured `#define`s are
+  /// emitted and `#include` directives outside of comments are
+  /// replaced with the contents of the file they refer to.
+  pub fn preprocess(&self) -> Result<PreprocessedSource> {
+    let (.., suffix) = sys::version();
+    self.preprocess_for(suffix == Some("es"))
+  }
+
+  fn preprocess_for(&self, es: bool) -> Result<PreprocessedSource> {
+    let mut preprocessed = PreprocessedSource {
+      source: String::new(),
+      files: vec![self.name.clone()],
+      lines: Vec::new(),
+    };
+
+    let version = self
+      .source
+      .lines()
+      .enumerate()
+      .find(|(_, line)| !line.trim().is_empty())
+      .filter(|(_, line)| line.trim_start().starts_with("#version"));
+
+    let line_count = self.source.lines().count();
+    let mut stack = vec![self.name.clone()];
+
+    if let Some((idx, line)) = version {
+      let () = push_line(&mut preprocessed, line, Some((0, idx + 1)));
+      let () = self.push_defines(&mut preprocessed);
+      let () = self.process(
+        &mut preprocessed,
+        &mut stack,
+        &self.source,
+        idx + 1..line_count,
+      )?;
+    } else if es {
+      let version = Shader::glsl_version();
+      let () = push_line(&mut preprocessed, &format!("#version {version}"), None);
+      let () = self.push_defines(&mut preprocessed);
+      // Default precision qualifiers are regular statements and have
+      // to follow any `#extension` directives.
+      let end = extension_block_end(self.source.lines());
+      let () = self.process(&mut preprocessed, &mut stack, &self.source, 0..end)?;
+      let () = push_line(&mut preprocessed, ES_PRECISION, None);
+      let () = self.process(&mut preprocessed, &mut stack, &self.source, end..line_count)?;
+    } else {
+      let version = Shader::glsl_version();
+      let () = push_line(&mut preprocessed, &format!("#version {version} core"), None);
+      let () = self.push_defines(&mut preprocessed);
+      let () = self.process(&mut preprocessed, &mut stack, &self.source, 0..line_count)?;
+    }
+    Ok(preprocessed)
+  }
+
+  fn push_defines(&self, preprocessed: &mut PreprocessedSource) {
+    for (name, value) in &self.defines {
+      let () = push_line(preprocessed, &format!("#define {name} {value}"), None);
+    }
+  }
+
+  /// Process the given range of lines of `source`, originating from the
+  /// file at the top of `stack`.
+  fn process(
+    &self,
+    preprocessed: &mut PreprocessedSource,
+    stack: &mut Vec<String>,
+    source: &str,
+    lines: Range<usize>,
+  ) -> Result<()> {
+    let name = stack.last().unwrap().clone();
+    let file = preprocessed
+      .files
+      .iter()
+      .position(|file| *file == name)
+      .unwrap_or_else(|| {
+        preprocessed.files.push(name.clone());
+        preprocessed.files.len() - 1
+      });
+
+    let mut in_comment = false;
+    for (idx, line) in source
+      .lines()
+      .enumerate()
+      .skip(lines.start)
+      .take(lines.len())
+    {
+      let line_no = idx + 1;
+      let include = strip_comments(line, &mut in_comment);
+      let include = include
+        .trim_start()
+        .strip_prefix("#include")
+        .map(str::trim)
+        .map(|include| {
+          include
+            .strip_prefix('"')
+            .and_then(|include| include.strip_suffix('"'))
+            .with_context(|| format!("{name}:{line_no}: malformed include directive: `{line}`"))
+        })
+        .transpose()?;
+
+      if let Some(include) = include {
+        if stack.iter().any(|file| file == include) {
+          bail!("{name}:{line_no}: recursive include of `{include}`")
+        }
+
+        let resolver = self.resolver.with_context(|| {
+          format!("{name}:{line_no}: no resolver present for including `{include}`")
+        })?;
+        let included = resolver
+          .resolve(include)
+          .with_context(|| format!("{name}:{line_no}: failed to include `{include}`"))?;
+
+        let () = stack.push(include.to_string());
+        let count = included.lines().count();
+        let () = self.process(preprocessed, stack, &included, 0..count)?;
+        let _include = stack.pop();
+      } else {
+        let () = push_line(preprocessed, line, Some((file, line_no)));
+      }
+    }
+    Ok(())
+  }
+}
+
+fn push_line(preprocessed: &mut PreprocessedSource, line: &str, origin: Option<(usize, usize)>) {
+  let () = preprocessed.source.push_str(line);
+  let () = preprocessed.source.push('\n');
+  let () = preprocessed.lines.push(origin);
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Create an in-memory include resolver with a few files.
+  fn resolver() -> HashMap<String, String> {
+    HashMap::from([
+      (
+        "light.glsl".to_string(),
+        "#include \"common.glsl\"\nvec3 light() { return vec3(SCALE); }\n".to_string(),
+      ),
+      ("common.glsl".to_string(), "float common;\n".to_string()),
+      (
+        "recursive.glsl".to_string(),
+        "#include \"recursive.glsl\"\n".to_string(),
+      ),
+    ])
+  }
+
+  /// Check that we inject `#version` and `#define` directives and
+  /// resolve includes.
+  #[test]
+  fn preprocessing() {
+    let resolver = resolver();
+    let source = ShaderSource::new(
+      "main.glsl",
+      "out vec4 color;\n#include \"light.glsl\"\nvoid main() {}\n",
+    )
+    .set_define("SCALE", "2.0")
+    .set_include_resolver(&resolver);
+
+    let preprocessed = source.preprocess().unwrap();
+    let expected = format!(
+      "#version {} core\n#define SCALE 2.0\nout vec4 color;\nfloat common;\nvec3 light() {{ return vec3(SCALE); }}\nvoid main() {{}}\n",
+      Shader::glsl_version()
+    );
+    assert_eq!(preprocessed.source(), expected);
+    assert_eq!(preprocessed.origin(0), None);
+    assert_eq!(preprocessed.origin(1), None);
+    assert_eq!(preprocessed.origin(2), None);
+    assert_eq!(preprocessed.origin(3), Some(("main.glsl", 1)));
+    assert_eq!(preprocessed.origin(4), Some(("common.glsl", 1)));
+    assert_eq!(preprocessed.origin(5), Some(("light.glsl", 2)));
+    assert_eq!(preprocessed.origin(6), Some(("main.glsl", 3)));
+    assert_eq!(preprocessed.origin(7), None);
+  }
+
+  /// Check that setting a `#define` twice replaces its value.
+  #[test]
+  fn define_replacement() {
+    let source = ShaderSource::new("main.glsl", "void main() {}\n")
+      .set_define("FOO", "1")
+      .set_define("BAR", "2")
+      .set_define("FOO", "3");
+    let preprocessed = source.preprocess_for(false).unwrap();
+    let lines = preprocessed.source().lines().collect::<Vec<_>>();
+    assert_eq!(
+      lines[1..],
+      ["#define FOO 3", "#define BAR 2", "void main() {}"]
+    );
+  }
+
+  /// Make sure that an existing `#version` directive is preserved.
+  #[test]
+  fn version_preservation() {
+    let source =
+      ShaderSource::new("main.glsl", "\n#version 300 es\nvoid main() {}\n").set_define("FOO", "1");
+    let preprocessed = source.preprocess().unwrap();
+    assert_eq!(
+      preprocessed.source(),
+      "#version 300 es\n#define FOO 1\nvoid main() {}\n"
+    );
+    assert_eq!(preprocessed.origin(1), Some(("main.glsl", 2)));
+    assert_eq!(preprocessed.origin(3), Some(("main.glsl", 3)));
+  }
+
+  /// Check that default precision qualifiers for GLSL ES are injected
+  /// after any leading `#extension` directives.
+  #[test]
+  fn es_precision_placement() {
+    let code =
+      "/* Extensions\n */\n#extension GL_OES_texture_3D : enable\n// Code.\nvoid main() {}\n";
+    let source = ShaderSource::new("main.glsl", code).set_define("FOO", "1");
+    let preprocessed = source.preprocess_for(true).unwrap();
+    let lines = preprocessed.source().lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with("#version "), "{lines:?}");
+    assert_eq!(
+      lines[1..],
+      [
+        "#define FOO 1",
+        "/* Extensions",
+        " */",
+        "#extension GL_OES_texture_3D : enable",
+        ES_PRECISION,
+        "// Code.",
+        "void main() {}",
+      ]
+    );
+    assert_eq!(preprocessed.origin(5), Some(("main.glsl", 3)));
+    assert_eq!(preprocessed.origin(6), None);
+    assert_eq!(preprocessed.origin(7), Some(("main.glsl", 4)));
+
+    let source = ShaderSource::new("main.glsl", "void main() {}\n");
+    let preprocessed = source.preprocess_for(true).unwrap();
+    let lines = preprocessed.source().lines().collect::<Vec<_>>();
+    assert_eq!(lines[1..], [ES_PRECISION, "void main() {}"]);
+  }
+
+  /// Make sure that `#include` directives inside comments are ignored.
+  #[test]
+  fn commented_includes() {
+    let resolver = resolver();
+    let code = "// #include \"foo.glsl\"\n/*\n#include \"foo.glsl\"\n*/\n#include \"common.glsl\" // Common.\nvoid main() {}\n";
+    let source = ShaderSource::new("main.glsl", code).set_include_resolver(&resolver);
+    let preprocessed = source.preprocess_for(false).unwrap();
+    let lines = preprocessed.source().lines().collect::<Vec<_>>();
+    assert_eq!(
+      lines[1..],
+      [
+        "// #include \"foo.glsl\"",
+        "/*",
+        "#include \"foo.glsl\"",
+        "*/",
+        "float common;",
+        "void main() {}",
+      ]
+    );
+  }
+
+  /// Check that include errors are reported properly.
+  #[test]
+  fn include_errors() {
+    let source = ShaderSource::new("main.glsl", "#include \"light.glsl\"\n");
+    let err = source.preprocess().unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "main.glsl:1: no resolver present for including `light.glsl`"
+    );
+
+    let resolver = resolver();
+    let source =
+      ShaderSource::new("main.glsl", "\n#include \"foo.glsl\"\n").set_include_resolver(&resolver);
+    let err = source.preprocess().unwrap_err();
+    assert_eq!(err.to_string(), "main.glsl:2: failed to include `foo.glsl`");
+
+    let source =
+      ShaderSource::new("main.glsl", "#include <light.glsl>\n").set_include_resolver(&resolver);
+    let err = source.preprocess().unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "main.glsl:1: malformed include directive: `#include <light.glsl>`"
+    );
+
+    let source = ShaderSource::new("main.glsl", "#include \"recursive.glsl\"\n")
+      .set_include_resolver(&resolver);
+    let err = source.preprocess().unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "recursive.glsl:1: recursive include of `recursive.glsl`"
+    );
+  }
+
+  /// Check that diagnostic locations are mapped back to the original
+  /// files and lines.
+  #[test]
+  fn diagnostic_remapping() {
+    let resolver = resolver();
+    let source = ShaderSource::new("main.glsl", "#include \"light.glsl\"\nvoid main() {}\n")
+      .set_include_resolver(&resolver);
+    let preprocessed = source.preprocess().unwrap();
+
+    let log = "0:3(12): error: `SCALE' undeclared\n0(4) : error C0000: syntax error\nERROR: 0:2: 'common' : redefinition\nerror: something else";
+    let mut diagnostics = ShaderDiagnostic::parse_log(log);
+    let () = diagnostics
+      .iter_mut()
+      .for_each(|diagnostic| preprocessed.remap(diagnostic));
+
+    let locations = diagnostics
+      .iter()
+      .map(|diagnostic| {
+        (
+          diagnostic.file.as_deref(),
+          diagnostic.line,
+          diagnostic.column,
+        )
+      })
+      .collect::<Vec<_>>();
+    let expected = [
+      (Some("light.glsl"), Some(2), Some(12)),
+      (Some("main.glsl"), Some(2), None),
+      (Some("common.glsl"), Some(1), None),
+      (None, None, None),
+    ];
+    assert_eq!(locations, expected);
+  }
+}
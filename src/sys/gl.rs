@@ -3,12 +3,15 @@
 
 use std::error::Error as StdError;
 use std::fmt::Debug;
+use std::fmt::Display;
 
 
 pub(super) mod protected {
   pub trait Sealed {}
 
+  impl Sealed for u8 {}
   impl Sealed for u16 {}
+  impl Sealed for u32 {}
 }
 
 
@@ -26,6 +29,7 @@ pub trait Gl: protected::Sealed {
   // Constant-y types.
   type Capability: Copy + Debug;
   type ClearMask: Copy + Debug;
+  type CompressedTextureFormat: Copy + Debug;
   type CullFace: Copy + Debug;
   type Factor: Copy + Debug;
   type FrontFace: Copy + Debug;
@@ -39,6 +43,8 @@ pub trait Gl: protected::Sealed {
   type TexturePixelFormat: Copy + Debug;
   type TextureTarget: Copy + Debug;
   type TextureWrap: Copy + Debug;
+  type TextureWrapAxis: Copy + Debug;
+  type TransformFeedbackMode: Copy + Debug;
   type Type: Copy + Debug;
   type VertexBufferTarget: Copy + Debug;
   type VertexBufferUsage: Copy + Debug;
@@ -46,6 +52,7 @@ pub trait Gl: protected::Sealed {
   // Object types.
   type Framebuffer: Debug;
   type Program: Debug;
+  type Sampler: Debug;
   type Shader: Debug;
   type Texture: Debug;
   type VertexArrayObject: Debug;
@@ -53,7 +60,8 @@ pub trait Gl: protected::Sealed {
 
   // Misc types.
   type FramebufferStatus: Debug + Eq;
-  type UniformLocation: Debug;
+  type GlslType: Copy + Debug + Display + Eq;
+  type UniformLocation: Clone + Debug;
 
 
   fn error(&self) -> Result<(), Self::Error>;
@@ -70,18 +78,81 @@ pub trait Gl: protected::Sealed {
   fn set_clear_color(&self, r: f32, g: f32, b: f32, a: f32);
 
   fn set_pixel_unpack_alignment(&self, alignment: u32);
+  /// Set the number of pixels per row in client memory for pixel
+  /// uploads, with `0` meaning that rows are as long as the image
+  /// being uploaded.
+  fn set_pixel_unpack_row_length(&self, length: u32);
+  /// Set the number of pixels and rows to skip in client memory
+  /// before the first pixel being uploaded.
+  fn set_pixel_unpack_skip(&self, pixels: u32, rows: u32);
+  fn set_pixel_pack_alignment(&self, alignment: u32);
+
+  /// Enable or disable primitive restart for indices of type `T`.
+  ///
+  /// The maximum value representable by `T` acts as the restart index.
+  /// Note that on WebGL primitive restart is always enabled and can't
+  /// be disabled.
+  fn set_primitive_restart<T>(&self, enable: bool)
+  where
+    T: BuiltinType<Self>;
 
   fn clear(&self, mask: Self::ClearMask);
 
-  fn draw_arrays(&self, primitive: Self::Primitive, count: i32);
-  fn draw_arrays_instanced(&self, primitive: Self::Primitive, count: i32, instance_count: i32);
-  fn draw_elements<T>(&self, primitive: Self::Primitive, count: i32)
+  fn draw_arrays(&self, primitive: Self::Primitive, first: i32, count: i32);
+  fn draw_arrays_instanced(
+    &self,
+    primitive: Self::Primitive,
+    first: i32,
+    count: i32,
+    instance_count: i32,
+  );
+  fn draw_elements<T>(&self, primitive: Self::Primitive, count: i32, offset: i32)
+  where
+    T: BuiltinType<Self>;
+  fn draw_elements_instanced<T>(
+    &self,
+    primitive: Self::Primitive,
+    count: i32,
+    offset: i32,
+    instance_count: i32,
+  ) where
+    T: BuiltinType<Self>;
+  /// Draw `count` indexed elements, adding `base_vertex` to each index
+  /// before fetching the vertex it refers to.
+  ///
+  /// WebGL does not support base vertices natively. It emulates them
+  /// by reading back the indices and uploading adjusted ones into a
+  /// temporary buffer on every call, which is comparatively expensive.
+  /// If an adjusted index overflows, nothing is drawn and the emulation
+  /// raises an invalid value error, to be retrieved via
+  /// [`Gl::error`].
+  fn draw_elements_base_vertex<T>(
+    &self,
+    primitive: Self::Primitive,
+    count: i32,
+    offset: i32,
+    base_vertex: i32,
+  ) where
+    T: BuiltinType<Self>;
+  fn draw_range_elements<T>(
+    &self,
+    primitive: Self::Primitive,
+    start: u32,
+    end: u32,
+    count: i32,
+    offset: i32,
+  ) where
+    T: BuiltinType<Self>;
+  fn multi_draw_arrays(&self, primitive: Self::Primitive, firsts: &[i32], counts: &[i32]);
+  fn multi_draw_elements<T>(&self, primitive: Self::Primitive, counts: &[i32], offsets: &[i32])
   where
     T: BuiltinType<Self>;
 
   fn create_framebuffer(&self) -> Result<Self::Framebuffer, Self::Error>;
   fn delete_framebuffer(&self, fbo: &Self::Framebuffer);
   fn bind_framebuffer(&self, fbo: Option<&Self::Framebuffer>);
+  /// Retrieve the currently bound framebuffer, if any.
+  fn framebuffer_binding(&self) -> Option<Self::Framebuffer>;
 
   fn check_framebuffer_status(&self) -> Self::FramebufferStatus;
   fn set_framebuffer_depth_texture(
@@ -89,21 +160,78 @@ pub trait Gl: protected::Sealed {
     texture_target: Self::TextureTarget,
     texture: &Self::Texture,
   );
+  /// Attach a level of a texture as color attachment `index` of the
+  /// bound framebuffer.
+  fn set_framebuffer_color_texture(
+    &self,
+    index: u32,
+    texture_target: Self::TextureTarget,
+    texture: &Self::Texture,
+    level: u32,
+  );
+  /// Attach a single layer of a level of an array texture as color
+  /// attachment `index` of the bound framebuffer.
+  fn set_framebuffer_color_texture_layer(
+    &self,
+    index: u32,
+    texture: &Self::Texture,
+    level: u32,
+    layer: u32,
+  );
 
   fn unset_draw_buffer(&self);
   fn unset_read_buffer(&self);
 
+  /// Read a rectangle of pixels from the bound framebuffer.
+  ///
+  /// `pixels` has to be large enough to hold all pixels, taking into
+  /// account the pack alignment, or an error is reported.
+  fn read_pixels(
+    &self,
+    x: i32,
+    y: i32,
+    w: u32,
+    h: u32,
+    pixel_format: Self::TexturePixelFormat,
+    channel_type: Self::Type,
+    pixels: &mut [u8],
+  ) -> Result<(), Self::Error>;
+
   fn create_shader(&self, ty: Self::ShaderType) -> Option<Self::Shader>;
   fn delete_shader(&self, shader: &Self::Shader);
   fn set_shader_source(&self, shader: &Self::Shader, source: &str);
   fn compile_shader(&self, shader: &Self::Shader) -> Result<(), Vec<u8>>;
+  /// Retrieve the info log of a shader, which may contain warnings
+  /// even if compilation succeeded.
+  fn shader_info_log(&self, shader: &Self::Shader) -> Vec<u8>;
   fn attach_shader(&self, program: &Self::Program, shader: &Self::Shader);
   fn detach_shader(&self, program: &Self::Program, shader: &Self::Shader);
 
   fn create_program(&self) -> Option<Self::Program>;
   fn delete_program(&self, program: &Self::Program);
+  /// Bind the vertex attribute with the given name to the provided
+  /// index. Takes effect when the program is linked next.
+  fn bind_attrib_location(&self, program: &Self::Program, index: u32, name: &str);
+  /// Bind the fragment shader output with the given name to the
+  /// provided color number. Takes effect when the program is linked
+  /// next.
+  ///
+  /// WebGL does not support this operation and output locations have
+  /// to be specified using `layout(location = N)` qualifiers instead.
+  fn bind_frag_data_location(&self, program: &Self::Program, color: u32, name: &str);
+  /// Set the varyings to record in transform feedback mode. Takes
+  /// effect when the program is linked next.
+  fn set_transform_feedback_varyings(
+    &self,
+    program: &Self::Program,
+    varyings: &[&str],
+    mode: Self::TransformFeedbackMode,
+  );
   fn link_program(&self, program: &Self::Program) -> Result<(), Vec<u8>>;
   fn validate_program(&self, program: &Self::Program) -> Result<(), Vec<u8>>;
+  /// Retrieve the info log of a program, as produced by the last link
+  /// or validation operation.
+  fn program_info_log(&self, program: &Self::Program) -> Vec<u8>;
   fn use_program(&self, program: &Self::Program);
 
   fn attrib_location(&self, program: &Self::Program, attrib: &str) -> Option<u32>;
@@ -113,11 +241,48 @@ pub trait Gl: protected::Sealed {
     uniform: &str,
   ) -> Option<Self::UniformLocation>;
 
+  /// Retrieve the name, type, and array size of each active attribute
+  /// of a program.
+  fn active_attribs(&self, program: &Self::Program) -> Vec<(String, Self::GlslType, u32)>;
+  /// Retrieve the index, name, type, and array size of each active
+  /// uniform of a program.
+  fn active_uniforms(&self, program: &Self::Program) -> Vec<(u32, String, Self::GlslType, u32)>;
+  /// Retrieve the name, binding point, data size (in bytes), and the
+  /// indices of the member uniforms (as reported by
+  /// [`Gl::active_uniforms`]) of each active uniform block of a
+  /// program.
+  fn active_uniform_blocks(&self, program: &Self::Program) -> Vec<(String, u32, u32, Vec<u32>)>;
+
+  /// Retrieve the value of a uniform consisting of `N` `float`
+  /// components, e.g., a `vecN` or a flattened matrix.
+  ///
+  /// For this and the following getters, an error is reported if
+  /// `location` does not refer to a uniform of `program` with exactly
+  /// the requested number of components.
   fn uniform_fv<const N: usize>(
     &self,
     program: &Self::Program,
     location: &Self::UniformLocation,
-  ) -> [f32; N];
+  ) -> Result<[f32; N], Self::Error>;
+  /// Retrieve the value of an `int` (`N` = 1), `ivecN`, `bool`, or
+  /// `bvecN` uniform.
+  fn uniform_iv<const N: usize>(
+    &self,
+    program: &Self::Program,
+    location: &Self::UniformLocation,
+  ) -> Result<[i32; N], Self::Error>;
+  /// Retrieve the value of a `uint` (`N` = 1) or `uvecN` uniform.
+  fn uniform_uiv<const N: usize>(
+    &self,
+    program: &Self::Program,
+    location: &Self::UniformLocation,
+  ) -> Result<[u32; N], Self::Error>;
+  /// Retrieve the value of a `matCxR` uniform, in column-major order.
+  fn uniform_matrix_fv<const C: usize, const R: usize>(
+    &self,
+    program: &Self::Program,
+    location: &Self::UniformLocation,
+  ) -> Result<[[f32; R]; C], Self::Error>;
 
   fn set_uniform_1i(&self, location: &Self::UniformLocation, data: i32);
   fn set_uniform_1ui(&self, location: &Self::UniformLocation, data: u32);
@@ -128,6 +293,22 @@ pub trait Gl: protected::Sealed {
   fn set_uniform_4fv(&self, location: &Self::UniformLocation, data: &[[f32; 4]]);
   fn set_uniform_matrix(&self, location: &Self::UniformLocation, matrix: &[f32; 16]);
   fn set_uniform_matrices(&self, location: &Self::UniformLocation, matrices: &[[f32; 16]]);
+  /// Set a `float` (`N` = 1) or `vecN` uniform, or an array thereof.
+  ///
+  /// Component counts outside of `1..=4` are rejected at compile time,
+  /// here and for the other `set_uniform_*v` methods.
+  fn set_uniform_fv<const N: usize>(&self, location: &Self::UniformLocation, data: &[[f32; N]]);
+  /// Set an `int` (`N` = 1) or `ivecN` uniform, or an array thereof.
+  fn set_uniform_iv<const N: usize>(&self, location: &Self::UniformLocation, data: &[[i32; N]]);
+  /// Set a `uint` (`N` = 1) or `uvecN` uniform, or an array thereof.
+  fn set_uniform_uiv<const N: usize>(&self, location: &Self::UniformLocation, data: &[[u32; N]]);
+  /// Set a `matCxR` uniform, or an array thereof, from column-major
+  /// data. Dimensions outside of `2..=4` are rejected at compile time.
+  fn set_uniform_matrix_fv<const C: usize, const R: usize>(
+    &self,
+    location: &Self::UniformLocation,
+    data: &[[[f32; R]; C]],
+  );
 
   fn create_vertex_buffer(&self) -> Result<Self::VertexBufferObject, Self::Error>;
   fn delete_vertex_buffer(&self, vbo: &Self::VertexBufferObject);
@@ -193,6 +374,46 @@ pub trait Gl: protected::Sealed {
     pixels: Option<&[u8]>,
   ) -> Result<(), Self::Error>;
 
+  /// Check whether the context supports the given compressed
+  /// texture format.
+  ///
+  /// On WebGL, this enables the corresponding extension as a side
+  /// effect, which is required before the format can be used.
+  fn is_compressed_format_supported(&self, format: Self::CompressedTextureFormat) -> bool;
+
+  fn set_compressed_texture_image_2d(
+    &self,
+    target: Self::TextureTarget,
+    level: u32,
+    format: Self::CompressedTextureFormat,
+    w: u32,
+    h: u32,
+    data: &[u8],
+  ) -> Result<(), Self::Error>;
+
+  fn set_compressed_texture_image_3d(
+    &self,
+    target: Self::TextureTarget,
+    level: u32,
+    format: Self::CompressedTextureFormat,
+    w: u32,
+    h: u32,
+    count: u32,
+    data: &[u8],
+  ) -> Result<(), Self::Error>;
+
+  fn set_texture_sub_image_2d(
+    &self,
+    target: Self::TextureTarget,
+    pixel_format: Self::TexturePixelFormat,
+    channel_type: Self::Type,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    pixels: &[u8],
+  ) -> Result<(), Self::Error>;
+
   fn set_texture_sub_image_3d(
     &self,
     target: Self::TextureTarget,
@@ -215,7 +436,56 @@ pub trait Gl: protected::Sealed {
 
   fn set_texture_compare_mode(&self, target: Self::TextureTarget, mode: Self::TextureCompareMode);
   fn set_texture_compare_func(&self, target: Self::TextureTarget, func: Self::Func);
-  fn set_texture_wrap(&self, target: Self::TextureTarget, wrap: Self::TextureWrap);
+  fn set_texture_wrap(
+    &self,
+    target: Self::TextureTarget,
+    axis: Self::TextureWrapAxis,
+    wrap: Self::TextureWrap,
+  );
+  fn set_texture_lod_range(&self, target: Self::TextureTarget, min: f32, max: f32);
+
+  /// Retrieve the maximum supported degree of anisotropic filtering,
+  /// if anisotropic filtering is supported by the context.
+  ///
+  /// On WebGL, this enables the `EXT_texture_filter_anisotropic`
+  /// extension as a side effect.
+  fn max_texture_anisotropy(&self) -> Option<f32>;
+  /// Set the degree of anisotropic filtering of the texture bound to
+  /// `target`.
+  ///
+  /// Anisotropic filtering has to be supported, as reported by
+  /// [`Gl::max_texture_anisotropy`].
+  fn set_texture_anisotropy(&self, target: Self::TextureTarget, anisotropy: f32);
+
+  /// Set the index of the lowest mipmap level of the texture bound to
+  /// `target` that is accessed.
+  fn set_texture_base_level(&self, target: Self::TextureTarget, level: u32);
+
+  /// Set the index of the highest mipmap level of the texture bound
+  /// to `target` that is accessed.
+  fn set_texture_max_level(&self, target: Self::TextureTarget, level: u32);
 
   fn generate_mipmaps(&self, target: Self::TextureTarget);
+
+  fn create_sampler(&self) -> Result<Self::Sampler, Self::Error>;
+  fn delete_sampler(&self, sampler: &Self::Sampler);
+  /// Bind a sampler to the given texture unit, overriding the sampling
+  /// state of the texture bound to it.
+  fn bind_sampler(&self, unit: u32, sampler: Option<&Self::Sampler>);
+
+  fn set_sampler_filter(
+    &self,
+    sampler: &Self::Sampler,
+    ty: Self::TextureFilterType,
+    filter: Self::TextureFilter,
+  );
+  fn set_sampler_wrap(
+    &self,
+    sampler: &Self::Sampler,
+    axis: Self::TextureWrapAxis,
+    wrap: Self::TextureWrap,
+  );
+  fn set_sampler_lod_range(&self, sampler: &Self::Sampler, min: f32, max: f32);
+  fn set_sampler_compare_mode(&self, sampler: &Self::Sampler, mode: Self::TextureCompareMode);
+  fn set_sampler_compare_func(&self, sampler: &Self::Sampler, func: Self::Func);
 }
@@ -0,0 +1,146 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use super::GlslType;
+
+
+/// The GLSL name of each known [`GlslType`].
+const NAMES: [(GlslType, &str); 40] = [
+  (GlslType::Float, "float"),
+  (GlslType::Vec2, "vec2"),
+  (GlslType::Vec3, "vec3"),
+  (GlslType::Vec4, "vec4"),
+  (GlslType::Int, "int"),
+  (GlslType::IVec2, "ivec2"),
+  (GlslType::IVec3, "ivec3"),
+  (GlslType::IVec4, "ivec4"),
+  (GlslType::UInt, "uint"),
+  (GlslType::UVec2, "uvec2"),
+  (GlslType::UVec3, "uvec3"),
+  (GlslType::UVec4, "uvec4"),
+  (GlslType::Bool, "bool"),
+  (GlslType::BVec2, "bvec2"),
+  (GlslType::BVec3, "bvec3"),
+  (GlslType::BVec4, "bvec4"),
+  (GlslType::Mat2, "mat2"),
+  (GlslType::Mat3, "mat3"),
+  (GlslType::Mat4, "mat4"),
+  (GlslType::Mat2x3, "mat2x3"),
+  (GlslType::Mat2x4, "mat2x4"),
+  (GlslType::Mat3x2, "mat3x2"),
+  (GlslType::Mat3x4, "mat3x4"),
+  (GlslType::Mat4x2, "mat4x2"),
+  (GlslType::Mat4x3, "mat4x3"),
+  (GlslType::Sampler2D, "sampler2D"),
+  (GlslType::Sampler3D, "sampler3D"),
+  (GlslType::SamplerCube, "samplerCube"),
+  (GlslType::Sampler2DShadow, "sampler2DShadow"),
+  (GlslType::Sampler2DArray, "sampler2DArray"),
+  (GlslType::Sampler2DArrayShadow, "sampler2DArrayShadow"),
+  (GlslType::SamplerCubeShadow, "samplerCubeShadow"),
+  (GlslType::ISampler2D, "isampler2D"),
+  (GlslType::ISampler3D, "isampler3D"),
+  (GlslType::ISamplerCube, "isamplerCube"),
+  (GlslType::ISampler2DArray, "isampler2DArray"),
+  (GlslType::USampler2D, "usampler2D"),
+  (GlslType::USampler3D, "usampler3D"),
+  (GlslType::USamplerCube, "usamplerCube"),
+  (GlslType::USampler2DArray, "usampler2DArray"),
+];
+
+
+impl GlslType {
+  /// Check whether the type is one of the sampler types.
+  pub fn is_sampler(&self) -> bool {
+    matches!(
+      *self,
+      Self::Sampler2D
+        | Self::Sampler3D
+        | Self::SamplerCube
+        | Self::Sampler2DShadow
+        | Self::Sampler2DArray
+        | Self::Sampler2DArrayShadow
+        | Self::SamplerCubeShadow
+        | Self::ISampler2D
+        | Self::ISampler3D
+        | Self::ISamplerCube
+        | Self::ISampler2DArray
+        | Self::USampler2D
+        | Self::USampler3D
+        | Self::USamplerCube
+        | Self::USampler2DArray
+    )
+  }
+
+  /// Retrieve the number of scalar components making up a value of the
+  /// type, e.g., 3 for `vec3` and 16 for `mat4`.
+  ///
+  /// Samplers are represented by a single (texture unit) component.
+  pub(in crate::sys) fn component_count(&self) -> Option<usize> {
+    let count = match *self {
+      Self::Float | Self::Int | Self::UInt | Self::Bool => 1,
+      Self::Vec2 | Self::IVec2 | Self::UVec2 | Self::BVec2 => 2,
+      Self::Vec3 | Self::IVec3 | Self::UVec3 | Self::BVec3 => 3,
+      Self::Vec4 | Self::IVec4 | Self::UVec4 | Self::BVec4 | Self::Mat2 => 4,
+      Self::Mat2x3 | Self::Mat3x2 => 6,
+      Self::Mat2x4 | Self::Mat4x2 => 8,
+      Self::Mat3 => 9,
+      Self::Mat3x4 | Self::Mat4x3 => 12,
+      Self::Mat4 => 16,
+      type_ if type_.is_sampler() => 1,
+      _ => return None,
+    };
+    Some(count)
+  }
+
+  /// Look up a type by its GLSL name, e.g., `vec3`.
+  pub fn from_name(name: &str) -> Option<Self> {
+    NAMES
+      .iter()
+      .find(|(_type, name_)| *name_ == name)
+      .map(|(type_, _name)| *type_)
+  }
+
+  /// Retrieve the GLSL name of the type, e.g., `vec3`.
+  pub fn name(&self) -> Option<&'static str> {
+    NAMES
+      .iter()
+      .find(|(type_, _name)| type_ == self)
+      .map(|(_type, name)| *name)
+  }
+}
+
+impl Display for GlslType {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    match self.name() {
+      Some(name) => f.write_str(name),
+      None => write!(f, "{:#x}", self.0),
+    }
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that GLSL names and types map to each other.
+  #[test]
+  fn name_mapping() {
+    for (type_, name) in NAMES {
+      assert_eq!(GlslType::from_name(name), Some(type_));
+      assert_eq!(type_.to_string(), name);
+    }
+    assert_eq!(GlslType::from_name("vec5"), None);
+    assert!(GlslType::USampler2DArray.is_sampler());
+    assert!(!GlslType::Mat4.is_sampler());
+    assert_eq!(GlslType::BVec3.component_count(), Some(3));
+    assert_eq!(GlslType::Mat3x4.component_count(), Some(12));
+    assert_eq!(GlslType::SamplerCube.component_count(), Some(1));
+  }
+}
@@ -2,15 +2,19 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 mod gl;
+mod glsl;
 #[cfg(not(target_arch = "wasm32"))]
 mod opengl;
 #[cfg(target_arch = "wasm32")]
 mod webgl;
+mod wrap;
 
 use self::gl::protected::Sealed;
 
 pub use self::gl::BuiltinType;
 pub use self::gl::Gl;
+pub(crate) use self::wrap::wrap_axis_index;
+pub(crate) use self::wrap::WRAP_AXES;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub use self::opengl::*;
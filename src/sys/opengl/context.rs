@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
 use std::error::Error as StdError;
+use std::ffi::c_char;
+use std::ffi::c_void;
+use std::ffi::CStr;
 use std::ffi::CString;
 use std::fmt::Debug;
 use std::fmt::Display;
@@ -10,7 +13,6 @@ use std::fmt::Result as FmtResult;
 use std::mem::MaybeUninit;
 use std::ops::BitOr;
 use std::ops::BitOrAssign;
-use std::ptr::null_mut;
 use std::slice;
 
 use crate::sys::BuiltinType;
@@ -20,9 +22,17 @@ use crate::sys::Sealed;
 use super::gl;
 
 
+// Anisotropic filtering is only part of OpenGL 4.6 and is provided by
+// extensions before that, so it's missing from our bindings.
+const TEXTURE_MAX_ANISOTROPY: u32 = 0x84fe;
+const MAX_TEXTURE_MAX_ANISOTROPY: u32 = 0x84ff;
+
 #[derive(Debug)]
 pub struct Program(u32);
 
+#[derive(Debug)]
+pub struct Sampler(u32);
+
 #[derive(Debug)]
 pub struct Shader(u32);
 
@@ -38,7 +48,7 @@ pub struct VertexArrayObject(u32);
 #[derive(Debug)]
 pub struct VertexBufferObject(u32);
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct UniformLocation(i32);
 
 
@@ -72,9 +82,16 @@ impl StdError for Error {}
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug)]
 pub enum Type {
+  Byte = gl::BYTE,
   Float = gl::FLOAT,
+  HalfFloat = gl::HALF_FLOAT,
+  Int = gl::INT,
   Short = gl::SHORT,
   UnsignedByte = gl::UNSIGNED_BYTE,
+  UnsignedInt = gl::UNSIGNED_INT,
+  UnsignedInt10F11F11FRev = gl::UNSIGNED_INT_10F_11F_11F_REV,
+  UnsignedInt2101010Rev = gl::UNSIGNED_INT_2_10_10_10_REV,
+  UnsignedInt248 = gl::UNSIGNED_INT_24_8,
   UnsignedShort = gl::UNSIGNED_SHORT,
 }
 
@@ -88,7 +105,10 @@ pub enum Capability {
   DepthTest = gl::DEPTH_TEST,
   FramebufferSRGB = gl::FRAMEBUFFER_SRGB,
   Multisample = gl::MULTISAMPLE,
+  PrimitiveRestart = gl::PRIMITIVE_RESTART,
+  ProgramPointSize = gl::PROGRAM_POINT_SIZE,
   ScissorTest = gl::SCISSOR_TEST,
+  TextureCubeMapSeamless = gl::TEXTURE_CUBE_MAP_SEAMLESS,
 }
 
 
@@ -130,10 +150,17 @@ pub enum CullFace {
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Primitive {
+  Points = gl::POINTS,
   Lines = gl::LINES,
+  LineLoop = gl::LINE_LOOP,
+  LineStrip = gl::LINE_STRIP,
+  LinesAdjacency = gl::LINES_ADJACENCY,
+  LineStripAdjacency = gl::LINE_STRIP_ADJACENCY,
   Triangles = gl::TRIANGLES,
   TriangleFan = gl::TRIANGLE_FAN,
   TriangleStrip = gl::TRIANGLE_STRIP,
+  TrianglesAdjacency = gl::TRIANGLES_ADJACENCY,
+  TriangleStripAdjacency = gl::TRIANGLE_STRIP_ADJACENCY,
 }
 
 
@@ -180,11 +207,61 @@ impl Display for FramebufferStatus {
 }
 
 
+/// The type of a GLSL variable, e.g., of a uniform or an attribute.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GlslType(pub(in crate::sys) u32);
+
+#[expect(non_upper_case_globals)]
+impl GlslType {
+  pub const Float: Self = Self(gl::FLOAT);
+  pub const Vec2: Self = Self(gl::FLOAT_VEC2);
+  pub const Vec3: Self = Self(gl::FLOAT_VEC3);
+  pub const Vec4: Self = Self(gl::FLOAT_VEC4);
+  pub const Int: Self = Self(gl::INT);
+  pub const IVec2: Self = Self(gl::INT_VEC2);
+  pub const IVec3: Self = Self(gl::INT_VEC3);
+  pub const IVec4: Self = Self(gl::INT_VEC4);
+  pub const UInt: Self = Self(gl::UNSIGNED_INT);
+  pub const UVec2: Self = Self(gl::UNSIGNED_INT_VEC2);
+  pub const UVec3: Self = Self(gl::UNSIGNED_INT_VEC3);
+  pub const UVec4: Self = Self(gl::UNSIGNED_INT_VEC4);
+  pub const Bool: Self = Self(gl::BOOL);
+  pub const BVec2: Self = Self(gl::BOOL_VEC2);
+  pub const BVec3: Self = Self(gl::BOOL_VEC3);
+  pub const BVec4: Self = Self(gl::BOOL_VEC4);
+  pub const Mat2: Self = Self(gl::FLOAT_MAT2);
+  pub const Mat3: Self = Self(gl::FLOAT_MAT3);
+  pub const Mat4: Self = Self(gl::FLOAT_MAT4);
+  pub const Mat2x3: Self = Self(gl::FLOAT_MAT2x3);
+  pub const Mat2x4: Self = Self(gl::FLOAT_MAT2x4);
+  pub const Mat3x2: Self = Self(gl::FLOAT_MAT3x2);
+  pub const Mat3x4: Self = Self(gl::FLOAT_MAT3x4);
+  pub const Mat4x2: Self = Self(gl::FLOAT_MAT4x2);
+  pub const Mat4x3: Self = Self(gl::FLOAT_MAT4x3);
+  pub const Sampler2D: Self = Self(gl::SAMPLER_2D);
+  pub const Sampler3D: Self = Self(gl::SAMPLER_3D);
+  pub const SamplerCube: Self = Self(gl::SAMPLER_CUBE);
+  pub const Sampler2DShadow: Self = Self(gl::SAMPLER_2D_SHADOW);
+  pub const Sampler2DArray: Self = Self(gl::SAMPLER_2D_ARRAY);
+  pub const Sampler2DArrayShadow: Self = Self(gl::SAMPLER_2D_ARRAY_SHADOW);
+  pub const SamplerCubeShadow: Self = Self(gl::SAMPLER_CUBE_SHADOW);
+  pub const ISampler2D: Self = Self(gl::INT_SAMPLER_2D);
+  pub const ISampler3D: Self = Self(gl::INT_SAMPLER_3D);
+  pub const ISamplerCube: Self = Self(gl::INT_SAMPLER_CUBE);
+  pub const ISampler2DArray: Self = Self(gl::INT_SAMPLER_2D_ARRAY);
+  pub const USampler2D: Self = Self(gl::UNSIGNED_INT_SAMPLER_2D);
+  pub const USampler3D: Self = Self(gl::UNSIGNED_INT_SAMPLER_3D);
+  pub const USamplerCube: Self = Self(gl::UNSIGNED_INT_SAMPLER_CUBE);
+  pub const USampler2DArray: Self = Self(gl::UNSIGNED_INT_SAMPLER_2D_ARRAY);
+}
+
+
 #[repr(u32)]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug)]
 pub enum ShaderType {
   Fragment = gl::FRAGMENT_SHADER,
+  Geometry = gl::GEOMETRY_SHADER,
   Vertex = gl::VERTEX_SHADER,
 }
 
@@ -192,9 +269,16 @@ impl ShaderType {
   pub fn as_str(&self) -> &'static str {
     match self {
       Self::Fragment => "fragment",
+      Self::Geometry => "geometry",
       Self::Vertex => "vertex",
     }
   }
+
+  /// Check whether the shader type is supported by the backend.
+  #[inline]
+  pub fn is_supported(&self) -> bool {
+    true
+  }
 }
 
 
@@ -222,6 +306,29 @@ pub enum VertexBufferUsage {
 pub enum TextureTarget {
   Texture2D = gl::TEXTURE_2D,
   Texture2DArray = gl::TEXTURE_2D_ARRAY,
+  Texture3D = gl::TEXTURE_3D,
+  CubeMap = gl::TEXTURE_CUBE_MAP,
+  // The individual faces of a cube map, usable only for specifying
+  // images and attaching faces to framebuffers.
+  CubeMapPositiveX = gl::TEXTURE_CUBE_MAP_POSITIVE_X,
+  CubeMapNegativeX = gl::TEXTURE_CUBE_MAP_NEGATIVE_X,
+  CubeMapPositiveY = gl::TEXTURE_CUBE_MAP_POSITIVE_Y,
+  CubeMapNegativeY = gl::TEXTURE_CUBE_MAP_NEGATIVE_Y,
+  CubeMapPositiveZ = gl::TEXTURE_CUBE_MAP_POSITIVE_Z,
+  CubeMapNegativeZ = gl::TEXTURE_CUBE_MAP_NEGATIVE_Z,
+}
+
+impl TextureTarget {
+  /// The targets of the six faces of a cube map, in the order expected
+  /// by [`Builder::from_cube_faces`][crate::TextureBuilder::from_cube_faces].
+  pub const CUBE_MAP_FACES: [Self; 6] = [
+    Self::CubeMapPositiveX,
+    Self::CubeMapNegativeX,
+    Self::CubeMapPositiveY,
+    Self::CubeMapNegativeY,
+    Self::CubeMapPositiveZ,
+    Self::CubeMapNegativeZ,
+  ];
 }
 
 #[repr(u32)]
@@ -229,12 +336,136 @@ pub enum TextureTarget {
 #[derive(Clone, Copy, Debug)]
 pub enum TextureInternalFormat {
   Gray8 = gl::R8,
-  Depth = gl::DEPTH_COMPONENT,
   RG8 = gl::RG8,
   RGB8 = gl::RGB8,
   SRGB8 = gl::SRGB8,
   RGBA8 = gl::RGBA8,
   SRGBA8 = gl::SRGB8_ALPHA8,
+  R16F = gl::R16F,
+  RG16F = gl::RG16F,
+  RGB16F = gl::RGB16F,
+  RGBA16F = gl::RGBA16F,
+  R32F = gl::R32F,
+  RG32F = gl::RG32F,
+  RGB32F = gl::RGB32F,
+  RGBA32F = gl::RGBA32F,
+  R11G11B10F = gl::R11F_G11F_B10F,
+  RGB10A2 = gl::RGB10_A2,
+  R8I = gl::R8I,
+  R8UI = gl::R8UI,
+  R16I = gl::R16I,
+  R16UI = gl::R16UI,
+  R32I = gl::R32I,
+  R32UI = gl::R32UI,
+  RG8I = gl::RG8I,
+  RG8UI = gl::RG8UI,
+  RG16I = gl::RG16I,
+  RG16UI = gl::RG16UI,
+  RG32I = gl::RG32I,
+  RG32UI = gl::RG32UI,
+  RGB8I = gl::RGB8I,
+  RGB8UI = gl::RGB8UI,
+  RGB16I = gl::RGB16I,
+  RGB16UI = gl::RGB16UI,
+  RGB32I = gl::RGB32I,
+  RGB32UI = gl::RGB32UI,
+  RGBA8I = gl::RGBA8I,
+  RGBA8UI = gl::RGBA8UI,
+  RGBA16I = gl::RGBA16I,
+  RGBA16UI = gl::RGBA16UI,
+  RGBA32I = gl::RGBA32I,
+  RGBA32UI = gl::RGBA32UI,
+  Depth16 = gl::DEPTH_COMPONENT16,
+  Depth24 = gl::DEPTH_COMPONENT24,
+  Depth32F = gl::DEPTH_COMPONENT32F,
+  Depth24Stencil8 = gl::DEPTH24_STENCIL8,
+}
+
+#[expect(non_upper_case_globals)]
+impl TextureInternalFormat {
+  /// The depth format used for depth maps.
+  pub const Depth: Self = Self::Depth32F;
+}
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum CompressedTextureFormat {
+  // S3TC (BC1-BC3).
+  BC1RGB = 0x83F0,   // COMPRESSED_RGB_S3TC_DXT1_EXT
+  BC1SRGB = 0x8C4C,  // COMPRESSED_SRGB_S3TC_DXT1_EXT
+  BC1RGBA = 0x83F1,  // COMPRESSED_RGBA_S3TC_DXT1_EXT
+  BC1SRGBA = 0x8C4D, // COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT
+  BC2 = 0x83F2,      // COMPRESSED_RGBA_S3TC_DXT3_EXT
+  BC2SRGB = 0x8C4E,  // COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT
+  BC3 = 0x83F3,      // COMPRESSED_RGBA_S3TC_DXT5_EXT
+  BC3SRGB = 0x8C4F,  // COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT
+  // RGTC (BC4 & BC5).
+  BC4 = gl::COMPRESSED_RED_RGTC1,
+  BC4Signed = gl::COMPRESSED_SIGNED_RED_RGTC1,
+  BC5 = gl::COMPRESSED_RG_RGTC2,
+  BC5Signed = gl::COMPRESSED_SIGNED_RG_RGTC2,
+  // BPTC (BC6H & BC7).
+  BC6H = 0x8E8F,       // COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT
+  BC6HSigned = 0x8E8E, // COMPRESSED_RGB_BPTC_SIGNED_FLOAT
+  BC7 = 0x8E8C,        // COMPRESSED_RGBA_BPTC_UNORM
+  BC7SRGB = 0x8E8D,    // COMPRESSED_SRGB_ALPHA_BPTC_UNORM
+  // ETC2 & EAC.
+  ETC2RGB8 = 0x9274,      // COMPRESSED_RGB8_ETC2
+  ETC2SRGB8 = 0x9275,     // COMPRESSED_SRGB8_ETC2
+  ETC2RGB8A1 = 0x9276,    // COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2
+  ETC2SRGB8A1 = 0x9277,   // COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2
+  ETC2RGBA8 = 0x9278,     // COMPRESSED_RGBA8_ETC2_EAC
+  ETC2SRGBA8 = 0x9279,    // COMPRESSED_SRGB8_ALPHA8_ETC2_EAC
+  EACR11 = 0x9270,        // COMPRESSED_R11_EAC
+  EACR11Signed = 0x9271,  // COMPRESSED_SIGNED_R11_EAC
+  EACRG11 = 0x9272,       // COMPRESSED_RG11_EAC
+  EACRG11Signed = 0x9273, // COMPRESSED_SIGNED_RG11_EAC
+  // ASTC.
+  ASTC4x4 = 0x93B0,     // COMPRESSED_RGBA_ASTC_4x4_KHR
+  ASTC4x4SRGB = 0x93D0, // COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR
+  ASTC5x5 = 0x93B2,     // COMPRESSED_RGBA_ASTC_5x5_KHR
+  ASTC5x5SRGB = 0x93D2, // COMPRESSED_SRGB8_ALPHA8_ASTC_5x5_KHR
+  ASTC6x6 = 0x93B4,     // COMPRESSED_RGBA_ASTC_6x6_KHR
+  ASTC6x6SRGB = 0x93D4, // COMPRESSED_SRGB8_ALPHA8_ASTC_6x6_KHR
+  ASTC8x8 = 0x93B7,     // COMPRESSED_RGBA_ASTC_8x8_KHR
+  ASTC8x8SRGB = 0x93D7, // COMPRESSED_SRGB8_ALPHA8_ASTC_8x8_KHR
+}
+
+
+impl CompressedTextureFormat {
+  /// Retrieve the extensions required for using the format.
+  fn extensions(&self) -> &'static [&'static str] {
+    match self {
+      Self::BC1RGB | Self::BC1RGBA | Self::BC2 | Self::BC3 => &["GL_EXT_texture_compression_s3tc"],
+      Self::BC1SRGB | Self::BC1SRGBA | Self::BC2SRGB | Self::BC3SRGB => {
+        &["GL_EXT_texture_compression_s3tc", "GL_EXT_texture_sRGB"]
+      },
+      // RGTC is part of OpenGL 3.0.
+      Self::BC4 | Self::BC4Signed | Self::BC5 | Self::BC5Signed => &[],
+      Self::BC6H | Self::BC6HSigned | Self::BC7 | Self::BC7SRGB => {
+        &["GL_ARB_texture_compression_bptc"]
+      },
+      Self::ETC2RGB8
+      | Self::ETC2SRGB8
+      | Self::ETC2RGB8A1
+      | Self::ETC2SRGB8A1
+      | Self::ETC2RGBA8
+      | Self::ETC2SRGBA8
+      | Self::EACR11
+      | Self::EACR11Signed
+      | Self::EACRG11
+      | Self::EACRG11Signed => &["GL_ARB_ES3_compatibility"],
+      Self::ASTC4x4
+      | Self::ASTC4x4SRGB
+      | Self::ASTC5x5
+      | Self::ASTC5x5SRGB
+      | Self::ASTC6x6
+      | Self::ASTC6x6SRGB
+      | Self::ASTC8x8
+      | Self::ASTC8x8SRGB => &["GL_KHR_texture_compression_astc_ldr"],
+    }
+  }
 }
 
 #[repr(u32)]
@@ -246,12 +477,18 @@ pub enum TexturePixelFormat {
   RG = gl::RG,
   RGB = gl::RGB,
   RGBA = gl::RGBA,
+  GrayInteger = gl::RED_INTEGER,
+  RGInteger = gl::RG_INTEGER,
+  RGBInteger = gl::RGB_INTEGER,
+  RGBAInteger = gl::RGBA_INTEGER,
+  DepthStencil = gl::DEPTH_STENCIL,
 }
 
 #[repr(u32)]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug)]
 pub enum TextureCompareMode {
+  None = gl::NONE,
   RefToTexture = gl::COMPARE_REF_TO_TEXTURE,
 }
 
@@ -261,6 +498,38 @@ pub enum TextureCompareMode {
 pub enum TextureWrap {
   ClampToEdge = gl::CLAMP_TO_EDGE,
   Repeat = gl::REPEAT,
+  MirroredRepeat = gl::MIRRORED_REPEAT,
+  ClampToBorder = gl::CLAMP_TO_BORDER,
+}
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum TextureWrapAxis {
+  S = gl::TEXTURE_WRAP_S,
+  T = gl::TEXTURE_WRAP_T,
+  R = gl::TEXTURE_WRAP_R,
+}
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum TextureSwizzle {
+  Red = gl::RED,
+  Green = gl::GREEN,
+  Blue = gl::BLUE,
+  Alpha = gl::ALPHA,
+  Zero = gl::ZERO,
+  One = gl::ONE,
+}
+
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum TransformFeedbackMode {
+  Interleaved = gl::INTERLEAVED_ATTRIBS,
+  Separate = gl::SEPARATE_ATTRIBS,
 }
 
 #[repr(u32)]
@@ -284,12 +553,24 @@ pub enum TextureFilter {
 }
 
 
+impl BuiltinType<Context> for u8 {
+  fn as_type() -> Type {
+    Type::UnsignedByte
+  }
+}
+
 impl BuiltinType<Context> for u16 {
   fn as_type() -> Type {
     Type::UnsignedShort
   }
 }
 
+impl BuiltinType<Context> for u32 {
+  fn as_type() -> Type {
+    Type::UnsignedInt
+  }
+}
+
 
 /// The OpenGL context in use.
 ///
@@ -298,26 +579,278 @@ impl BuiltinType<Context> for u16 {
 pub struct Context {}
 
 impl Context {
+  /// Set the size of rasterized points, in pixels.
+  ///
+  /// This size is only used if [`Capability::ProgramPointSize`] is
+  /// disabled. Otherwise, the vertex shader is expected to write
+  /// `gl_PointSize`, which is also the only option on WebGL.
+  #[inline]
+  pub fn set_point_size(&self, size: f32) {
+    let () = unsafe { gl::PointSize(size) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  /// Set the color used for texels outside of the texture when
+  /// sampling with [`TextureWrap::ClampToBorder`].
+  ///
+  /// WebGL does not support border colors.
+  #[inline]
+  pub fn set_sampler_border_color(&self, sampler: &Sampler, color: [f32; 4]) {
+    let () = unsafe { gl::SamplerParameterfv(sampler.0, gl::TEXTURE_BORDER_COLOR, color.as_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  /// Set the bias added to the level of detail computed when sampling
+  /// the texture bound to `target`.
+  ///
+  /// WebGL does not support level of detail biases.
+  #[inline]
+  pub fn set_texture_lod_bias(&self, target: TextureTarget, bias: f32) {
+    let () = unsafe { gl::TexParameterf(target as _, gl::TEXTURE_LOD_BIAS, bias) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  /// Set the color used for texels outside of the texture bound to
+  /// `target` when sampling with [`TextureWrap::ClampToBorder`].
+  ///
+  /// WebGL does not support border colors.
+  #[inline]
+  pub fn set_texture_border_color(&self, target: TextureTarget, color: [f32; 4]) {
+    let () = unsafe { gl::TexParameterfv(target as _, gl::TEXTURE_BORDER_COLOR, color.as_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  /// Set the components that the red, green, blue, and alpha
+  /// components of the texture bound to `target` are read from, e.g.,
+  /// to sample a single channel texture as gray scale.
+  ///
+  /// WebGL does not support texture swizzling.
+  #[inline]
+  pub fn set_texture_swizzle(&self, target: TextureTarget, swizzle: [TextureSwizzle; 4]) {
+    let () = [
+      gl::TEXTURE_SWIZZLE_R,
+      gl::TEXTURE_SWIZZLE_G,
+      gl::TEXTURE_SWIZZLE_B,
+      gl::TEXTURE_SWIZZLE_A,
+    ]
+    .into_iter()
+    .zip(swizzle)
+    .for_each(|(param, swizzle)| {
+      let () = unsafe { gl::TexParameteri(target as _, param, swizzle as _) };
+    });
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  /// Read back a level of the texture bound to `target`, including all
+  /// layers of array textures.
+  ///
+  /// `pixels` has to be large enough to hold the entire level, taking
+  /// into account the pack alignment, or an error is reported. WebGL
+  /// lacks this functionality, requiring the texture to be read via a
+  /// framebuffer instead.
+  pub fn texture_image(
+    &self,
+    target: TextureTarget,
+    level: u32,
+    pixel_format: TexturePixelFormat,
+    channel_type: Type,
+    pixels: &mut [u8],
+  ) -> Result<(), Error> {
+    let level_param = |param| {
+      let mut value = MaybeUninit::uninit();
+      let () =
+        unsafe { gl::GetTexLevelParameteriv(target as _, level as _, param, value.as_mut_ptr()) };
+      let () = self.error()?;
+      Ok(unsafe { value.assume_init() } as u32)
+    };
+    let width = level_param(gl::TEXTURE_WIDTH)?;
+    let height = level_param(gl::TEXTURE_HEIGHT)?;
+    let depth = level_param(gl::TEXTURE_DEPTH)?;
+    let rows = height.checked_mul(depth);
+    let () = self.check_pack_size(width, rows, pixel_format, channel_type, pixels)?;
+
+    let () = unsafe {
+      gl::GetTexImage(
+        target as _,
+        level as _,
+        pixel_format as _,
+        channel_type as _,
+        pixels.as_mut_ptr().cast(),
+      )
+    };
+    let () = self.error()?;
+    Ok(())
+  }
+
+  /// Check that `pixels` can hold `rows` rows of `width` pixels of the
+  /// given format and type, packed according to the pack alignment.
+  fn check_pack_size(
+    &self,
+    width: u32,
+    rows: Option<u32>,
+    pixel_format: TexturePixelFormat,
+    channel_type: Type,
+    pixels: &[u8],
+  ) -> Result<(), Error> {
+    let components = match pixel_format {
+      TexturePixelFormat::Gray | TexturePixelFormat::Depth | TexturePixelFormat::GrayInteger => 1,
+      TexturePixelFormat::RG | TexturePixelFormat::RGInteger => 2,
+      TexturePixelFormat::RGB | TexturePixelFormat::RGBInteger => 3,
+      TexturePixelFormat::RGBA | TexturePixelFormat::RGBAInteger => 4,
+      // Depth-stencil data is only ever used with packed types.
+      TexturePixelFormat::DepthStencil => 1,
+    };
+    let pixel_size = match channel_type {
+      Type::Byte | Type::UnsignedByte => components,
+      Type::Short | Type::UnsignedShort | Type::HalfFloat => 2 * components,
+      Type::Int | Type::UnsignedInt | Type::Float => 4 * components,
+      Type::UnsignedInt10F11F11FRev | Type::UnsignedInt2101010Rev | Type::UnsignedInt248 => 4,
+    };
+
+    let mut alignment = MaybeUninit::uninit();
+    let () = unsafe { gl::GetIntegerv(gl::PACK_ALIGNMENT, alignment.as_mut_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+    let alignment = unsafe { alignment.assume_init() } as usize;
+
+    // The last row is not padded to the alignment.
+    let required = rows.and_then(|rows| {
+      let row = (width as usize).checked_mul(pixel_size)?;
+      let stride = row.checked_next_multiple_of(alignment.max(1))?;
+      match rows.checked_sub(1) {
+        Some(rows) => stride.checked_mul(rows as usize)?.checked_add(row),
+        None => Some(0),
+      }
+    });
+
+    match required {
+      Some(required) if required <= pixels.len() => Ok(()),
+      _ => Err(Error(gl::INVALID_OPERATION)),
+    }
+  }
+
+  /// Check whether the context supports the extension with the given
+  /// name.
+  fn has_extension(&self, name: &str) -> bool {
+    let mut count = MaybeUninit::uninit();
+    let () = unsafe { gl::GetIntegerv(gl::NUM_EXTENSIONS, count.as_mut_ptr()) };
+    let count = unsafe { count.assume_init() };
+    debug_assert_eq!(self.error(), Ok(()));
+
+    (0..count as u32).any(|index| {
+      let extension = unsafe { gl::GetStringi(gl::EXTENSIONS, index) };
+      !extension.is_null()
+        && unsafe { CStr::from_ptr(extension.cast()) }.to_bytes() == name.as_bytes()
+    })
+  }
+
   fn check_program(&self, program: &Program, status_attrib: u32) -> Result<(), Vec<u8>> {
     let mut status = MaybeUninit::uninit();
     let () = unsafe { gl::GetProgramiv(program.0, status_attrib, status.as_mut_ptr()) };
     let status = unsafe { status.assume_init() };
     if status == i32::from(false) {
-      let mut info_len = MaybeUninit::uninit();
-      let () = unsafe { gl::GetProgramiv(program.0, gl::INFO_LOG_LENGTH, info_len.as_mut_ptr()) };
-      let info_len = unsafe { info_len.assume_init() };
-
-      let mut log = Vec::<u8>::with_capacity(info_len as _);
-      let () = unsafe {
-        gl::GetProgramInfoLog(program.0, info_len, null_mut(), log.as_mut_ptr().cast());
-      };
-      let () = unsafe { log.set_len(info_len as _) };
-      Err(log)
+      Err(self.program_info_log(program))
     } else {
       Ok(())
     }
   }
 
+  #[inline]
+  fn program_param(&self, program: &Program, param: u32) -> i32 {
+    let mut value = MaybeUninit::uninit();
+    let () = unsafe { gl::GetProgramiv(program.0, param, value.as_mut_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+    unsafe { value.assume_init() }
+  }
+
+  #[inline]
+  fn uniform_block_param(&self, program: &Program, index: u32, param: u32) -> i32 {
+    let mut value = MaybeUninit::uninit();
+    let () = unsafe { gl::GetActiveUniformBlockiv(program.0, index, param, value.as_mut_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+    unsafe { value.assume_init() }
+  }
+
+  /// Check that `location` refers to an active uniform of `program`
+  /// (or to an element thereof) with exactly `count` components.
+  ///
+  /// `GetUniform*` write all components of the uniform, irrespective
+  /// of the buffer they are provided, so this check has to happen
+  /// before reading back any value.
+  fn check_uniform_components(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+    count: usize,
+  ) -> Result<(), Error> {
+    if location.0 >= 0 {
+      for (_index, name, type_, size) in self.active_uniforms(program) {
+        let base = name.strip_suffix("[0]").unwrap_or(&name);
+        let found = if base.len() == name.len() {
+          self
+            .uniform_location(program, &name)
+            .map(|location| location.0)
+            == Some(location.0)
+        } else {
+          (0..size).any(|idx| {
+            let name = format!("{base}[{idx}]");
+            self
+              .uniform_location(program, &name)
+              .map(|location| location.0)
+              == Some(location.0)
+          })
+        };
+
+        if found {
+          if type_.component_count() == Some(count) {
+            return Ok(())
+          }
+          break
+        }
+      }
+    }
+    Err(Error(gl::INVALID_OPERATION))
+  }
+
+  /// Retrieve information about all active attributes or uniforms,
+  /// using the provided "getter" (`GetActiveAttrib` or
+  /// `GetActiveUniform`).
+  fn active_variables(
+    &self,
+    program: &Program,
+    count_param: u32,
+    max_len_param: u32,
+    get: unsafe extern "system" fn(u32, u32, i32, *mut i32, *mut i32, *mut u32, *mut c_char),
+  ) -> Vec<(u32, String, GlslType, u32)> {
+    let count = self.program_param(program, count_param);
+    let max_len = self.program_param(program, max_len_param);
+
+    (0..count as u32)
+      .map(|index| {
+        let mut name = vec![0u8; max_len as usize];
+        let mut len = 0;
+        let mut size = 0;
+        let mut ty = 0;
+        let () = unsafe {
+          get(
+            program.0,
+            index,
+            max_len,
+            &mut len,
+            &mut size,
+            &mut ty,
+            name.as_mut_ptr().cast(),
+          )
+        };
+        debug_assert_eq!(self.error(), Ok(()));
+
+        let () = name.truncate(len as usize);
+        // TODO: Should use `String::from_utf8_lossy_owned` once stable.
+        let name = String::from_utf8_lossy(&name).into_owned();
+        (index, name, GlslType(ty), size as u32)
+      })
+      .collect()
+  }
+
   #[inline]
   fn set_uniform_matrices_impl(&self, location: &UniformLocation, matrices: &[[f32; 16]]) {
     let transpose = 0;
@@ -340,6 +873,7 @@ impl Gl for Context {
 
   type Capability = Capability;
   type ClearMask = ClearMask;
+  type CompressedTextureFormat = CompressedTextureFormat;
   type CullFace = CullFace;
   type Factor = Factor;
   type FramebufferStatus = FramebufferStatus;
@@ -354,17 +888,21 @@ impl Gl for Context {
   type TexturePixelFormat = TexturePixelFormat;
   type TextureTarget = TextureTarget;
   type TextureWrap = TextureWrap;
+  type TextureWrapAxis = TextureWrapAxis;
+  type TransformFeedbackMode = TransformFeedbackMode;
   type Type = Type;
   type VertexBufferTarget = VertexBufferTarget;
   type VertexBufferUsage = VertexBufferUsage;
 
   type Framebuffer = Framebuffer;
   type Program = Program;
+  type Sampler = Sampler;
   type Shader = Shader;
   type Texture = Texture;
   type VertexArrayObject = VertexArrayObject;
   type VertexBufferObject = VertexBufferObject;
 
+  type GlslType = GlslType;
   type UniformLocation = UniformLocation;
 
   #[inline]
@@ -431,6 +969,39 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn set_pixel_unpack_row_length(&self, length: u32) {
+    let () = unsafe { gl::PixelStorei(gl::UNPACK_ROW_LENGTH, length as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_pixel_unpack_skip(&self, pixels: u32, rows: u32) {
+    let () = unsafe { gl::PixelStorei(gl::UNPACK_SKIP_PIXELS, pixels as _) };
+    let () = unsafe { gl::PixelStorei(gl::UNPACK_SKIP_ROWS, rows as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_pixel_pack_alignment(&self, alignment: u32) {
+    let () = unsafe { gl::PixelStorei(gl::PACK_ALIGNMENT, alignment as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_primitive_restart<T>(&self, enable: bool)
+  where
+    T: BuiltinType<Self>,
+  {
+    if enable {
+      let index = u32::MAX >> (32 - 8 * size_of::<T>());
+      let () = unsafe { gl::Enable(gl::PRIMITIVE_RESTART) };
+      let () = unsafe { gl::PrimitiveRestartIndex(index) };
+    } else {
+      let () = unsafe { gl::Disable(gl::PRIMITIVE_RESTART) };
+    }
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   #[inline]
   fn clear(&self, mask: ClearMask) {
     let () = unsafe { gl::Clear(mask.0) };
@@ -438,23 +1009,135 @@ impl Gl for Context {
   }
 
   #[inline]
-  fn draw_arrays(&self, primitive: Primitive, count: i32) {
-    let () = unsafe { gl::DrawArrays(primitive as _, 0, count) };
+  fn draw_arrays(&self, primitive: Primitive, first: i32, count: i32) {
+    let () = unsafe { gl::DrawArrays(primitive as _, first, count) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn draw_arrays_instanced(
+    &self,
+    primitive: Primitive,
+    first: i32,
+    count: i32,
+    instance_count: i32,
+  ) {
+    let () = unsafe { gl::DrawArraysInstanced(primitive as _, first, count, instance_count) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn draw_elements<T>(&self, primitive: Primitive, count: i32, offset: i32)
+  where
+    T: BuiltinType<Self>,
+  {
+    let () =
+      unsafe { gl::DrawElements(primitive as _, count, T::as_type() as _, offset as *const _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn draw_elements_instanced<T>(
+    &self,
+    primitive: Primitive,
+    count: i32,
+    offset: i32,
+    instance_count: i32,
+  ) where
+    T: BuiltinType<Self>,
+  {
+    let () = unsafe {
+      gl::DrawElementsInstanced(
+        primitive as _,
+        count,
+        T::as_type() as _,
+        offset as *const _,
+        instance_count,
+      )
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn draw_elements_base_vertex<T>(
+    &self,
+    primitive: Primitive,
+    count: i32,
+    offset: i32,
+    base_vertex: i32,
+  ) where
+    T: BuiltinType<Self>,
+  {
+    let () = unsafe {
+      gl::DrawElementsBaseVertex(
+        primitive as _,
+        count,
+        T::as_type() as _,
+        offset as *const _,
+        base_vertex,
+      )
+    };
     debug_assert_eq!(self.error(), Ok(()));
   }
 
   #[inline]
-  fn draw_arrays_instanced(&self, primitive: Primitive, count: i32, instance_count: i32) {
-    let () = unsafe { gl::DrawArraysInstanced(primitive as _, 0, count, instance_count) };
+  fn draw_range_elements<T>(
+    &self,
+    primitive: Primitive,
+    start: u32,
+    end: u32,
+    count: i32,
+    offset: i32,
+  ) where
+    T: BuiltinType<Self>,
+  {
+    let () = unsafe {
+      gl::DrawRangeElements(
+        primitive as _,
+        start,
+        end,
+        count,
+        T::as_type() as _,
+        offset as *const _,
+      )
+    };
     debug_assert_eq!(self.error(), Ok(()));
   }
 
   #[inline]
-  fn draw_elements<T>(&self, primitive: Primitive, count: i32)
+  fn multi_draw_arrays(&self, primitive: Primitive, firsts: &[i32], counts: &[i32]) {
+    debug_assert_eq!(firsts.len(), counts.len());
+
+    let () = unsafe {
+      gl::MultiDrawArrays(
+        primitive as _,
+        firsts.as_ptr(),
+        counts.as_ptr(),
+        counts.len() as _,
+      )
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn multi_draw_elements<T>(&self, primitive: Primitive, counts: &[i32], offsets: &[i32])
   where
     T: BuiltinType<Self>,
   {
-    let () = unsafe { gl::DrawElements(primitive as _, count, T::as_type() as _, null_mut()) };
+    debug_assert_eq!(offsets.len(), counts.len());
+
+    let offsets = offsets
+      .iter()
+      .map(|offset| *offset as *const c_void)
+      .collect::<Vec<_>>();
+    let () = unsafe {
+      gl::MultiDrawElements(
+        primitive as _,
+        counts.as_ptr(),
+        T::as_type() as _,
+        offsets.as_ptr(),
+        counts.len() as _,
+      )
+    };
     debug_assert_eq!(self.error(), Ok(()));
   }
 
@@ -479,6 +1162,16 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn framebuffer_binding(&self) -> Option<Framebuffer> {
+    let mut fbo = MaybeUninit::uninit();
+    let () = unsafe { gl::GetIntegerv(gl::FRAMEBUFFER_BINDING, fbo.as_mut_ptr()) };
+    let fbo = unsafe { fbo.assume_init() };
+    debug_assert_eq!(self.error(), Ok(()));
+
+    (fbo != 0).then_some(Framebuffer(fbo as u32))
+  }
+
   #[inline]
   fn set_framebuffer_depth_texture(&self, texture_target: TextureTarget, texture: &Texture) {
     let mipmap_level = 0;
@@ -494,6 +1187,46 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn set_framebuffer_color_texture(
+    &self,
+    index: u32,
+    texture_target: TextureTarget,
+    texture: &Texture,
+    level: u32,
+  ) {
+    let () = unsafe {
+      gl::FramebufferTexture2D(
+        gl::FRAMEBUFFER,
+        gl::COLOR_ATTACHMENT0 + index,
+        texture_target as _,
+        texture.0,
+        level as _,
+      )
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_framebuffer_color_texture_layer(
+    &self,
+    index: u32,
+    texture: &Texture,
+    level: u32,
+    layer: u32,
+  ) {
+    let () = unsafe {
+      gl::FramebufferTextureLayer(
+        gl::FRAMEBUFFER,
+        gl::COLOR_ATTACHMENT0 + index,
+        texture.0,
+        level as _,
+        layer as _,
+      )
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   #[inline]
   fn unset_draw_buffer(&self) {
     let () = unsafe { gl::DrawBuffer(gl::NONE) };
@@ -506,6 +1239,34 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn read_pixels(
+    &self,
+    x: i32,
+    y: i32,
+    w: u32,
+    h: u32,
+    pixel_format: TexturePixelFormat,
+    channel_type: Type,
+    pixels: &mut [u8],
+  ) -> Result<(), Error> {
+    let () = self.check_pack_size(w, Some(h), pixel_format, channel_type, pixels)?;
+
+    let () = unsafe {
+      gl::ReadPixels(
+        x,
+        y,
+        w as _,
+        h as _,
+        pixel_format as _,
+        channel_type as _,
+        pixels.as_mut_ptr().cast(),
+      )
+    };
+    let () = self.error()?;
+    Ok(())
+  }
+
   #[inline]
   fn check_framebuffer_status(&self) -> FramebufferStatus {
     let status = unsafe { gl::CheckFramebufferStatus(gl::FRAMEBUFFER) };
@@ -550,20 +1311,24 @@ impl Gl for Context {
     let () = unsafe { gl::GetShaderiv(shader.0, gl::COMPILE_STATUS, status.as_mut_ptr()) };
     let status = unsafe { status.assume_init() };
     if status == i32::from(false) {
-      let mut info_len = MaybeUninit::uninit();
-      let () = unsafe { gl::GetShaderiv(shader.0, gl::INFO_LOG_LENGTH, info_len.as_mut_ptr()) };
-      let info_len = unsafe { info_len.assume_init() };
-
-      let mut log = Vec::<u8>::with_capacity(info_len as _);
-      let () =
-        unsafe { gl::GetShaderInfoLog(shader.0, info_len, null_mut(), log.as_mut_ptr().cast()) };
-      let () = unsafe { log.set_len(info_len as _) };
-      Err(log)
+      Err(self.shader_info_log(shader))
     } else {
       Ok(())
     }
   }
 
+  fn shader_info_log(&self, shader: &Shader) -> Vec<u8> {
+    let mut info_len = MaybeUninit::uninit();
+    let () = unsafe { gl::GetShaderiv(shader.0, gl::INFO_LOG_LENGTH, info_len.as_mut_ptr()) };
+    let info_len = unsafe { info_len.assume_init() };
+
+    let mut log = Vec::<u8>::with_capacity(info_len as _);
+    let mut len = 0;
+    let () = unsafe { gl::GetShaderInfoLog(shader.0, info_len, &mut len, log.as_mut_ptr().cast()) };
+    let () = unsafe { log.set_len(len as _) };
+    log
+  }
+
   #[inline]
   fn attach_shader(&self, program: &Program, shader: &Shader) {
     let () = unsafe { gl::AttachShader(program.0, shader.0) };
@@ -592,6 +1357,46 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn bind_attrib_location(&self, program: &Program, index: u32, name: &str) {
+    // SANITY: Callers have to ensure there are no NUL bytes inside
+    //         `name`.
+    let cname = CString::new(name).unwrap();
+    let () = unsafe { gl::BindAttribLocation(program.0, index, cname.as_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn bind_frag_data_location(&self, program: &Program, color: u32, name: &str) {
+    // SANITY: Callers have to ensure there are no NUL bytes inside
+    //         `name`.
+    let cname = CString::new(name).unwrap();
+    let () = unsafe { gl::BindFragDataLocation(program.0, color, cname.as_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_transform_feedback_varyings(
+    &self,
+    program: &Program,
+    varyings: &[&str],
+    mode: TransformFeedbackMode,
+  ) {
+    // SANITY: Callers have to ensure there are no NUL bytes inside
+    //         `varyings`.
+    let cvaryings = varyings
+      .iter()
+      .map(|varying| CString::new(*varying).unwrap())
+      .collect::<Vec<_>>();
+    let ptrs = cvaryings
+      .iter()
+      .map(|varying| varying.as_ptr())
+      .collect::<Vec<_>>();
+    let () = unsafe {
+      gl::TransformFeedbackVaryings(program.0, ptrs.len() as _, ptrs.as_ptr(), mode as _)
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   fn link_program(&self, program: &Program) -> Result<(), Vec<u8>> {
     let () = unsafe { gl::LinkProgram(program.0) };
     let () = self.check_program(program, gl::LINK_STATUS)?;
@@ -604,6 +1409,19 @@ impl Gl for Context {
     Ok(())
   }
 
+  fn program_info_log(&self, program: &Program) -> Vec<u8> {
+    let mut info_len = MaybeUninit::uninit();
+    let () = unsafe { gl::GetProgramiv(program.0, gl::INFO_LOG_LENGTH, info_len.as_mut_ptr()) };
+    let info_len = unsafe { info_len.assume_init() };
+
+    let mut log = Vec::<u8>::with_capacity(info_len as _);
+    let mut len = 0;
+    let () =
+      unsafe { gl::GetProgramInfoLog(program.0, info_len, &mut len, log.as_mut_ptr().cast()) };
+    let () = unsafe { log.set_len(len as _) };
+    log
+  }
+
   #[inline]
   fn use_program(&self, program: &Program) {
     let () = unsafe { gl::UseProgram(program.0) };
@@ -636,16 +1454,140 @@ impl Gl for Context {
     }
   }
 
-  #[inline]
-  fn uniform_fv<const N: usize>(&self, program: &Program, location: &UniformLocation) -> [f32; N] {
+  fn active_attribs(&self, program: &Program) -> Vec<(String, GlslType, u32)> {
+    self
+      .active_variables(
+        program,
+        gl::ACTIVE_ATTRIBUTES,
+        gl::ACTIVE_ATTRIBUTE_MAX_LENGTH,
+        gl::GetActiveAttrib,
+      )
+      .into_iter()
+      .map(|(_index, name, type_, size)| (name, type_, size))
+      .collect()
+  }
+
+  fn active_uniforms(&self, program: &Program) -> Vec<(u32, String, GlslType, u32)> {
+    self.active_variables(
+      program,
+      gl::ACTIVE_UNIFORMS,
+      gl::ACTIVE_UNIFORM_MAX_LENGTH,
+      gl::GetActiveUniform,
+    )
+  }
+
+  fn active_uniform_blocks(&self, program: &Program) -> Vec<(String, u32, u32, Vec<u32>)> {
+    let count = self.program_param(program, gl::ACTIVE_UNIFORM_BLOCKS);
+
+    (0..count as u32)
+      .map(|index| {
+        let name_len = self.uniform_block_param(program, index, gl::UNIFORM_BLOCK_NAME_LENGTH);
+        let mut name = vec![0u8; name_len as usize];
+        let mut len = 0;
+        let () = unsafe {
+          gl::GetActiveUniformBlockName(
+            program.0,
+            index,
+            name_len,
+            &mut len,
+            name.as_mut_ptr().cast(),
+          )
+        };
+        debug_assert_eq!(self.error(), Ok(()));
+        let () = name.truncate(len as usize);
+        // TODO: Should use `String::from_utf8_lossy_owned` once stable.
+        let name = String::from_utf8_lossy(&name).into_owned();
+
+        let binding = self.uniform_block_param(program, index, gl::UNIFORM_BLOCK_BINDING);
+        let size = self.uniform_block_param(program, index, gl::UNIFORM_BLOCK_DATA_SIZE);
+        let uniform_count =
+          self.uniform_block_param(program, index, gl::UNIFORM_BLOCK_ACTIVE_UNIFORMS);
+        let mut uniforms = vec![0i32; uniform_count as usize];
+        if uniform_count > 0 {
+          let () = unsafe {
+            gl::GetActiveUniformBlockiv(
+              program.0,
+              index,
+              gl::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES,
+              uniforms.as_mut_ptr(),
+            )
+          };
+          debug_assert_eq!(self.error(), Ok(()));
+        }
+        let uniforms = uniforms.into_iter().map(|index| index as u32).collect();
+
+        (name, binding as u32, size as u32, uniforms)
+      })
+      .collect()
+  }
+
+  #[inline]
+  fn uniform_fv<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[f32; N], Error> {
+    let () = self.check_uniform_components(program, location, N)?;
+
     let mut data = MaybeUninit::<[f32; N]>::uninit();
     let () = unsafe { gl::GetUniformfv(program.0, location.0, data.as_mut_ptr().cast()) };
-    // SANITY: We know that program and location are valid, so the above
-    //         call should be infallible.
-    assert_eq!(self.error(), Ok(()));
+    let () = self.error()?;
+
+    // SAFETY: The uniform consists of exactly as many components as
+    //         `data` has room for, all of which got written by
+    //         `GetUniformfv` on success.
+    Ok(unsafe { data.assume_init() })
+  }
+
+  fn uniform_iv<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[i32; N], Error> {
+    let () = self.check_uniform_components(program, location, N)?;
+
+    let mut data = MaybeUninit::<[i32; N]>::uninit();
+    let () = unsafe { gl::GetUniformiv(program.0, location.0, data.as_mut_ptr().cast()) };
+    let () = self.error()?;
+
+    // SAFETY: The uniform consists of exactly as many components as
+    //         `data` has room for, all of which got written by
+    //         `GetUniformiv` on success.
+    Ok(unsafe { data.assume_init() })
+  }
+
+  fn uniform_uiv<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[u32; N], Error> {
+    let () = self.check_uniform_components(program, location, N)?;
+
+    let mut data = MaybeUninit::<[u32; N]>::uninit();
+    let () = unsafe { gl::GetUniformuiv(program.0, location.0, data.as_mut_ptr().cast()) };
+    let () = self.error()?;
+
+    // SAFETY: The uniform consists of exactly as many components as
+    //         `data` has room for, all of which got written by
+    //         `GetUniformuiv` on success.
+    Ok(unsafe { data.assume_init() })
+  }
+
+  fn uniform_matrix_fv<const C: usize, const R: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[[f32; R]; C], Error> {
+    let () = self.check_uniform_components(program, location, C * R)?;
+
+    let mut data = MaybeUninit::<[[f32; R]; C]>::uninit();
+    let () = unsafe { gl::GetUniformfv(program.0, location.0, data.as_mut_ptr().cast()) };
+    let () = self.error()?;
 
-    // SAFETY: On success, `data` will be written by `GetUniformfv`.
-    unsafe { data.assume_init() }
+    // SAFETY: The uniform consists of exactly as many components as
+    //         `data` has room for, all of which got written by
+    //         `GetUniformfv` on success.
+    Ok(unsafe { data.assume_init() })
   }
 
   #[inline]
@@ -700,6 +1642,88 @@ impl Gl for Context {
     self.set_uniform_matrices_impl(location, slice::from_ref(matrix))
   }
 
+  fn set_uniform_fv<const N: usize>(&self, location: &UniformLocation, data: &[[f32; N]]) {
+    const {
+      assert!(
+        N >= 1 && N <= 4,
+        "uniform component count has to be in 1..=4"
+      )
+    };
+    let f = match N {
+      1 => gl::Uniform1fv,
+      2 => gl::Uniform2fv,
+      3 => gl::Uniform3fv,
+      4 => gl::Uniform4fv,
+      _ => unreachable!(),
+    };
+    let () = unsafe { f(location.0, data.len() as _, data.as_ptr().cast()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_uniform_iv<const N: usize>(&self, location: &UniformLocation, data: &[[i32; N]]) {
+    const {
+      assert!(
+        N >= 1 && N <= 4,
+        "uniform component count has to be in 1..=4"
+      )
+    };
+    let f = match N {
+      1 => gl::Uniform1iv,
+      2 => gl::Uniform2iv,
+      3 => gl::Uniform3iv,
+      4 => gl::Uniform4iv,
+      _ => unreachable!(),
+    };
+    let () = unsafe { f(location.0, data.len() as _, data.as_ptr().cast()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_uniform_uiv<const N: usize>(&self, location: &UniformLocation, data: &[[u32; N]]) {
+    const {
+      assert!(
+        N >= 1 && N <= 4,
+        "uniform component count has to be in 1..=4"
+      )
+    };
+    let f = match N {
+      1 => gl::Uniform1uiv,
+      2 => gl::Uniform2uiv,
+      3 => gl::Uniform3uiv,
+      4 => gl::Uniform4uiv,
+      _ => unreachable!(),
+    };
+    let () = unsafe { f(location.0, data.len() as _, data.as_ptr().cast()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_uniform_matrix_fv<const C: usize, const R: usize>(
+    &self,
+    location: &UniformLocation,
+    data: &[[[f32; R]; C]],
+  ) {
+    const {
+      assert!(
+        C >= 2 && C <= 4 && R >= 2 && R <= 4,
+        "uniform matrix dimensions have to be in 2..=4"
+      )
+    };
+    let f = match (C, R) {
+      (2, 2) => gl::UniformMatrix2fv,
+      (2, 3) => gl::UniformMatrix2x3fv,
+      (2, 4) => gl::UniformMatrix2x4fv,
+      (3, 2) => gl::UniformMatrix3x2fv,
+      (3, 3) => gl::UniformMatrix3fv,
+      (3, 4) => gl::UniformMatrix3x4fv,
+      (4, 2) => gl::UniformMatrix4x2fv,
+      (4, 3) => gl::UniformMatrix4x3fv,
+      (4, 4) => gl::UniformMatrix4fv,
+      _ => unreachable!(),
+    };
+    let transpose = 0;
+    let () = unsafe { f(location.0, data.len() as _, transpose, data.as_ptr().cast()) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   #[inline]
   fn create_vertex_buffer(&self) -> Result<VertexBufferObject, Error> {
     let mut vbo = 0;
@@ -896,6 +1920,102 @@ impl Gl for Context {
     Ok(())
   }
 
+  fn is_compressed_format_supported(&self, format: CompressedTextureFormat) -> bool {
+    format
+      .extensions()
+      .iter()
+      .all(|extension| self.has_extension(extension))
+  }
+
+  #[inline]
+  fn set_compressed_texture_image_2d(
+    &self,
+    target: TextureTarget,
+    level: u32,
+    format: CompressedTextureFormat,
+    w: u32,
+    h: u32,
+    data: &[u8],
+  ) -> Result<(), Error> {
+    let border = 0;
+
+    let () = unsafe {
+      gl::CompressedTexImage2D(
+        target as _,
+        level as _,
+        format as _,
+        w as _,
+        h as _,
+        border,
+        data.len() as _,
+        data.as_ptr().cast(),
+      )
+    };
+    let () = self.error()?;
+    Ok(())
+  }
+
+  #[inline]
+  fn set_compressed_texture_image_3d(
+    &self,
+    target: TextureTarget,
+    level: u32,
+    format: CompressedTextureFormat,
+    w: u32,
+    h: u32,
+    count: u32,
+    data: &[u8],
+  ) -> Result<(), Error> {
+    let border = 0;
+
+    let () = unsafe {
+      gl::CompressedTexImage3D(
+        target as _,
+        level as _,
+        format as _,
+        w as _,
+        h as _,
+        count as _,
+        border,
+        data.len() as _,
+        data.as_ptr().cast(),
+      )
+    };
+    let () = self.error()?;
+    Ok(())
+  }
+
+  #[inline]
+  fn set_texture_sub_image_2d(
+    &self,
+    target: TextureTarget,
+    pixel_format: TexturePixelFormat,
+    channel_type: Type,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    pixels: &[u8],
+  ) -> Result<(), Error> {
+    let level = 0;
+
+    let () = unsafe {
+      gl::TexSubImage2D(
+        target as _,
+        level,
+        x as _,
+        y as _,
+        w as _,
+        h as _,
+        pixel_format as _,
+        channel_type as _,
+        pixels.as_ptr().cast(),
+      )
+    };
+    let () = self.error()?;
+    Ok(())
+  }
+
   #[inline]
   fn set_texture_sub_image_3d(
     &self,
@@ -955,9 +2075,46 @@ impl Gl for Context {
   }
 
   #[inline]
-  fn set_texture_wrap(&self, target: TextureTarget, wrap: TextureWrap) {
-    let () = unsafe { gl::TexParameteri(target as _, gl::TEXTURE_WRAP_S, wrap as _) };
-    let () = unsafe { gl::TexParameteri(target as _, gl::TEXTURE_WRAP_T, wrap as _) };
+  fn set_texture_wrap(&self, target: TextureTarget, axis: TextureWrapAxis, wrap: TextureWrap) {
+    let () = unsafe { gl::TexParameteri(target as _, axis as _, wrap as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_texture_lod_range(&self, target: TextureTarget, min: f32, max: f32) {
+    let () = unsafe { gl::TexParameterf(target as _, gl::TEXTURE_MIN_LOD, min) };
+    let () = unsafe { gl::TexParameterf(target as _, gl::TEXTURE_MAX_LOD, max) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn max_texture_anisotropy(&self) -> Option<f32> {
+    if !self.has_extension("GL_EXT_texture_filter_anisotropic")
+      && !self.has_extension("GL_ARB_texture_filter_anisotropic")
+    {
+      return None
+    }
+
+    let mut value = MaybeUninit::uninit();
+    let () = unsafe { gl::GetFloatv(MAX_TEXTURE_MAX_ANISOTROPY, value.as_mut_ptr()) };
+    debug_assert_eq!(self.error(), Ok(()));
+    Some(unsafe { value.assume_init() })
+  }
+
+  #[inline]
+  fn set_texture_anisotropy(&self, target: TextureTarget, anisotropy: f32) {
+    let () = unsafe { gl::TexParameterf(target as _, TEXTURE_MAX_ANISOTROPY, anisotropy) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_texture_base_level(&self, target: TextureTarget, level: u32) {
+    let () = unsafe { gl::TexParameteri(target as _, gl::TEXTURE_BASE_LEVEL, level as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_texture_max_level(&self, target: TextureTarget, level: u32) {
+    let () = unsafe { gl::TexParameteri(target as _, gl::TEXTURE_MAX_LEVEL, level as _) };
     debug_assert_eq!(self.error(), Ok(()));
   }
 
@@ -966,6 +2123,58 @@ impl Gl for Context {
     let () = unsafe { gl::GenerateMipmap(target as _) };
     debug_assert_eq!(self.error(), Ok(()));
   }
+
+  #[inline]
+  fn create_sampler(&self) -> Result<Sampler, Error> {
+    let mut id = 0;
+    let () = unsafe { gl::GenSamplers(1, &mut id) };
+    let () = self.error()?;
+    Ok(Sampler(id))
+  }
+
+  #[inline]
+  fn delete_sampler(&self, sampler: &Sampler) {
+    let () = unsafe { gl::DeleteSamplers(1, &sampler.0) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn bind_sampler(&self, unit: u32, sampler: Option<&Sampler>) {
+    let sampler = sampler.map(|sampler| sampler.0).unwrap_or_default();
+    let () = unsafe { gl::BindSampler(unit, sampler) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_filter(&self, sampler: &Sampler, ty: TextureFilterType, filter: TextureFilter) {
+    let () = unsafe { gl::SamplerParameteri(sampler.0, ty as _, filter as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_wrap(&self, sampler: &Sampler, axis: TextureWrapAxis, wrap: TextureWrap) {
+    let () = unsafe { gl::SamplerParameteri(sampler.0, axis as _, wrap as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_lod_range(&self, sampler: &Sampler, min: f32, max: f32) {
+    let () = unsafe { gl::SamplerParameterf(sampler.0, gl::TEXTURE_MIN_LOD, min) };
+    let () = unsafe { gl::SamplerParameterf(sampler.0, gl::TEXTURE_MAX_LOD, max) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_compare_mode(&self, sampler: &Sampler, mode: TextureCompareMode) {
+    let () = unsafe { gl::SamplerParameteri(sampler.0, gl::TEXTURE_COMPARE_MODE, mode as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_compare_func(&self, sampler: &Sampler, func: Func) {
+    let () = unsafe { gl::SamplerParameteri(sampler.0, gl::TEXTURE_COMPARE_FUNC, func as _) };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
 }
 
 
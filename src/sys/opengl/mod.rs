@@ -9,6 +9,7 @@ mod gl;
 
 pub use context::Capability;
 pub use context::ClearMask;
+pub use context::CompressedTextureFormat;
 pub use context::Context;
 pub use context::CullFace;
 pub use context::Factor;
@@ -16,8 +17,10 @@ pub use context::Framebuffer;
 pub use context::FramebufferStatus;
 pub use context::FrontFace;
 pub use context::Func;
+pub use context::GlslType;
 pub use context::Primitive;
 pub use context::Program;
+pub use context::Sampler;
 pub use context::Shader;
 pub use context::ShaderType;
 pub use context::Texture;
@@ -26,8 +29,11 @@ pub use context::TextureFilter;
 pub use context::TextureFilterType;
 pub use context::TextureInternalFormat;
 pub use context::TexturePixelFormat;
+pub use context::TextureSwizzle;
 pub use context::TextureTarget;
 pub use context::TextureWrap;
+pub use context::TextureWrapAxis;
+pub use context::TransformFeedbackMode;
 pub use context::Type;
 pub use context::UniformLocation;
 pub use context::VertexArrayObject;
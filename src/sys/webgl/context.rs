@@ -1,18 +1,23 @@
 // Copyright (C) 2025-2026 Daniel Mueller <deso@posteo.net>
 // SPDX-License-Identifier: (Apache-2.0 OR MIT)
 
+use std::cell::Cell;
 use std::error::Error as StdError;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
+use std::mem::size_of;
 use std::mem::size_of_val;
 use std::ops::BitOr;
 use std::ops::BitOrAssign;
+use std::rc::Rc;
 use std::slice;
 
 use wasm_bindgen::prelude::JsValue;
+use wasm_bindgen::JsCast as _;
 
+use web_sys::js_sys::Array;
 use web_sys::js_sys::Float32Array;
 use web_sys::js_sys::Uint32Array;
 use web_sys::WebGl2RenderingContext;
@@ -20,6 +25,7 @@ use web_sys::WebGl2RenderingContext;
 pub use web_sys::WebGlBuffer as VertexBufferObject;
 pub use web_sys::WebGlFramebuffer as Framebuffer;
 pub use web_sys::WebGlProgram as Program;
+pub use web_sys::WebGlSampler as Sampler;
 pub use web_sys::WebGlShader as Shader;
 pub use web_sys::WebGlTexture as Texture;
 pub use web_sys::WebGlUniformLocation as UniformLocation;
@@ -30,6 +36,11 @@ use crate::sys::Gl;
 use crate::sys::Sealed;
 
 
+// Constants of the `EXT_texture_filter_anisotropic` extension.
+const TEXTURE_MAX_ANISOTROPY: u32 = 0x84fe;
+const MAX_TEXTURE_MAX_ANISOTROPY: u32 = 0x84ff;
+
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct Error(u32);
 
@@ -58,9 +69,16 @@ impl StdError for Error {}
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug)]
 pub enum Type {
+  Byte = WebGl2RenderingContext::BYTE,
   Float = WebGl2RenderingContext::FLOAT,
+  HalfFloat = WebGl2RenderingContext::HALF_FLOAT,
+  Int = WebGl2RenderingContext::INT,
   Short = WebGl2RenderingContext::SHORT,
   UnsignedByte = WebGl2RenderingContext::UNSIGNED_BYTE,
+  UnsignedInt = WebGl2RenderingContext::UNSIGNED_INT,
+  UnsignedInt10F11F11FRev = WebGl2RenderingContext::UNSIGNED_INT_10F_11F_11F_REV,
+  UnsignedInt2101010Rev = WebGl2RenderingContext::UNSIGNED_INT_2_10_10_10_REV,
+  UnsignedInt248 = WebGl2RenderingContext::UNSIGNED_INT_24_8,
   UnsignedShort = WebGl2RenderingContext::UNSIGNED_SHORT,
 }
 
@@ -114,7 +132,10 @@ pub enum CullFace {
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Primitive {
+  Points = WebGl2RenderingContext::POINTS,
   Lines = WebGl2RenderingContext::LINES,
+  LineLoop = WebGl2RenderingContext::LINE_LOOP,
+  LineStrip = WebGl2RenderingContext::LINE_STRIP,
   Triangles = WebGl2RenderingContext::TRIANGLES,
   TriangleFan = WebGl2RenderingContext::TRIANGLE_FAN,
   TriangleStrip = WebGl2RenderingContext::TRIANGLE_STRIP,
@@ -164,11 +185,63 @@ impl Display for FramebufferStatus {
 }
 
 
+/// The type of a GLSL variable, e.g., of a uniform or an attribute.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GlslType(pub(in crate::sys) u32);
+
+#[expect(non_upper_case_globals)]
+impl GlslType {
+  pub const Float: Self = Self(WebGl2RenderingContext::FLOAT);
+  pub const Vec2: Self = Self(WebGl2RenderingContext::FLOAT_VEC2);
+  pub const Vec3: Self = Self(WebGl2RenderingContext::FLOAT_VEC3);
+  pub const Vec4: Self = Self(WebGl2RenderingContext::FLOAT_VEC4);
+  pub const Int: Self = Self(WebGl2RenderingContext::INT);
+  pub const IVec2: Self = Self(WebGl2RenderingContext::INT_VEC2);
+  pub const IVec3: Self = Self(WebGl2RenderingContext::INT_VEC3);
+  pub const IVec4: Self = Self(WebGl2RenderingContext::INT_VEC4);
+  pub const UInt: Self = Self(WebGl2RenderingContext::UNSIGNED_INT);
+  pub const UVec2: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_VEC2);
+  pub const UVec3: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_VEC3);
+  pub const UVec4: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_VEC4);
+  pub const Bool: Self = Self(WebGl2RenderingContext::BOOL);
+  pub const BVec2: Self = Self(WebGl2RenderingContext::BOOL_VEC2);
+  pub const BVec3: Self = Self(WebGl2RenderingContext::BOOL_VEC3);
+  pub const BVec4: Self = Self(WebGl2RenderingContext::BOOL_VEC4);
+  pub const Mat2: Self = Self(WebGl2RenderingContext::FLOAT_MAT2);
+  pub const Mat3: Self = Self(WebGl2RenderingContext::FLOAT_MAT3);
+  pub const Mat4: Self = Self(WebGl2RenderingContext::FLOAT_MAT4);
+  pub const Mat2x3: Self = Self(WebGl2RenderingContext::FLOAT_MAT2X3);
+  pub const Mat2x4: Self = Self(WebGl2RenderingContext::FLOAT_MAT2X4);
+  pub const Mat3x2: Self = Self(WebGl2RenderingContext::FLOAT_MAT3X2);
+  pub const Mat3x4: Self = Self(WebGl2RenderingContext::FLOAT_MAT3X4);
+  pub const Mat4x2: Self = Self(WebGl2RenderingContext::FLOAT_MAT4X2);
+  pub const Mat4x3: Self = Self(WebGl2RenderingContext::FLOAT_MAT4X3);
+  pub const Sampler2D: Self = Self(WebGl2RenderingContext::SAMPLER_2D);
+  pub const Sampler3D: Self = Self(WebGl2RenderingContext::SAMPLER_3D);
+  pub const SamplerCube: Self = Self(WebGl2RenderingContext::SAMPLER_CUBE);
+  pub const Sampler2DShadow: Self = Self(WebGl2RenderingContext::SAMPLER_2D_SHADOW);
+  pub const Sampler2DArray: Self = Self(WebGl2RenderingContext::SAMPLER_2D_ARRAY);
+  pub const Sampler2DArrayShadow: Self = Self(WebGl2RenderingContext::SAMPLER_2D_ARRAY_SHADOW);
+  pub const SamplerCubeShadow: Self = Self(WebGl2RenderingContext::SAMPLER_CUBE_SHADOW);
+  pub const ISampler2D: Self = Self(WebGl2RenderingContext::INT_SAMPLER_2D);
+  pub const ISampler3D: Self = Self(WebGl2RenderingContext::INT_SAMPLER_3D);
+  pub const ISamplerCube: Self = Self(WebGl2RenderingContext::INT_SAMPLER_CUBE);
+  pub const ISampler2DArray: Self = Self(WebGl2RenderingContext::INT_SAMPLER_2D_ARRAY);
+  pub const USampler2D: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_2D);
+  pub const USampler3D: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_3D);
+  pub const USamplerCube: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_CUBE);
+  pub const USampler2DArray: Self = Self(WebGl2RenderingContext::UNSIGNED_INT_SAMPLER_2D_ARRAY);
+}
+
+
 #[repr(u32)]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug)]
 pub enum ShaderType {
   Fragment = WebGl2RenderingContext::FRAGMENT_SHADER,
+  /// Geometry shaders are not supported by WebGL and only present for
+  /// API compatibility with the OpenGL backend.
+  Geometry = 0x8DD9,
   Vertex = WebGl2RenderingContext::VERTEX_SHADER,
 }
 
@@ -176,9 +249,16 @@ impl ShaderType {
   pub fn as_str(&self) -> &'static str {
     match self {
       Self::Fragment => "fragment",
+      Self::Geometry => "geometry",
       Self::Vertex => "vertex",
     }
   }
+
+  /// Check whether the shader type is supported by the backend.
+  #[inline]
+  pub fn is_supported(&self) -> bool {
+    !matches!(self, Self::Geometry)
+  }
 }
 
 
@@ -206,6 +286,29 @@ pub enum VertexBufferUsage {
 pub enum TextureTarget {
   Texture2D = WebGl2RenderingContext::TEXTURE_2D,
   Texture2DArray = WebGl2RenderingContext::TEXTURE_2D_ARRAY,
+  Texture3D = WebGl2RenderingContext::TEXTURE_3D,
+  CubeMap = WebGl2RenderingContext::TEXTURE_CUBE_MAP,
+  // The individual faces of a cube map, usable only for specifying
+  // images and attaching faces to framebuffers.
+  CubeMapPositiveX = WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_X,
+  CubeMapNegativeX = WebGl2RenderingContext::TEXTURE_CUBE_MAP_NEGATIVE_X,
+  CubeMapPositiveY = WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_Y,
+  CubeMapNegativeY = WebGl2RenderingContext::TEXTURE_CUBE_MAP_NEGATIVE_Y,
+  CubeMapPositiveZ = WebGl2RenderingContext::TEXTURE_CUBE_MAP_POSITIVE_Z,
+  CubeMapNegativeZ = WebGl2RenderingContext::TEXTURE_CUBE_MAP_NEGATIVE_Z,
+}
+
+impl TextureTarget {
+  /// The targets of the six faces of a cube map, in the order expected
+  /// by [`Builder::from_cube_faces`][crate::TextureBuilder::from_cube_faces].
+  pub const CUBE_MAP_FACES: [Self; 6] = [
+    Self::CubeMapPositiveX,
+    Self::CubeMapNegativeX,
+    Self::CubeMapPositiveY,
+    Self::CubeMapNegativeY,
+    Self::CubeMapPositiveZ,
+    Self::CubeMapNegativeZ,
+  ];
 }
 
 #[repr(u32)]
@@ -213,12 +316,137 @@ pub enum TextureTarget {
 #[derive(Clone, Copy, Debug)]
 pub enum TextureInternalFormat {
   Gray8 = WebGl2RenderingContext::R8,
-  Depth = WebGl2RenderingContext::DEPTH_COMPONENT32F,
   RG8 = WebGl2RenderingContext::RG8,
   RGB8 = WebGl2RenderingContext::RGB8,
   SRGB8 = WebGl2RenderingContext::SRGB8,
   RGBA8 = WebGl2RenderingContext::RGBA8,
   SRGBA8 = WebGl2RenderingContext::SRGB8_ALPHA8,
+  R16F = WebGl2RenderingContext::R16F,
+  RG16F = WebGl2RenderingContext::RG16F,
+  RGB16F = WebGl2RenderingContext::RGB16F,
+  RGBA16F = WebGl2RenderingContext::RGBA16F,
+  R32F = WebGl2RenderingContext::R32F,
+  RG32F = WebGl2RenderingContext::RG32F,
+  RGB32F = WebGl2RenderingContext::RGB32F,
+  RGBA32F = WebGl2RenderingContext::RGBA32F,
+  R11G11B10F = WebGl2RenderingContext::R11F_G11F_B10F,
+  RGB10A2 = WebGl2RenderingContext::RGB10_A2,
+  R8I = WebGl2RenderingContext::R8I,
+  R8UI = WebGl2RenderingContext::R8UI,
+  R16I = WebGl2RenderingContext::R16I,
+  R16UI = WebGl2RenderingContext::R16UI,
+  R32I = WebGl2RenderingContext::R32I,
+  R32UI = WebGl2RenderingContext::R32UI,
+  RG8I = WebGl2RenderingContext::RG8I,
+  RG8UI = WebGl2RenderingContext::RG8UI,
+  RG16I = WebGl2RenderingContext::RG16I,
+  RG16UI = WebGl2RenderingContext::RG16UI,
+  RG32I = WebGl2RenderingContext::RG32I,
+  RG32UI = WebGl2RenderingContext::RG32UI,
+  RGB8I = WebGl2RenderingContext::RGB8I,
+  RGB8UI = WebGl2RenderingContext::RGB8UI,
+  RGB16I = WebGl2RenderingContext::RGB16I,
+  RGB16UI = WebGl2RenderingContext::RGB16UI,
+  RGB32I = WebGl2RenderingContext::RGB32I,
+  RGB32UI = WebGl2RenderingContext::RGB32UI,
+  RGBA8I = WebGl2RenderingContext::RGBA8I,
+  RGBA8UI = WebGl2RenderingContext::RGBA8UI,
+  RGBA16I = WebGl2RenderingContext::RGBA16I,
+  RGBA16UI = WebGl2RenderingContext::RGBA16UI,
+  RGBA32I = WebGl2RenderingContext::RGBA32I,
+  RGBA32UI = WebGl2RenderingContext::RGBA32UI,
+  Depth16 = WebGl2RenderingContext::DEPTH_COMPONENT16,
+  Depth24 = WebGl2RenderingContext::DEPTH_COMPONENT24,
+  Depth32F = WebGl2RenderingContext::DEPTH_COMPONENT32F,
+  Depth24Stencil8 = WebGl2RenderingContext::DEPTH24_STENCIL8,
+}
+
+#[expect(non_upper_case_globals)]
+impl TextureInternalFormat {
+  /// The depth format used for depth maps.
+  pub const Depth: Self = Self::Depth32F;
+}
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum CompressedTextureFormat {
+  // S3TC (BC1-BC3).
+  BC1RGB = 0x83F0,   // COMPRESSED_RGB_S3TC_DXT1_EXT
+  BC1SRGB = 0x8C4C,  // COMPRESSED_SRGB_S3TC_DXT1_EXT
+  BC1RGBA = 0x83F1,  // COMPRESSED_RGBA_S3TC_DXT1_EXT
+  BC1SRGBA = 0x8C4D, // COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT
+  BC2 = 0x83F2,      // COMPRESSED_RGBA_S3TC_DXT3_EXT
+  BC2SRGB = 0x8C4E,  // COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT
+  BC3 = 0x83F3,      // COMPRESSED_RGBA_S3TC_DXT5_EXT
+  BC3SRGB = 0x8C4F,  // COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT
+  // RGTC (BC4 & BC5).
+  BC4 = 0x8DBB,       // COMPRESSED_RED_RGTC1
+  BC4Signed = 0x8DBC, // COMPRESSED_SIGNED_RED_RGTC1
+  BC5 = 0x8DBD,       // COMPRESSED_RG_RGTC2
+  BC5Signed = 0x8DBE, // COMPRESSED_SIGNED_RG_RGTC2
+  // BPTC (BC6H & BC7).
+  BC6H = 0x8E8F,       // COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT
+  BC6HSigned = 0x8E8E, // COMPRESSED_RGB_BPTC_SIGNED_FLOAT
+  BC7 = 0x8E8C,        // COMPRESSED_RGBA_BPTC_UNORM
+  BC7SRGB = 0x8E8D,    // COMPRESSED_SRGB_ALPHA_BPTC_UNORM
+  // ETC2 & EAC.
+  ETC2RGB8 = 0x9274,      // COMPRESSED_RGB8_ETC2
+  ETC2SRGB8 = 0x9275,     // COMPRESSED_SRGB8_ETC2
+  ETC2RGB8A1 = 0x9276,    // COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2
+  ETC2SRGB8A1 = 0x9277,   // COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2
+  ETC2RGBA8 = 0x9278,     // COMPRESSED_RGBA8_ETC2_EAC
+  ETC2SRGBA8 = 0x9279,    // COMPRESSED_SRGB8_ALPHA8_ETC2_EAC
+  EACR11 = 0x9270,        // COMPRESSED_R11_EAC
+  EACR11Signed = 0x9271,  // COMPRESSED_SIGNED_R11_EAC
+  EACRG11 = 0x9272,       // COMPRESSED_RG11_EAC
+  EACRG11Signed = 0x9273, // COMPRESSED_SIGNED_RG11_EAC
+  // ASTC.
+  ASTC4x4 = 0x93B0,     // COMPRESSED_RGBA_ASTC_4x4_KHR
+  ASTC4x4SRGB = 0x93D0, // COMPRESSED_SRGB8_ALPHA8_ASTC_4x4_KHR
+  ASTC5x5 = 0x93B2,     // COMPRESSED_RGBA_ASTC_5x5_KHR
+  ASTC5x5SRGB = 0x93D2, // COMPRESSED_SRGB8_ALPHA8_ASTC_5x5_KHR
+  ASTC6x6 = 0x93B4,     // COMPRESSED_RGBA_ASTC_6x6_KHR
+  ASTC6x6SRGB = 0x93D4, // COMPRESSED_SRGB8_ALPHA8_ASTC_6x6_KHR
+  ASTC8x8 = 0x93B7,     // COMPRESSED_RGBA_ASTC_8x8_KHR
+  ASTC8x8SRGB = 0x93D7, // COMPRESSED_SRGB8_ALPHA8_ASTC_8x8_KHR
+}
+
+
+impl CompressedTextureFormat {
+  /// Retrieve the extensions required for using the format.
+  fn extensions(&self) -> &'static [&'static str] {
+    match self {
+      Self::BC1RGB | Self::BC1RGBA | Self::BC2 | Self::BC3 => &["WEBGL_compressed_texture_s3tc"],
+      Self::BC1SRGB | Self::BC1SRGBA | Self::BC2SRGB | Self::BC3SRGB => {
+        &["WEBGL_compressed_texture_s3tc_srgb"]
+      },
+      Self::BC4 | Self::BC4Signed | Self::BC5 | Self::BC5Signed => {
+        &["EXT_texture_compression_rgtc"]
+      },
+      Self::BC6H | Self::BC6HSigned | Self::BC7 | Self::BC7SRGB => {
+        &["EXT_texture_compression_bptc"]
+      },
+      Self::ETC2RGB8
+      | Self::ETC2SRGB8
+      | Self::ETC2RGB8A1
+      | Self::ETC2SRGB8A1
+      | Self::ETC2RGBA8
+      | Self::ETC2SRGBA8
+      | Self::EACR11
+      | Self::EACR11Signed
+      | Self::EACRG11
+      | Self::EACRG11Signed => &["WEBGL_compressed_texture_etc"],
+      Self::ASTC4x4
+      | Self::ASTC4x4SRGB
+      | Self::ASTC5x5
+      | Self::ASTC5x5SRGB
+      | Self::ASTC6x6
+      | Self::ASTC6x6SRGB
+      | Self::ASTC8x8
+      | Self::ASTC8x8SRGB => &["WEBGL_compressed_texture_astc"],
+    }
+  }
 }
 
 #[repr(u32)]
@@ -230,12 +458,18 @@ pub enum TexturePixelFormat {
   RG = WebGl2RenderingContext::RG,
   RGB = WebGl2RenderingContext::RGB,
   RGBA = WebGl2RenderingContext::RGBA,
+  GrayInteger = WebGl2RenderingContext::RED_INTEGER,
+  RGInteger = WebGl2RenderingContext::RG_INTEGER,
+  RGBInteger = WebGl2RenderingContext::RGB_INTEGER,
+  RGBAInteger = WebGl2RenderingContext::RGBA_INTEGER,
+  DepthStencil = WebGl2RenderingContext::DEPTH_STENCIL,
 }
 
 #[repr(u32)]
 #[non_exhaustive]
 #[derive(Clone, Copy, Debug)]
 pub enum TextureCompareMode {
+  None = WebGl2RenderingContext::NONE,
   RefToTexture = WebGl2RenderingContext::COMPARE_REF_TO_TEXTURE,
 }
 
@@ -245,6 +479,25 @@ pub enum TextureCompareMode {
 pub enum TextureWrap {
   ClampToEdge = WebGl2RenderingContext::CLAMP_TO_EDGE,
   Repeat = WebGl2RenderingContext::REPEAT,
+  MirroredRepeat = WebGl2RenderingContext::MIRRORED_REPEAT,
+}
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum TextureWrapAxis {
+  S = WebGl2RenderingContext::TEXTURE_WRAP_S,
+  T = WebGl2RenderingContext::TEXTURE_WRAP_T,
+  R = WebGl2RenderingContext::TEXTURE_WRAP_R,
+}
+
+
+#[repr(u32)]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug)]
+pub enum TransformFeedbackMode {
+  Interleaved = WebGl2RenderingContext::INTERLEAVED_ATTRIBS,
+  Separate = WebGl2RenderingContext::SEPARATE_ATTRIBS,
 }
 
 #[repr(u32)]
@@ -267,31 +520,188 @@ pub enum TextureFilter {
   NearestMipmapNearest = WebGl2RenderingContext::NEAREST_MIPMAP_NEAREST,
 }
 
+impl BuiltinType<Context> for u8 {
+  fn as_type() -> Type {
+    Type::UnsignedByte
+  }
+}
+
 impl BuiltinType<Context> for u16 {
   fn as_type() -> Type {
     Type::UnsignedShort
   }
 }
 
+impl BuiltinType<Context> for u32 {
+  fn as_type() -> Type {
+    Type::UnsignedInt
+  }
+}
+
 
 /// The WebGL context in use.
 ///
 /// A context is guaranteed to be cheaply cloneable.
 #[derive(Clone, Debug)]
-pub struct Context(WebGl2RenderingContext);
+pub struct Context(
+  WebGl2RenderingContext,
+  /// An error raised by emulated functionality, which gets reported
+  /// (and cleared) by the next `error` call, just like a GL error.
+  Rc<Cell<u32>>,
+);
 
 impl Context {
   #[inline]
   pub fn new(context: WebGl2RenderingContext) -> Self {
-    Self(context)
+    Self(
+      context,
+      Rc::new(Cell::new(WebGl2RenderingContext::NO_ERROR)),
+    )
+  }
+
+  fn check_program(&self, program: &Program, status_attrib: u32) -> Result<(), Vec<u8>> {
+    let status = self.0.get_program_parameter(program, status_attrib);
+    if status.as_bool() == Some(false) {
+      Err(self.program_info_log(program))
+    } else {
+      Ok(())
+    }
   }
 
-  fn check_program(&self, program: &Program) -> Result<(), Vec<u8>> {
-    let result = self.0.get_program_info_log(program);
-    match result {
-      Some(log) if !log.is_empty() => Err(log.into_bytes()),
-      _ => Ok(()),
+  /// Emulate a "base vertex" draw call, which WebGL does not support.
+  ///
+  /// The `count` indices starting at byte `offset` in the currently
+  /// bound element array buffer are read back, adjusted by
+  /// `base_vertex`, and uploaded into a temporary element array buffer,
+  /// which is bound while `f` runs. Indices with all bits set are left
+  /// untouched, as they denote a primitive restart. If an adjusted index
+  /// no longer fits into `T` (or would be mistaken for a primitive
+  /// restart), all indices are widened to `u32` and `f` is told so via
+  /// the index type it receives. If an adjusted index does not fit into
+  /// a `u32`, `f` is not run and `INVALID_VALUE` is raised instead.
+  ///
+  /// Note that this amounts to a synchronous read back of the index
+  /// data as well as the creation and upload of a new buffer on every
+  /// call.
+  fn with_rebased_indices<T, F>(&self, count: i32, offset: i32, base_vertex: i32, f: F)
+  where
+    T: BuiltinType<Self>,
+    F: FnOnce(Type),
+  {
+    let target = WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER;
+    let size = size_of::<T>();
+    let mut indices = vec![0u8; count as usize * size];
+    let () = self
+      .0
+      .get_buffer_sub_data_with_i32_and_u8_array(target, offset, &mut indices);
+
+    let restart = u32::MAX >> (32 - 8 * size);
+    let indices = indices
+      .chunks_exact(size)
+      .map(|index| {
+        let mut bytes = [0; 4];
+        let () = bytes[..size].copy_from_slice(index);
+        let value = u32::from_le_bytes(bytes);
+        if value == restart {
+          return Ok(None)
+        }
+        value
+          .checked_add_signed(base_vertex)
+          .filter(|value| *value != u32::MAX)
+          .map(Some)
+          .ok_or(value)
+      })
+      .collect::<Result<Vec<_>, _>>();
+    let indices = match indices {
+      Ok(indices) => indices,
+      Err(_value) => {
+        let () = self.1.set(WebGl2RenderingContext::INVALID_VALUE);
+        return
+      },
+    };
+
+    let wide = indices.iter().flatten().any(|value| *value >= restart);
+    let (ty, size, restart) = if wide {
+      (Type::UnsignedInt, size_of::<u32>(), u32::MAX)
+    } else {
+      (T::as_type(), size, restart)
+    };
+    let indices = indices
+      .into_iter()
+      .flat_map(|value| {
+        let value = value.unwrap_or(restart);
+        value.to_le_bytes().into_iter().take(size)
+      })
+      .collect::<Vec<u8>>();
+
+    let original = self
+      .0
+      .get_parameter(WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER_BINDING)
+      .ok()
+      .and_then(|buffer| buffer.dyn_into::<VertexBufferObject>().ok());
+    let buffer = self.0.create_buffer();
+    let () = self.0.bind_buffer(target, buffer.as_ref());
+    let () =
+      self
+        .0
+        .buffer_data_with_u8_array(target, &indices, WebGl2RenderingContext::STREAM_DRAW);
+
+    let () = f(ty);
+
+    let () = self.0.bind_buffer(target, original.as_ref());
+    let () = self.0.delete_buffer(buffer.as_ref());
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn program_param(&self, program: &Program, param: u32) -> u32 {
+    let value = self.0.get_program_parameter(program, param);
+    value.as_f64().unwrap_or_default() as u32
+  }
+
+  #[inline]
+  fn uniform_block_param(&self, program: &Program, index: u32, param: u32) -> JsValue {
+    self
+      .0
+      .get_active_uniform_block_parameter(program, index, param)
+      .unwrap_or(JsValue::UNDEFINED)
+  }
+
+  /// Retrieve the value of an integer or boolean (vector) uniform.
+  ///
+  /// Depending on the uniform's type, `getUniform` reports a number, a
+  /// boolean, a typed array, or an array of booleans.
+  fn uniform_values<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[f64; N], Error> {
+    let value = self.0.get_uniform(program, location);
+    // The location may not belong to `program`.
+    let () = self.error()?;
+    let number = |value: &JsValue| {
+      value
+        .as_f64()
+        .or_else(|| value.as_bool().map(|value| f64::from(u8::from(value))))
+    };
+
+    let mut result = [0f64; N];
+    if let Some(value) = number(&value) {
+      if N != 1 {
+        return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+      }
+      result[0] = value;
+    } else {
+      let array = Array::from(&value);
+      if array.length() as usize != N {
+        return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+      }
+      let () = result
+        .iter_mut()
+        .zip(array.iter())
+        .for_each(|(result, value)| *result = number(&value).unwrap_or_default());
     }
+    Ok(result)
   }
 
   #[inline]
@@ -313,6 +723,7 @@ impl Gl for Context {
 
   type Capability = Capability;
   type ClearMask = ClearMask;
+  type CompressedTextureFormat = CompressedTextureFormat;
   type CullFace = CullFace;
   type Factor = Factor;
   type FramebufferStatus = FramebufferStatus;
@@ -327,22 +738,29 @@ impl Gl for Context {
   type TexturePixelFormat = TexturePixelFormat;
   type TextureTarget = TextureTarget;
   type TextureWrap = TextureWrap;
+  type TextureWrapAxis = TextureWrapAxis;
+  type TransformFeedbackMode = TransformFeedbackMode;
   type Type = Type;
   type VertexBufferTarget = VertexBufferTarget;
   type VertexBufferUsage = VertexBufferUsage;
 
   type Framebuffer = Framebuffer;
   type Program = Program;
+  type Sampler = Sampler;
   type Shader = Shader;
   type Texture = Texture;
   type VertexArrayObject = VertexArrayObject;
   type VertexBufferObject = VertexBufferObject;
 
+  type GlslType = GlslType;
   type UniformLocation = UniformLocation;
 
   #[inline]
   fn error(&self) -> Result<(), Error> {
-    let error = self.0.get_error();
+    let error = match self.1.replace(WebGl2RenderingContext::NO_ERROR) {
+      WebGl2RenderingContext::NO_ERROR => self.0.get_error(),
+      error => error,
+    };
     if error == WebGl2RenderingContext::NO_ERROR {
       Ok(())
     } else {
@@ -406,6 +824,43 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn set_pixel_unpack_row_length(&self, length: u32) {
+    let () = self
+      .0
+      .pixel_storei(WebGl2RenderingContext::UNPACK_ROW_LENGTH, length as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_pixel_unpack_skip(&self, pixels: u32, rows: u32) {
+    let () = self
+      .0
+      .pixel_storei(WebGl2RenderingContext::UNPACK_SKIP_PIXELS, pixels as _);
+    let () = self
+      .0
+      .pixel_storei(WebGl2RenderingContext::UNPACK_SKIP_ROWS, rows as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_pixel_pack_alignment(&self, alignment: u32) {
+    let () = self
+      .0
+      .pixel_storei(WebGl2RenderingContext::PACK_ALIGNMENT, alignment as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_primitive_restart<T>(&self, _enable: bool)
+  where
+    T: BuiltinType<Self>,
+  {
+    // WebGL 2 always has primitive restart enabled, with the maximum
+    // value of the index type acting as the restart index. There is no
+    // way to change that.
+  }
+
   #[inline]
   fn clear(&self, mask: ClearMask) {
     let () = self.0.clear(mask.0);
@@ -413,30 +868,122 @@ impl Gl for Context {
   }
 
   #[inline]
-  fn draw_arrays(&self, primitive: Primitive, count: i32) {
-    let () = self.0.draw_arrays(primitive as _, 0, count);
+  fn draw_arrays(&self, primitive: Primitive, first: i32, count: i32) {
+    let () = self.0.draw_arrays(primitive as _, first, count);
     debug_assert_eq!(self.error(), Ok(()));
   }
 
   #[inline]
-  fn draw_arrays_instanced(&self, primitive: Primitive, count: i32, instance_count: i32) {
+  fn draw_arrays_instanced(
+    &self,
+    primitive: Primitive,
+    first: i32,
+    count: i32,
+    instance_count: i32,
+  ) {
     let () = self
       .0
-      .draw_arrays_instanced(primitive as _, 0, count, instance_count);
+      .draw_arrays_instanced(primitive as _, first, count, instance_count);
     debug_assert_eq!(self.error(), Ok(()));
   }
 
   #[inline]
-  fn draw_elements<T>(&self, primitive: Primitive, count: i32)
+  fn draw_elements<T>(&self, primitive: Primitive, count: i32, offset: i32)
   where
     T: BuiltinType<Self>,
   {
     let () = self
       .0
-      .draw_elements_with_i32(primitive as _, count, T::as_type() as _, 0);
+      .draw_elements_with_i32(primitive as _, count, T::as_type() as _, offset);
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn draw_elements_instanced<T>(
+    &self,
+    primitive: Primitive,
+    count: i32,
+    offset: i32,
+    instance_count: i32,
+  ) where
+    T: BuiltinType<Self>,
+  {
+    let () = self.0.draw_elements_instanced_with_i32(
+      primitive as _,
+      count,
+      T::as_type() as _,
+      offset,
+      instance_count,
+    );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn draw_elements_base_vertex<T>(
+    &self,
+    primitive: Primitive,
+    count: i32,
+    offset: i32,
+    base_vertex: i32,
+  ) where
+    T: BuiltinType<Self>,
+  {
+    if base_vertex == 0 {
+      return self.draw_elements::<T>(primitive, count, offset)
+    }
+
+    let () = self.with_rebased_indices::<T, _>(count, offset, base_vertex, |ty| {
+      let () = self
+        .0
+        .draw_elements_with_i32(primitive as _, count, ty as _, 0);
+      debug_assert_eq!(self.error(), Ok(()));
+    });
+  }
+
+  #[inline]
+  fn draw_range_elements<T>(
+    &self,
+    primitive: Primitive,
+    start: u32,
+    end: u32,
+    count: i32,
+    offset: i32,
+  ) where
+    T: BuiltinType<Self>,
+  {
+    let () = self.0.draw_range_elements_with_i32(
+      primitive as _,
+      start,
+      end,
+      count,
+      T::as_type() as _,
+      offset,
+    );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn multi_draw_arrays(&self, primitive: Primitive, firsts: &[i32], counts: &[i32]) {
+    debug_assert_eq!(firsts.len(), counts.len());
+
+    // WebGL has no native multi-draw support without extensions, so we
+    // just issue the individual draw calls ourselves.
+    let () = firsts
+      .iter()
+      .zip(counts)
+      .for_each(|(first, count)| self.draw_arrays(primitive, *first, *count));
+  }
+
+  fn multi_draw_elements<T>(&self, primitive: Primitive, counts: &[i32], offsets: &[i32])
+  where
+    T: BuiltinType<Self>,
+  {
+    debug_assert_eq!(offsets.len(), counts.len());
+
+    let () = counts
+      .iter()
+      .zip(offsets)
+      .for_each(|(count, offset)| self.draw_elements::<T>(primitive, *count, *offset));
+  }
+
   #[inline]
   fn create_framebuffer(&self) -> Result<Framebuffer, Error> {
     self
@@ -459,6 +1006,15 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn framebuffer_binding(&self) -> Option<Framebuffer> {
+    self
+      .0
+      .get_parameter(WebGl2RenderingContext::FRAMEBUFFER_BINDING)
+      .ok()
+      .and_then(|fbo| fbo.dyn_into::<Framebuffer>().ok())
+  }
+
   #[inline]
   fn set_framebuffer_depth_texture(&self, texture_target: TextureTarget, texture: &Texture) {
     let mipmap_level = 0;
@@ -472,6 +1028,42 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn set_framebuffer_color_texture(
+    &self,
+    index: u32,
+    texture_target: TextureTarget,
+    texture: &Texture,
+    level: u32,
+  ) {
+    let () = self.0.framebuffer_texture_2d(
+      WebGl2RenderingContext::FRAMEBUFFER,
+      WebGl2RenderingContext::COLOR_ATTACHMENT0 + index,
+      texture_target as _,
+      Some(texture),
+      level as _,
+    );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_framebuffer_color_texture_layer(
+    &self,
+    index: u32,
+    texture: &Texture,
+    level: u32,
+    layer: u32,
+  ) {
+    let () = self.0.framebuffer_texture_layer(
+      WebGl2RenderingContext::FRAMEBUFFER,
+      WebGl2RenderingContext::COLOR_ATTACHMENT0 + index,
+      Some(texture),
+      level as _,
+      layer as _,
+    );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   #[inline]
   fn unset_draw_buffer(&self) {
     let buffers = JsValue::from(Uint32Array::from([WebGl2RenderingContext::NONE].as_slice()));
@@ -485,6 +1077,40 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn read_pixels(
+    &self,
+    x: i32,
+    y: i32,
+    w: u32,
+    h: u32,
+    pixel_format: TexturePixelFormat,
+    channel_type: Type,
+    pixels: &mut [u8],
+  ) -> Result<(), Error> {
+    let () = self
+      .0
+      .read_pixels_with_opt_u8_array(
+        x,
+        y,
+        w as _,
+        h as _,
+        pixel_format as _,
+        channel_type as _,
+        Some(pixels),
+      )
+      // The call may also throw without raising a GL error, e.g., if
+      // the buffer doesn't match the requested type.
+      .map_err(|_err| {
+        self
+          .error()
+          .err()
+          .unwrap_or(Error(WebGl2RenderingContext::INVALID_OPERATION))
+      })?;
+    let () = self.error()?;
+    Ok(())
+  }
+
   #[inline]
   fn check_framebuffer_status(&self) -> FramebufferStatus {
     let status = self
@@ -513,13 +1139,25 @@ impl Gl for Context {
   #[inline]
   fn compile_shader(&self, shader: &Shader) -> Result<(), Vec<u8>> {
     let () = self.0.compile_shader(shader);
-    let result = self.0.get_shader_info_log(shader);
-    match result {
-      Some(log) if !log.is_empty() => Err(log.into_bytes()),
-      _ => Ok(()),
+    let status = self
+      .0
+      .get_shader_parameter(shader, WebGl2RenderingContext::COMPILE_STATUS);
+    if status.as_bool() == Some(false) {
+      Err(self.shader_info_log(shader))
+    } else {
+      Ok(())
     }
   }
 
+  #[inline]
+  fn shader_info_log(&self, shader: &Shader) -> Vec<u8> {
+    self
+      .0
+      .get_shader_info_log(shader)
+      .unwrap_or_default()
+      .into_bytes()
+  }
+
   #[inline]
   fn attach_shader(&self, program: &Program, shader: &Shader) {
     let () = self.0.attach_shader(program, shader);
@@ -543,18 +1181,55 @@ impl Gl for Context {
     debug_assert_eq!(self.error(), Ok(()));
   }
 
+  #[inline]
+  fn bind_attrib_location(&self, program: &Program, index: u32, name: &str) {
+    let () = self.0.bind_attrib_location(program, index, name);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn bind_frag_data_location(&self, _program: &Program, _color: u32, _name: &str) {
+    // WebGL has no equivalent; GLSL ES requires output locations to be
+    // specified in the shader itself.
+  }
+
+  fn set_transform_feedback_varyings(
+    &self,
+    program: &Program,
+    varyings: &[&str],
+    mode: TransformFeedbackMode,
+  ) {
+    let varyings = varyings
+      .iter()
+      .map(|varying| JsValue::from_str(varying))
+      .collect::<Array>();
+    let () = self
+      .0
+      .transform_feedback_varyings(program, &varyings, mode as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   fn link_program(&self, program: &Program) -> Result<(), Vec<u8>> {
     let () = self.0.link_program(program);
-    let () = self.check_program(program)?;
+    let () = self.check_program(program, WebGl2RenderingContext::LINK_STATUS)?;
     Ok(())
   }
 
   fn validate_program(&self, program: &Program) -> Result<(), Vec<u8>> {
     let () = self.0.validate_program(program);
-    let () = self.check_program(program)?;
+    let () = self.check_program(program, WebGl2RenderingContext::VALIDATE_STATUS)?;
     Ok(())
   }
 
+  #[inline]
+  fn program_info_log(&self, program: &Program) -> Vec<u8> {
+    self
+      .0
+      .get_program_info_log(program)
+      .unwrap_or_default()
+      .into_bytes()
+  }
+
   #[inline]
   fn use_program(&self, program: &Program) {
     let () = self.0.use_program(Some(program));
@@ -576,15 +1251,134 @@ impl Gl for Context {
     self.0.get_uniform_location(program, name)
   }
 
-  #[inline]
-  fn uniform_fv<const N: usize>(&self, program: &Program, location: &UniformLocation) -> [f32; N] {
+  fn active_attribs(&self, program: &Program) -> Vec<(String, GlslType, u32)> {
+    let count = self.program_param(program, WebGl2RenderingContext::ACTIVE_ATTRIBUTES);
+    (0..count)
+      .filter_map(|index| self.0.get_active_attrib(program, index))
+      .map(|info| (info.name(), GlslType(info.type_()), info.size() as u32))
+      .collect()
+  }
+
+  fn active_uniforms(&self, program: &Program) -> Vec<(u32, String, GlslType, u32)> {
+    let count = self.program_param(program, WebGl2RenderingContext::ACTIVE_UNIFORMS);
+    (0..count)
+      .filter_map(|index| {
+        let info = self.0.get_active_uniform(program, index)?;
+        Some((
+          index,
+          info.name(),
+          GlslType(info.type_()),
+          info.size() as u32,
+        ))
+      })
+      .collect()
+  }
+
+  fn active_uniform_blocks(&self, program: &Program) -> Vec<(String, u32, u32, Vec<u32>)> {
+    let count = self.program_param(program, WebGl2RenderingContext::ACTIVE_UNIFORM_BLOCKS);
+    (0..count)
+      .map(|index| {
+        let name = self
+          .0
+          .get_active_uniform_block_name(program, index)
+          .unwrap_or_default();
+        let binding = self
+          .uniform_block_param(
+            program,
+            index,
+            WebGl2RenderingContext::UNIFORM_BLOCK_BINDING,
+          )
+          .as_f64()
+          .unwrap_or_default() as u32;
+        let size = self
+          .uniform_block_param(
+            program,
+            index,
+            WebGl2RenderingContext::UNIFORM_BLOCK_DATA_SIZE,
+          )
+          .as_f64()
+          .unwrap_or_default() as u32;
+        let uniforms = Uint32Array::from(self.uniform_block_param(
+          program,
+          index,
+          WebGl2RenderingContext::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES,
+        ))
+        .to_vec();
+
+        (name, binding, size, uniforms)
+      })
+      .collect()
+  }
+
+  #[inline]
+  fn uniform_fv<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[f32; N], Error> {
     let value = self.0.get_uniform(program, location);
+    // The location may not belong to `program`.
+    let () = self.error()?;
+
+    // Scalar uniforms are reported as plain numbers.
+    if let Some(value) = value.as_f64() {
+      if N != 1 {
+        return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+      }
+      return Ok([value as f32; N])
+    }
+
+    if !value.is_instance_of::<Float32Array>() {
+      return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+    }
     let array = Float32Array::from(value);
-    assert!(array.length() as usize == N);
+    if array.length() as usize != N {
+      return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+    }
 
     let mut result = [0f32; N];
     let () = array.copy_to(&mut result);
-    result
+    Ok(result)
+  }
+
+  fn uniform_iv<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[i32; N], Error> {
+    let values = self.uniform_values::<N>(program, location)?;
+    Ok(values.map(|value| value as i32))
+  }
+
+  fn uniform_uiv<const N: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[u32; N], Error> {
+    let values = self.uniform_values::<N>(program, location)?;
+    Ok(values.map(|value| value as u32))
+  }
+
+  fn uniform_matrix_fv<const C: usize, const R: usize>(
+    &self,
+    program: &Program,
+    location: &UniformLocation,
+  ) -> Result<[[f32; R]; C], Error> {
+    let value = self.0.get_uniform(program, location);
+    // The location may not belong to `program`.
+    let () = self.error()?;
+
+    if !value.is_instance_of::<Float32Array>() {
+      return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+    }
+    let array = Float32Array::from(value);
+    if array.length() as usize != C * R {
+      return Err(Error(WebGl2RenderingContext::INVALID_OPERATION))
+    }
+
+    let mut result = [[0f32; R]; C];
+    let () = array.copy_to(result.as_flattened_mut());
+    Ok(result)
   }
 
   #[inline]
@@ -644,6 +1438,110 @@ impl Gl for Context {
     self.set_uniform_matrices_impl(location, slice::from_ref(matrix))
   }
 
+  fn set_uniform_fv<const N: usize>(&self, location: &UniformLocation, data: &[[f32; N]]) {
+    const {
+      assert!(
+        N >= 1 && N <= 4,
+        "uniform component count has to be in 1..=4"
+      )
+    };
+    let location = Some(location);
+    let data = data.as_flattened();
+    let () = match N {
+      1 => self.0.uniform1fv_with_f32_array(location, data),
+      2 => self.0.uniform2fv_with_f32_array(location, data),
+      3 => self.0.uniform3fv_with_f32_array(location, data),
+      4 => self.0.uniform4fv_with_f32_array(location, data),
+      _ => unreachable!(),
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_uniform_iv<const N: usize>(&self, location: &UniformLocation, data: &[[i32; N]]) {
+    const {
+      assert!(
+        N >= 1 && N <= 4,
+        "uniform component count has to be in 1..=4"
+      )
+    };
+    let location = Some(location);
+    let data = data.as_flattened();
+    let () = match N {
+      1 => self.0.uniform1iv_with_i32_array(location, data),
+      2 => self.0.uniform2iv_with_i32_array(location, data),
+      3 => self.0.uniform3iv_with_i32_array(location, data),
+      4 => self.0.uniform4iv_with_i32_array(location, data),
+      _ => unreachable!(),
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_uniform_uiv<const N: usize>(&self, location: &UniformLocation, data: &[[u32; N]]) {
+    const {
+      assert!(
+        N >= 1 && N <= 4,
+        "uniform component count has to be in 1..=4"
+      )
+    };
+    let location = Some(location);
+    let data = data.as_flattened();
+    let () = match N {
+      1 => self.0.uniform1uiv_with_u32_array(location, data),
+      2 => self.0.uniform2uiv_with_u32_array(location, data),
+      3 => self.0.uniform3uiv_with_u32_array(location, data),
+      4 => self.0.uniform4uiv_with_u32_array(location, data),
+      _ => unreachable!(),
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn set_uniform_matrix_fv<const C: usize, const R: usize>(
+    &self,
+    location: &UniformLocation,
+    data: &[[[f32; R]; C]],
+  ) {
+    const {
+      assert!(
+        C >= 2 && C <= 4 && R >= 2 && R <= 4,
+        "uniform matrix dimensions have to be in 2..=4"
+      )
+    };
+    let location = Some(location);
+    let transpose = false;
+    let data = data.as_flattened().as_flattened();
+    let () = match (C, R) {
+      (2, 2) => self
+        .0
+        .uniform_matrix2fv_with_f32_array(location, transpose, data),
+      (2, 3) => self
+        .0
+        .uniform_matrix2x3fv_with_f32_array(location, transpose, data),
+      (2, 4) => self
+        .0
+        .uniform_matrix2x4fv_with_f32_array(location, transpose, data),
+      (3, 2) => self
+        .0
+        .uniform_matrix3x2fv_with_f32_array(location, transpose, data),
+      (3, 3) => self
+        .0
+        .uniform_matrix3fv_with_f32_array(location, transpose, data),
+      (3, 4) => self
+        .0
+        .uniform_matrix3x4fv_with_f32_array(location, transpose, data),
+      (4, 2) => self
+        .0
+        .uniform_matrix4x2fv_with_f32_array(location, transpose, data),
+      (4, 3) => self
+        .0
+        .uniform_matrix4x3fv_with_f32_array(location, transpose, data),
+      (4, 4) => self
+        .0
+        .uniform_matrix4fv_with_f32_array(location, transpose, data),
+      _ => unreachable!(),
+    };
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
   #[inline]
   fn create_vertex_buffer(&self) -> Result<VertexBufferObject, Error> {
     self
@@ -822,6 +1720,96 @@ impl Gl for Context {
       .map_err(|_err| self.error().unwrap_err())
   }
 
+  fn is_compressed_format_supported(&self, format: CompressedTextureFormat) -> bool {
+    format.extensions().iter().all(|extension| {
+      // NB: Extensions have to be requested before their
+      //     functionality can be used.
+      matches!(self.0.get_extension(extension), Ok(Some(_)))
+    })
+  }
+
+  #[inline]
+  fn set_compressed_texture_image_2d(
+    &self,
+    target: TextureTarget,
+    level: u32,
+    format: CompressedTextureFormat,
+    w: u32,
+    h: u32,
+    data: &[u8],
+  ) -> Result<(), Error> {
+    let border = 0;
+
+    let () = self.0.compressed_tex_image_2d_with_u8_array(
+      target as _,
+      level as _,
+      format as _,
+      w as _,
+      h as _,
+      border,
+      data,
+    );
+    let () = self.error()?;
+    Ok(())
+  }
+
+  #[inline]
+  fn set_compressed_texture_image_3d(
+    &self,
+    target: TextureTarget,
+    level: u32,
+    format: CompressedTextureFormat,
+    w: u32,
+    h: u32,
+    count: u32,
+    data: &[u8],
+  ) -> Result<(), Error> {
+    let border = 0;
+
+    let () = self.0.compressed_tex_image_3d_with_u8_array(
+      target as _,
+      level as _,
+      format as _,
+      w as _,
+      h as _,
+      count as _,
+      border,
+      data,
+    );
+    let () = self.error()?;
+    Ok(())
+  }
+
+  #[inline]
+  fn set_texture_sub_image_2d(
+    &self,
+    target: TextureTarget,
+    pixel_format: TexturePixelFormat,
+    channel_type: Type,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    pixels: &[u8],
+  ) -> Result<(), Error> {
+    let level = 0;
+
+    self
+      .0
+      .tex_sub_image_2d_with_i32_and_i32_and_u32_and_type_and_opt_u8_array(
+        target as _,
+        level,
+        x as _,
+        y as _,
+        w as _,
+        h as _,
+        pixel_format as _,
+        channel_type as _,
+        Some(pixels),
+      )
+      .map_err(|_err| self.error().unwrap_err())
+  }
+
   #[inline]
   fn set_texture_sub_image_3d(
     &self,
@@ -888,16 +1876,57 @@ impl Gl for Context {
   }
 
   #[inline]
-  fn set_texture_wrap(&self, target: TextureTarget, wrap: TextureWrap) {
+  fn set_texture_wrap(&self, target: TextureTarget, axis: TextureWrapAxis, wrap: TextureWrap) {
+    let () = self.0.tex_parameteri(target as _, axis as _, wrap as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_texture_lod_range(&self, target: TextureTarget, min: f32, max: f32) {
+    let () = self
+      .0
+      .tex_parameterf(target as _, WebGl2RenderingContext::TEXTURE_MIN_LOD, min);
+    let () = self
+      .0
+      .tex_parameterf(target as _, WebGl2RenderingContext::TEXTURE_MAX_LOD, max);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  fn max_texture_anisotropy(&self) -> Option<f32> {
+    // NB: Extensions have to be requested before their functionality
+    //     can be used.
+    let Ok(Some(_extension)) = self.0.get_extension("EXT_texture_filter_anisotropic") else {
+      return None
+    };
+
+    let value = self.0.get_parameter(MAX_TEXTURE_MAX_ANISOTROPY).ok()?;
+    value.as_f64().map(|value| value as f32)
+  }
+
+  #[inline]
+  fn set_texture_anisotropy(&self, target: TextureTarget, anisotropy: f32) {
+    let () = self
+      .0
+      .tex_parameterf(target as _, TEXTURE_MAX_ANISOTROPY, anisotropy);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_texture_base_level(&self, target: TextureTarget, level: u32) {
     let () = self.0.tex_parameteri(
       target as _,
-      WebGl2RenderingContext::TEXTURE_WRAP_S,
-      wrap as _,
+      WebGl2RenderingContext::TEXTURE_BASE_LEVEL,
+      level as _,
     );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_texture_max_level(&self, target: TextureTarget, level: u32) {
     let () = self.0.tex_parameteri(
       target as _,
-      WebGl2RenderingContext::TEXTURE_WRAP_T,
-      wrap as _,
+      WebGl2RenderingContext::TEXTURE_MAX_LEVEL,
+      level as _,
     );
     debug_assert_eq!(self.error(), Ok(()));
   }
@@ -907,4 +1936,67 @@ impl Gl for Context {
     let () = self.0.generate_mipmap(target as _);
     debug_assert_eq!(self.error(), Ok(()));
   }
+
+  #[inline]
+  fn create_sampler(&self) -> Result<Sampler, Error> {
+    self
+      .0
+      .create_sampler()
+      .ok_or_else(|| self.error().unwrap_err())
+  }
+
+  #[inline]
+  fn delete_sampler(&self, sampler: &Sampler) {
+    let () = self.0.delete_sampler(Some(sampler));
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn bind_sampler(&self, unit: u32, sampler: Option<&Sampler>) {
+    let () = self.0.bind_sampler(unit, sampler);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_filter(&self, sampler: &Sampler, ty: TextureFilterType, filter: TextureFilter) {
+    let () = self.0.sampler_parameteri(sampler, ty as _, filter as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_wrap(&self, sampler: &Sampler, axis: TextureWrapAxis, wrap: TextureWrap) {
+    let () = self.0.sampler_parameteri(sampler, axis as _, wrap as _);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_lod_range(&self, sampler: &Sampler, min: f32, max: f32) {
+    let () = self
+      .0
+      .sampler_parameterf(sampler, WebGl2RenderingContext::TEXTURE_MIN_LOD, min);
+    let () = self
+      .0
+      .sampler_parameterf(sampler, WebGl2RenderingContext::TEXTURE_MAX_LOD, max);
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_compare_mode(&self, sampler: &Sampler, mode: TextureCompareMode) {
+    let () = self.0.sampler_parameteri(
+      sampler,
+      WebGl2RenderingContext::TEXTURE_COMPARE_MODE,
+      mode as _,
+    );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
+
+  #[inline]
+  fn set_sampler_compare_func(&self, sampler: &Sampler, func: Func) {
+    let () = self.0.sampler_parameteri(
+      sampler,
+      WebGl2RenderingContext::TEXTURE_COMPARE_FUNC,
+      func as _,
+    );
+    debug_assert_eq!(self.error(), Ok(()));
+  }
 }
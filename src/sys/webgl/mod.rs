@@ -6,6 +6,7 @@ mod context;
 
 pub use context::Capability;
 pub use context::ClearMask;
+pub use context::CompressedTextureFormat;
 pub use context::Context;
 pub use context::CullFace;
 pub use context::Factor;
@@ -13,8 +14,10 @@ pub use context::Framebuffer;
 pub use context::FramebufferStatus;
 pub use context::FrontFace;
 pub use context::Func;
+pub use context::GlslType;
 pub use context::Primitive;
 pub use context::Program;
+pub use context::Sampler;
 pub use context::Shader;
 pub use context::ShaderType;
 pub use context::Texture;
@@ -25,6 +28,8 @@ pub use context::TextureInternalFormat;
 pub use context::TexturePixelFormat;
 pub use context::TextureTarget;
 pub use context::TextureWrap;
+pub use context::TextureWrapAxis;
+pub use context::TransformFeedbackMode;
 pub use context::Type;
 pub use context::UniformLocation;
 pub use context::VertexArrayObject;
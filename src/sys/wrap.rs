@@ -0,0 +1,35 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use super::TextureWrapAxis;
+
+
+/// The texture wrap axes, in the order used for storing per-axis wrap
+/// modes.
+pub(crate) const WRAP_AXES: [TextureWrapAxis; 3] =
+  [TextureWrapAxis::S, TextureWrapAxis::T, TextureWrapAxis::R];
+
+
+/// Retrieve the index of the given axis in [`WRAP_AXES`].
+pub(crate) fn wrap_axis_index(axis: TextureWrapAxis) -> usize {
+  match axis {
+    TextureWrapAxis::S => 0,
+    TextureWrapAxis::T => 1,
+    TextureWrapAxis::R => 2,
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that wrap axes and their indices map to each other.
+  #[test]
+  fn wrap_axis_indices() {
+    for (idx, axis) in WRAP_AXES.into_iter().enumerate() {
+      assert_eq!(wrap_axis_index(axis), idx);
+    }
+  }
+}
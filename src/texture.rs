@@ -4,16 +4,28 @@
 use std::iter::once;
 use std::ops::Deref;
 
+use anyhow::anyhow;
 use anyhow::ensure;
 use anyhow::Context as _;
 use anyhow::Result;
 
+use crate::compressed::decompressed_info;
 use crate::sys;
 use crate::sys::Gl as _;
+use crate::CompressedImage;
+
+
+/// The row alignment used for pixel transfers, in bytes.
+const ROW_ALIGNMENT: u32 = 1;
 
 
 /// Information about an image to be used as a texture.
+///
+/// By default, image data is expected to be tightly packed. By setting
+/// a row stride and offsets, a sub-rectangle of a larger image or an
+/// image with padded rows can be uploaded without copying it first.
 #[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
 pub struct TextureInfo {
   /// The texture's width.
   pub width: u32,
@@ -25,6 +37,279 @@ pub struct TextureInfo {
   pub pixel_format: sys::TexturePixelFormat,
   /// The texture's color format/color channel type.
   pub color_format: sys::Type,
+  /// The distance between the starts of two consecutive rows in the
+  /// image data, in bytes, if rows are not tightly packed.
+  pub row_stride: Option<u32>,
+  /// The number of pixels to skip at the start of each row of the image
+  /// data.
+  pub x_offset: u32,
+  /// The number of rows to skip at the start of the image data.
+  pub y_offset: u32,
+}
+
+impl TextureInfo {
+  /// Create a new `TextureInfo` object describing tightly packed image
+  /// data.
+  pub fn new(
+    width: u32,
+    height: u32,
+    intern_format: sys::TextureInternalFormat,
+    pixel_format: sys::TexturePixelFormat,
+    color_format: sys::Type,
+  ) -> Self {
+    Self {
+      width,
+      height,
+      intern_format,
+      pixel_format,
+      color_format,
+      row_stride: None,
+      x_offset: 0,
+      y_offset: 0,
+    }
+  }
+
+  /// Set the distance between the starts of two consecutive rows in the
+  /// image data, in bytes.
+  pub fn with_row_stride(mut self, row_stride: u32) -> Self {
+    self.row_stride = Some(row_stride);
+    self
+  }
+
+  /// Set the number of pixels and rows to skip at the start of each row
+  /// and of the image data, respectively.
+  pub fn with_offset(mut self, x_offset: u32, y_offset: u32) -> Self {
+    self.x_offset = x_offset;
+    self.y_offset = y_offset;
+    self
+  }
+
+  /// Retrieve the size of a single pixel, in bytes.
+  pub fn pixel_size(&self) -> usize {
+    let components = self.components();
+    let size = match self.color_format {
+      sys::Type::Byte | sys::Type::UnsignedByte => 1,
+      sys::Type::HalfFloat | sys::Type::Short | sys::Type::UnsignedShort => 2,
+      sys::Type::Float | sys::Type::Int | sys::Type::UnsignedInt => 4,
+      // Packed types store all components in a single 32 bit word.
+      sys::Type::UnsignedInt10F11F11FRev
+      | sys::Type::UnsignedInt2101010Rev
+      | sys::Type::UnsignedInt248 => return 4,
+    };
+    components * size
+  }
+
+  /// Retrieve the number of components of a single pixel.
+  fn components(&self) -> usize {
+    match self.pixel_format {
+      sys::TexturePixelFormat::Gray
+      | sys::TexturePixelFormat::GrayInteger
+      | sys::TexturePixelFormat::Depth
+      | sys::TexturePixelFormat::DepthStencil => 1,
+      sys::TexturePixelFormat::RG | sys::TexturePixelFormat::RGInteger => 2,
+      sys::TexturePixelFormat::RGB | sys::TexturePixelFormat::RGBInteger => 3,
+      sys::TexturePixelFormat::RGBA | sys::TexturePixelFormat::RGBAInteger => 4,
+    }
+  }
+
+  /// Retrieve the size of a single row of pixels, in bytes, including
+  /// any padding required by the row stride or the row alignment used
+  /// for pixel transfers.
+  pub fn row_size(&self) -> usize {
+    if let Some(stride) = self.row_stride {
+      stride as usize
+    } else {
+      let size = self.width as usize * self.pixel_size();
+      size.next_multiple_of(ROW_ALIGNMENT as usize)
+    }
+  }
+
+  /// Retrieve the size of the image data described, in bytes.
+  pub fn image_size(&self) -> usize {
+    self.row_size() * self.height as usize
+  }
+
+  /// Check whether this image uses the same formats as `other`.
+  fn has_format_of(&self, other: &Self) -> bool {
+    self.intern_format as u32 == other.intern_format as u32
+      && self.pixel_format as u32 == other.pixel_format as u32
+      && self.color_format as u32 == other.color_format as u32
+  }
+
+  /// Check whether the internal format can be specified with the
+  /// given pixel format and color format.
+  fn is_valid_format(&self) -> bool {
+    use sys::TextureInternalFormat as I;
+    use sys::TexturePixelFormat as P;
+    use sys::Type as T;
+
+    matches!(
+      (self.intern_format, self.pixel_format, self.color_format),
+      (I::Gray8, P::Gray, T::UnsignedByte)
+        | (I::RG8, P::RG, T::UnsignedByte)
+        | (I::RGB8 | I::SRGB8, P::RGB, T::UnsignedByte)
+        | (I::RGBA8 | I::SRGBA8, P::RGBA, T::UnsignedByte)
+        | (I::R16F, P::Gray, T::HalfFloat | T::Float)
+        | (I::RG16F, P::RG, T::HalfFloat | T::Float)
+        | (I::RGB16F, P::RGB, T::HalfFloat | T::Float)
+        | (I::RGBA16F, P::RGBA, T::HalfFloat | T::Float)
+        | (I::R32F, P::Gray, T::Float)
+        | (I::RG32F, P::RG, T::Float)
+        | (I::RGB32F, P::RGB, T::Float)
+        | (I::RGBA32F, P::RGBA, T::Float)
+        | (
+          I::R11G11B10F,
+          P::RGB,
+          T::UnsignedInt10F11F11FRev | T::HalfFloat | T::Float
+        )
+        | (I::RGB10A2, P::RGBA, T::UnsignedInt2101010Rev)
+        | (I::R8I, P::GrayInteger, T::Byte)
+        | (I::R8UI, P::GrayInteger, T::UnsignedByte)
+        | (I::R16I, P::GrayInteger, T::Short)
+        | (I::R16UI, P::GrayInteger, T::UnsignedShort)
+        | (I::R32I, P::GrayInteger, T::Int)
+        | (I::R32UI, P::GrayInteger, T::UnsignedInt)
+        | (I::RG8I, P::RGInteger, T::Byte)
+        | (I::RG8UI, P::RGInteger, T::UnsignedByte)
+        | (I::RG16I, P::RGInteger, T::Short)
+        | (I::RG16UI, P::RGInteger, T::UnsignedShort)
+        | (I::RG32I, P::RGInteger, T::Int)
+        | (I::RG32UI, P::RGInteger, T::UnsignedInt)
+        | (I::RGB8I, P::RGBInteger, T::Byte)
+        | (I::RGB8UI, P::RGBInteger, T::UnsignedByte)
+        | (I::RGB16I, P::RGBInteger, T::Short)
+        | (I::RGB16UI, P::RGBInteger, T::UnsignedShort)
+        | (I::RGB32I, P::RGBInteger, T::Int)
+        | (I::RGB32UI, P::RGBInteger, T::UnsignedInt)
+        | (I::RGBA8I, P::RGBAInteger, T::Byte)
+        | (I::RGBA8UI, P::RGBAInteger, T::UnsignedByte)
+        | (I::RGBA16I, P::RGBAInteger, T::Short)
+        | (I::RGBA16UI, P::RGBAInteger, T::UnsignedShort)
+        | (I::RGBA32I, P::RGBAInteger, T::Int)
+        | (I::RGBA32UI, P::RGBAInteger, T::UnsignedInt)
+        | (I::Depth16, P::Depth, T::UnsignedShort | T::UnsignedInt)
+        | (I::Depth24, P::Depth, T::UnsignedInt)
+        | (I::Depth32F, P::Depth, T::Float)
+        | (I::Depth24Stencil8, P::DepthStencil, T::UnsignedInt248)
+    )
+  }
+
+  /// Check that the described format combination is valid.
+  pub(crate) fn validate(&self) -> Result<()> {
+    ensure!(
+      self.is_valid_format(),
+      "internal format {:?} cannot be specified with pixel format {:?} and color format {:?}",
+      self.intern_format,
+      self.pixel_format,
+      self.color_format
+    );
+    Ok(())
+  }
+
+  /// Check whether the described image data is tightly packed.
+  fn is_packed(&self) -> bool {
+    self.row_stride.is_none() && self.x_offset == 0 && self.y_offset == 0
+  }
+
+  /// Retrieve a copy of this object describing tightly packed image
+  /// data.
+  pub(crate) fn packed(&self) -> Self {
+    Self {
+      row_stride: None,
+      x_offset: 0,
+      y_offset: 0,
+      ..*self
+    }
+  }
+
+  /// Determine the unpack alignment and row length (in pixels) to use
+  /// for uploading the described image data.
+  fn unpack_layout(&self) -> Result<(u32, u32)> {
+    let Some(stride) = self.row_stride else {
+      return Ok((ROW_ALIGNMENT, 0))
+    };
+
+    let stride = stride as usize;
+    let pixel_size = self.pixel_size();
+    let row = self.width as usize * pixel_size;
+    ensure!(
+      stride >= row,
+      "row stride of {stride} bytes is smaller than {} pixel row of {row} bytes",
+      self.width
+    );
+
+    // GL only supports row lengths in full pixels, so any padding at
+    // the end of a row has to be expressed via the alignment.
+    let length = stride / pixel_size;
+    [1, 2, 4, 8]
+      .into_iter()
+      .find(|alignment| {
+        stride.is_multiple_of(*alignment)
+          && (length * pixel_size).next_multiple_of(*alignment) == stride
+      })
+      .map(|alignment| (alignment as u32, length as u32))
+      .ok_or_else(|| {
+        anyhow!("row stride of {stride} bytes cannot be expressed for pixels of {pixel_size} bytes")
+      })
+  }
+
+  /// Check that the described format combination is valid and that
+  /// `data` contains `depth` images of the described size.
+  ///
+  /// Tightly packed data has to match the expected size exactly,
+  /// whereas data with a row stride or offsets merely has to cover the
+  /// described region.
+  pub(crate) fn validate_data(&self, data: &[u8], depth: u32) -> Result<()> {
+    let () = self.validate()?;
+    let _layout = self.unpack_layout()?;
+
+    if self.is_packed() {
+      let size = self.image_size() * depth as usize;
+      ensure!(
+        data.len() == size,
+        "image data of {} bytes does not match {}x{}x{depth} image of {size} bytes \
+         ({} bytes per pixel, {} bytes per row)",
+        data.len(),
+        self.width,
+        self.height,
+        self.pixel_size(),
+        self.row_size()
+      );
+    } else {
+      let pixel_size = self.pixel_size();
+      let row_size = self.row_size();
+      let rows = self.height as usize * depth as usize;
+      let size = if rows == 0 || self.width == 0 {
+        0
+      } else {
+        self.y_offset as usize * row_size
+          + self.x_offset as usize * pixel_size
+          + (rows - 1) * row_size
+          + self.width as usize * pixel_size
+      };
+      ensure!(
+        data.len() >= size,
+        "image data of {} bytes is too small for {}x{}x{depth} image at offset ({}, {}) \
+         with row stride of {row_size} bytes, requiring {size} bytes",
+        data.len(),
+        self.width,
+        self.height,
+        self.x_offset,
+        self.y_offset,
+      );
+    }
+    Ok(())
+  }
+
+  /// Configure the pixel unpack state for uploading the described image
+  /// data.
+  fn apply_unpack_state(&self, context: &sys::Context) -> Result<()> {
+    let (alignment, length) = self.unpack_layout()?;
+    let () = context.set_pixel_unpack_alignment(alignment);
+    let () = context.set_pixel_unpack_row_length(length);
+    let () = context.set_pixel_unpack_skip(self.x_offset, self.y_offset);
+    Ok(())
+  }
 }
 
 impl AsRef<Self> for TextureInfo {
@@ -42,6 +327,8 @@ fn populate_texture(
   data: &[u8],
   info: &TextureInfo,
 ) -> Result<()> {
+  let () = info.validate_data(data, 1)?;
+  let () = info.apply_unpack_state(context)?;
   let () = context
     .set_texture_image_2d(
       target,
@@ -64,6 +351,9 @@ fn populate_3d_texture(
   data: &[u8],
   info: &TextureInfo,
 ) -> Result<()> {
+  let () = info.validate_data(data, 1)?;
+  let () = info.apply_unpack_state(context)?;
+
   let x = 0;
   let y = 0;
   let z = idx;
@@ -79,26 +369,125 @@ fn populate_3d_texture(
       info.height,
       data,
     )
-    .with_context(|| "failed to initialize 3D texture (index = {idx})")?;
+    .with_context(|| format!("failed to initialize 3D texture (index = {idx})"))?;
   Ok(())
 }
 
+/// Determine the pixel and color format to read back a texture of the
+/// given format with.
+///
+/// WebGL can only read normalized color buffers as RGBA bytes, integer
+/// ones as RGBA 32 bit integers, and floating point ones as RGBA
+/// floats.
+#[cfg(target_arch = "wasm32")]
+fn read_format(info: &TextureInfo) -> Option<(sys::TexturePixelFormat, sys::Type)> {
+  use sys::TextureInternalFormat as I;
+  use sys::TexturePixelFormat as P;
+  use sys::Type as T;
+
+  let integer = matches!(
+    info.pixel_format,
+    P::GrayInteger | P::RGInteger | P::RGBInteger | P::RGBAInteger
+  );
+  let format = match (info.intern_format, info.color_format) {
+    (I::Gray8 | I::RG8 | I::RGB8 | I::SRGB8 | I::RGBA8 | I::SRGBA8, T::UnsignedByte) => {
+      (P::RGBA, T::UnsignedByte)
+    },
+    (
+      I::R16F
+      | I::RG16F
+      | I::RGB16F
+      | I::RGBA16F
+      | I::R32F
+      | I::RG32F
+      | I::RGB32F
+      | I::RGBA32F
+      | I::R11G11B10F,
+      T::Float,
+    ) => (P::RGBA, T::Float),
+    (_, T::Byte | T::Short | T::Int) if integer => (P::RGBAInteger, T::Int),
+    (_, T::UnsignedByte | T::UnsignedShort | T::UnsignedInt) if integer => {
+      (P::RGBAInteger, T::UnsignedInt)
+    },
+    _ => return None,
+  };
+  Some(format)
+}
+
+/// Repack pixels read back in the format described by `src_info` into
+/// the (narrower) format described by `dst_info`.
+#[cfg(target_arch = "wasm32")]
+fn repack(src_info: &TextureInfo, src: &[u8], dst_info: &TextureInfo, dst: &mut [u8]) {
+  let src_size = src_info.pixel_size() / src_info.components();
+  let dst_size = dst_info.pixel_size() / dst_info.components();
+
+  for (src_row, dst_row) in src
+    .chunks(src_info.row_size())
+    .zip(dst.chunks_mut(dst_info.row_size()))
+  {
+    for (src_pixel, dst_pixel) in src_row
+      .chunks_exact(src_info.pixel_size())
+      .zip(dst_row.chunks_exact_mut(dst_info.pixel_size()))
+    {
+      for (src, dst) in src_pixel
+        .chunks_exact(src_size)
+        .zip(dst_pixel.chunks_exact_mut(dst_size))
+      {
+        // WebGL is little endian, so narrowing an integer boils down to
+        // dropping its upper bytes.
+        let () = dst.copy_from_slice(&src[..dst_size]);
+      }
+    }
+  }
+}
+
 
 /// Builder infrastructure for a texture.
 #[derive(Debug)]
 pub struct Builder<C = ()> {
   /// The GL context.
   context: C,
-  /// The texture wrap setting to use.
-  wrap: sys::TextureWrap,
+  /// The wrap modes along the S, T, and R axes.
+  wrap: [sys::TextureWrap; 3],
   /// Whether or not to create mipmaps.
   mipmaps: bool,
+  /// The filter to use when minimizing, if explicitly set.
+  min_filter: Option<sys::TextureFilter>,
+  /// The filter to use when magnifying.
+  mag_filter: sys::TextureFilter,
+  /// The requested degree of anisotropic filtering, if any.
+  anisotropy: Option<f32>,
+  /// The minimum and maximum level of detail.
+  lod_range: (f32, f32),
+  /// The base and maximum mipmap level, if explicitly set.
+  level_range: Option<(u32, u32)>,
+  /// The bias added to the computed level of detail.
+  #[cfg(not(target_arch = "wasm32"))]
+  lod_bias: f32,
+  /// The color used for texels outside of the texture.
+  #[cfg(not(target_arch = "wasm32"))]
+  border_color: [f32; 4],
+  /// The components that the red, green, blue, and alpha components
+  /// are read from, if explicitly set.
+  #[cfg(not(target_arch = "wasm32"))]
+  swizzle: Option<[sys::TextureSwizzle; 4]>,
+  /// Whether to enable seamless cube map filtering when creating cube
+  /// maps.
+  #[cfg(not(target_arch = "wasm32"))]
+  seamless_cube_maps: bool,
 }
 
 impl Builder<()> {
-  /// Set the wrap mode for the to-be-created texture.
+  /// Set the wrap mode along all axes for the to-be-created texture.
   pub fn set_wrap_mode(mut self, wrap: sys::TextureWrap) -> Self {
-    self.wrap = wrap;
+    self.wrap = [wrap; 3];
+    self
+  }
+
+  /// Set the wrap mode along a single axis for the to-be-created
+  /// texture.
+  pub fn set_axis_wrap_mode(mut self, axis: sys::TextureWrapAxis, wrap: sys::TextureWrap) -> Self {
+    self.wrap[sys::wrap_axis_index(axis)] = wrap;
     self
   }
 
@@ -108,23 +497,149 @@ impl Builder<()> {
     self
   }
 
+  /// Set the filter to use when minimizing the texture.
+  ///
+  /// If not set, [`LinearMipmapLinear`][sys::TextureFilter::LinearMipmapLinear]
+  /// is used for textures with mipmaps and
+  /// [`Linear`][sys::TextureFilter::Linear] otherwise.
+  pub fn set_min_filter(mut self, filter: sys::TextureFilter) -> Self {
+    self.min_filter = Some(filter);
+    self
+  }
+
+  /// Set the filter to use when magnifying the texture.
+  ///
+  /// Only [`Linear`][sys::TextureFilter::Linear] and
+  /// [`Nearest`][sys::TextureFilter::Nearest] are valid magnification
+  /// filters.
+  pub fn set_mag_filter(mut self, filter: sys::TextureFilter) -> Self {
+    self.mag_filter = filter;
+    self
+  }
+
+  /// Set the degree of anisotropic filtering to use.
+  ///
+  /// The value is clamped to the maximum supported by the context. It
+  /// is ignored if the context does not support anisotropic filtering.
+  pub fn set_anisotropy(mut self, anisotropy: f32) -> Self {
+    self.anisotropy = Some(anisotropy);
+    self
+  }
+
+  /// Set the range of mipmap levels of detail that can be selected.
+  pub fn set_lod_range(mut self, min: f32, max: f32) -> Self {
+    self.lod_range = (min, max);
+    self
+  }
+
+  /// Set the indices of the lowest and highest mipmap levels that are
+  /// accessed.
+  pub fn set_level_range(mut self, base: u32, max: u32) -> Self {
+    self.level_range = Some((base, max));
+    self
+  }
+
+  /// Set the bias added to the level of detail computed when sampling
+  /// the texture.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn set_lod_bias(mut self, bias: f32) -> Self {
+    self.lod_bias = bias;
+    self
+  }
+
+  /// Set the color used for texels outside of the texture when using
+  /// [`ClampToBorder`][sys::TextureWrap::ClampToBorder] wrapping.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn set_border_color(mut self, color: [f32; 4]) -> Self {
+    self.border_color = color;
+    self
+  }
+
+  /// Set the components that the red, green, blue, and alpha
+  /// components of the texture are read from.
+  ///
+  /// This can be used to sample single channel textures as gray scale
+  /// or as alpha masks, for example.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn set_swizzle(mut self, swizzle: [sys::TextureSwizzle; 4]) -> Self {
+    self.swizzle = Some(swizzle);
+    self
+  }
+
+  /// Set whether to enable seamless filtering across cube map faces
+  /// when creating cube maps.
+  ///
+  /// Seamless filtering is global context state on OpenGL: enabling it
+  /// affects all cube maps sampled with the context and it is not
+  /// disabled again when the texture is dropped. Alternatively, enable
+  /// [`TextureCubeMapSeamless`][sys::Capability::TextureCubeMapSeamless]
+  /// once when setting up the context. WebGL always filters cube maps
+  /// seamlessly.
+  #[cfg(not(target_arch = "wasm32"))]
+  pub fn set_seamless_cube_maps(mut self, seamless: bool) -> Self {
+    self.seamless_cube_maps = seamless;
+    self
+  }
+
   /// Set the texture's GL context.
   pub fn set_context(self, context: &sys::Context) -> Builder<sys::Context> {
     let Self {
       context: (),
       wrap,
       mipmaps,
+      min_filter,
+      mag_filter,
+      anisotropy,
+      lod_range,
+      level_range,
+      #[cfg(not(target_arch = "wasm32"))]
+      lod_bias,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color,
+      #[cfg(not(target_arch = "wasm32"))]
+      swizzle,
+      #[cfg(not(target_arch = "wasm32"))]
+      seamless_cube_maps,
     } = self;
 
     Builder {
       context: context.clone(),
       wrap,
       mipmaps,
+      min_filter,
+      mag_filter,
+      anisotropy,
+      lod_range,
+      level_range,
+      #[cfg(not(target_arch = "wasm32"))]
+      lod_bias,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color,
+      #[cfg(not(target_arch = "wasm32"))]
+      swizzle,
+      #[cfg(not(target_arch = "wasm32"))]
+      seamless_cube_maps,
     }
   }
 }
 
 impl Builder<sys::Context> {
+  /// Check that the builder's settings are valid.
+  ///
+  /// This check has to happen before any GL objects are created, so
+  /// that no state needs to be cleaned up on error.
+  fn validate(&self) -> Result<()> {
+    ensure!(
+      matches!(
+        self.mag_filter,
+        sys::TextureFilter::Linear | sys::TextureFilter::Nearest
+      ),
+      "{:?} is not a valid magnification filter",
+      self.mag_filter
+    );
+    Ok(())
+  }
+
   /// Apply a certain texture state to the currently bound texture
   /// before it is being populated.
   ///
@@ -134,39 +649,64 @@ impl Builder<sys::Context> {
   /// set and restore.
   fn apply_pre_texture_state(&self, texture: &Texture) {
     let Self {
-      context: _,
+      context,
       wrap,
-      mipmaps,
+      mipmaps: _,
+      min_filter,
+      mag_filter,
+      anisotropy,
+      lod_range: (min_lod, max_lod),
+      level_range,
+      #[cfg(not(target_arch = "wasm32"))]
+      lod_bias,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color,
+      #[cfg(not(target_arch = "wasm32"))]
+      swizzle,
+      #[cfg(not(target_arch = "wasm32"))]
+        seamless_cube_maps: _,
     } = self;
+
     let target = texture.target();
-    let () = self.context.set_texture_filter(
-      target,
-      sys::TextureFilterType::Magnify,
-      sys::TextureFilter::Linear,
-    );
+    let () = context.set_texture_filter(target, sys::TextureFilterType::Magnify, *mag_filter);
 
-    let filter = if *mipmaps {
+    let filter = min_filter.unwrap_or(if texture.levels > 1 {
       sys::TextureFilter::LinearMipmapLinear
     } else {
       sys::TextureFilter::Linear
-    };
-    let () = self
-      .context
-      .set_texture_filter(target, sys::TextureFilterType::Minimize, filter);
-    let () = self.context.set_texture_wrap(target, *wrap);
+    });
+    let () = context.set_texture_filter(target, sys::TextureFilterType::Minimize, filter);
+    let () = sys::WRAP_AXES
+      .into_iter()
+      .zip(wrap)
+      .for_each(|(axis, wrap)| context.set_texture_wrap(target, axis, *wrap));
+    let () = context.set_texture_lod_range(target, *min_lod, *max_lod);
+
+    if let Some(anisotropy) = anisotropy {
+      if let Some(max) = context.max_texture_anisotropy() {
+        let () = context.set_texture_anisotropy(target, anisotropy.clamp(1.0, max));
+      }
+    }
+
+    if let Some((base, max)) = level_range {
+      let () = context.set_texture_base_level(target, *base);
+      let () = context.set_texture_max_level(target, *max);
+    }
 
-    // TODO: Probably not the full story.
-    let () = self.context.set_pixel_unpack_alignment(1);
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+      let () = context.set_texture_lod_bias(target, *lod_bias);
+      let () = context.set_texture_border_color(target, *border_color);
+      if let Some(swizzle) = swizzle {
+        let () = context.set_texture_swizzle(target, *swizzle);
+      }
+    }
   }
 
   /// Apply a certain texture state to the currently bound texture
   /// after it has been populated.
   fn apply_post_texture_state(&self, texture: &Texture) {
-    let Self {
-      context: _,
-      wrap: _,
-      mipmaps,
-    } = self;
+    let Self { mipmaps, .. } = self;
     let target = texture.target();
 
     if *mipmaps {
@@ -174,9 +714,35 @@ impl Builder<sys::Context> {
     }
   }
 
-  /// Create a new 2D `Texture` suitable for use as a depth map.
-  pub fn new_depth_map(&self, width: u32, height: u32) -> Result<Texture> {
-    let target = sys::TextureTarget::Texture2D;
+  /// Retrieve the number of mipmap levels of a texture of the given
+  /// size.
+  fn levels(&self, width: u32, height: u32) -> u32 {
+    if self.mipmaps {
+      width.max(height).max(1).ilog2() + 1
+    } else {
+      1
+    }
+  }
+
+  /// Create a new `Texture` suitable for use as a depth map, with
+  /// images of the given size specified for each of `images`.
+  fn new_depth_texture(
+    &self,
+    target: sys::TextureTarget,
+    images: &[sys::TextureTarget],
+    width: u32,
+    height: u32,
+  ) -> Result<Texture> {
+    let () = self.validate()?;
+    let info = TextureInfo::new(
+      width,
+      height,
+      sys::TextureInternalFormat::Depth,
+      sys::TexturePixelFormat::Depth,
+      sys::Type::Float,
+    );
+    let () = info.validate()?;
+
     let texture = Texture {
       context: self.context.clone(),
       texture: self
@@ -184,6 +750,10 @@ impl Builder<sys::Context> {
         .create_texture()
         .context("failed to generate texture ID")?,
       target,
+      info,
+      layers: images.len() as _,
+      levels: self.levels(width, height),
+      compressed: None,
     };
 
     let () = texture.bind();
@@ -198,17 +768,19 @@ impl Builder<sys::Context> {
       .context
       .set_texture_compare_func(target, sys::Func::Greater);
 
-    let result = self
-      .context
-      .set_texture_image_2d(
-        target,
-        sys::TextureInternalFormat::Depth,
-        sys::TexturePixelFormat::Depth,
-        sys::Type::Float,
-        width,
-        height,
-        None,
-      )
+    let result = images
+      .iter()
+      .try_for_each(|image| {
+        self.context.set_texture_image_2d(
+          *image,
+          info.intern_format,
+          info.pixel_format,
+          info.color_format,
+          width,
+          height,
+          None,
+        )
+      })
       .context("failed to depth map texture");
 
     if let Ok(()) = result {
@@ -220,8 +792,41 @@ impl Builder<sys::Context> {
     Ok(texture)
   }
 
+  /// Enable seamless cube map filtering, if requested.
+  #[inline]
+  fn enable_seamless_cube_maps(&self) {
+    #[cfg(not(target_arch = "wasm32"))]
+    if self.seamless_cube_maps {
+      let () = self.context.enable(sys::Capability::TextureCubeMapSeamless);
+    }
+  }
+
+  /// Create a new 2D `Texture` suitable for use as a depth map.
+  pub fn new_depth_map(&self, width: u32, height: u32) -> Result<Texture> {
+    let target = sys::TextureTarget::Texture2D;
+    self.new_depth_texture(target, &[target], width, height)
+  }
+
+  /// Create a new cube map `Texture` suitable for use as a depth map,
+  /// e.g., for point light shadows.
+  ///
+  /// Individual faces can be rendered to using
+  /// [`Framebuffer::with_cube_face`][crate::Framebuffer::with_cube_face].
+  pub fn new_depth_cube_map(&self, size: u32) -> Result<Texture> {
+    let () = self.enable_seamless_cube_maps();
+
+    self.new_depth_texture(
+      sys::TextureTarget::CubeMap,
+      &sys::TextureTarget::CUBE_MAP_FACES,
+      size,
+      size,
+    )
+  }
+
   /// Create a new 2D `Texture` from the provided image data.
   pub fn from_image(&self, data: &[u8], info: &TextureInfo) -> Result<Texture> {
+    let () = self.validate()?;
+    let () = info.validate_data(data, 1)?;
     let target = sys::TextureTarget::Texture2D;
 
     let texture = Texture {
@@ -231,6 +836,10 @@ impl Builder<sys::Context> {
         .create_texture()
         .context("failed to generate texture ID")?,
       target,
+      info: info.packed(),
+      layers: 1,
+      levels: self.levels(info.width, info.height),
+      compressed: None,
     };
     let () = texture.bind();
     let () = self.apply_pre_texture_state(&texture);
@@ -244,19 +853,117 @@ impl Builder<sys::Context> {
     Ok(texture)
   }
 
-  /// Create a new 3D `Texture` using the provided images.
-  pub fn from_images<I, D, M>(&self, width: u32, height: u32, mut images: I) -> Result<Texture>
+  /// Create a new cube map `Texture` from the provided face images.
+  ///
+  /// Faces are expected in the order positive X, negative X, positive
+  /// Y, negative Y, positive Z, and negative Z (see
+  /// [`sys::TextureTarget::CUBE_MAP_FACES`]). All faces have to be
+  /// square and of the same size and format.
+  ///
+  /// See [`Builder::set_seamless_cube_maps`] for enabling seamless
+  /// filtering across faces on OpenGL.
+  pub fn from_cube_faces<D, M>(&self, faces: [(D, M); 6]) -> Result<Texture>
+  where
+    D: AsRef<[u8]>,
+    M: AsRef<TextureInfo>,
+  {
+    let () = self.validate()?;
+    let info = faces[0].1.as_ref().packed();
+    ensure!(
+      info.width == info.height,
+      "cube map faces have to be square, but are {}x{}",
+      info.width,
+      info.height
+    );
+    let () = faces
+      .iter()
+      .enumerate()
+      .try_for_each(|(idx, (data, face))| {
+        let face = face.as_ref();
+        ensure!(
+          face.width == info.width && face.height == info.height,
+          "cube map face {idx} is of size {}x{}, but expected {}x{}",
+          face.width,
+          face.height,
+          info.width,
+          info.height
+        );
+        ensure!(
+          face.has_format_of(&info),
+          "cube map face {idx} is of format {:?}/{:?}/{:?}, but expected {:?}/{:?}/{:?}",
+          face.intern_format,
+          face.pixel_format,
+          face.color_format,
+          info.intern_format,
+          info.pixel_format,
+          info.color_format
+        );
+        face
+          .validate_data(data.as_ref(), 1)
+          .with_context(|| format!("cube map face {idx} is invalid"))
+      })?;
+
+    let target = sys::TextureTarget::CubeMap;
+    let texture = Texture {
+      context: self.context.clone(),
+      texture: self
+        .context
+        .create_texture()
+        .context("failed to generate texture ID")?,
+      target,
+      info,
+      layers: 6,
+      levels: self.levels(info.width, info.height),
+      compressed: None,
+    };
+
+    let () = self.enable_seamless_cube_maps();
+    let () = texture.bind();
+    let () = self.apply_pre_texture_state(&texture);
+    let result = sys::TextureTarget::CUBE_MAP_FACES
+      .into_iter()
+      .zip(&faces)
+      .enumerate()
+      .try_for_each(|(idx, (face, (data, info)))| {
+        populate_texture(&self.context, face, data.as_ref(), info.as_ref())
+          .with_context(|| format!("failed to populate cube map face {idx}"))
+      });
+    if let Ok(()) = result {
+      let () = self.apply_post_texture_state(&texture);
+    }
+    let () = texture.unbind();
+
+    let () = result?;
+    Ok(texture)
+  }
+
+  /// Create a new 2D array or 3D `Texture` using the provided images
+  /// as layers.
+  fn create_layered<I, D, M>(
+    &self,
+    target: sys::TextureTarget,
+    width: u32,
+    height: u32,
+    mut images: I,
+  ) -> Result<Texture>
   where
     I: ExactSizeIterator<Item = Result<(D, M)>>,
     D: AsRef<[u8]>,
     M: AsRef<TextureInfo>,
   {
+    let () = self.validate()?;
     let count = images.len();
     let (image, info) = images
       .next()
       .expect("3D texture creation requires at least one image")?;
-    let target = sys::TextureTarget::Texture2DArray;
 
+    let () = info.as_ref().validate()?;
+
+    let levels = if let sys::TextureTarget::Texture3D = target {
+      self.levels(width.max(count as _), height)
+    } else {
+      self.levels(width, height)
+    };
     let texture = Texture {
       context: self.context.clone(),
       texture: self
@@ -264,6 +971,14 @@ impl Builder<sys::Context> {
         .create_texture()
         .context("failed to generate texture ID")?,
       target,
+      info: TextureInfo {
+        width,
+        height,
+        ..info.as_ref().packed()
+      },
+      layers: count as _,
+      levels,
+      compressed: None,
     };
     let () = texture.bind();
     let () = self.apply_pre_texture_state(&texture);
@@ -287,13 +1002,19 @@ impl Builder<sys::Context> {
         .try_for_each(|(idx, result)| {
           let (image, info) = result?;
           let image = image.as_ref();
-          let info = info.as_ref();
+          // Images are uploaded into storage of the texture's internal
+          // format.
+          let info = TextureInfo {
+            intern_format: texture.info.intern_format,
+            ..*info.as_ref()
+          };
           ensure!(
             info.width <= width && info.height <= height,
             "image {idx} is larger than maximum bounds provided"
           );
 
-          let () = populate_3d_texture(&self.context, idx as _, target, image, info)?;
+          let () = populate_3d_texture(&self.context, idx as _, target, image, &info)
+            .with_context(|| format!("failed to populate image {idx}"))?;
           Ok(())
         })
     });
@@ -305,6 +1026,158 @@ impl Builder<sys::Context> {
 
     Ok(texture)
   }
+
+  /// Create a new 2D array `Texture` using the provided images as
+  /// layers.
+  ///
+  /// Images may be smaller than `width` x `height`, in which case they
+  /// are placed at the layer's origin.
+  pub fn from_images<I, D, M>(&self, width: u32, height: u32, images: I) -> Result<Texture>
+  where
+    I: ExactSizeIterator<Item = Result<(D, M)>>,
+    D: AsRef<[u8]>,
+    M: AsRef<TextureInfo>,
+  {
+    self.create_layered(sys::TextureTarget::Texture2DArray, width, height, images)
+  }
+
+  /// Create a new 3D (volume) `Texture` using the provided images as
+  /// slices along the third axis.
+  ///
+  /// As opposed to 2D array textures, 3D textures are filtered across
+  /// slices. Slices may be smaller than `width` x `height`, in which
+  /// case they are placed at the slice's origin.
+  pub fn from_slices<I, D, M>(&self, width: u32, height: u32, slices: I) -> Result<Texture>
+  where
+    I: ExactSizeIterator<Item = Result<(D, M)>>,
+    D: AsRef<[u8]>,
+    M: AsRef<TextureInfo>,
+  {
+    self.create_layered(sys::TextureTarget::Texture3D, width, height, slices)
+  }
+
+  /// Create a new 3D (volume) `Texture` of the given `depth` from
+  /// contiguous image data.
+  ///
+  /// `data` contains `depth` slices of the size described by `info`
+  /// back-to-back.
+  pub fn from_volume(&self, data: &[u8], info: &TextureInfo, depth: u32) -> Result<Texture> {
+    let () = self.validate()?;
+    let () = info.validate_data(data, depth)?;
+
+    let target = sys::TextureTarget::Texture3D;
+    let texture = Texture {
+      context: self.context.clone(),
+      texture: self
+        .context
+        .create_texture()
+        .context("failed to generate texture ID")?,
+      target,
+      info: info.packed(),
+      layers: depth,
+      levels: self.levels(info.width.max(depth), info.height),
+      compressed: None,
+    };
+    let () = texture.bind();
+    let () = self.apply_pre_texture_state(&texture);
+    let result = info.apply_unpack_state(&self.context).and_then(|()| {
+      self
+        .context
+        .set_texture_image_3d(
+          target,
+          info.intern_format,
+          info.pixel_format,
+          info.color_format,
+          info.width,
+          info.height,
+          depth,
+          Some(data),
+        )
+        .context("failed to populate 3D texture")
+    });
+    if let Ok(()) = result {
+      let () = self.apply_post_texture_state(&texture);
+    }
+    let () = texture.unbind();
+
+    let () = result?;
+    Ok(texture)
+  }
+
+  /// Create a new 2D or 2D array `Texture` from a compressed image.
+  ///
+  /// The first of `images` whose format is supported by the context
+  /// is used, allowing for the same image to be provided in multiple
+  /// formats. Mipmaps are taken from the image as-is, meaning that the
+  /// builder's mipmap setting has no effect.
+  pub fn from_compressed(&self, images: &[CompressedImage]) -> Result<Texture> {
+    let () = self.validate()?;
+    let image = images
+      .iter()
+      .find(|image| self.context.is_compressed_format_supported(image.format()))
+      .with_context(|| {
+        let formats = images
+          .iter()
+          .map(CompressedImage::format)
+          .collect::<Vec<_>>();
+        format!("none of the compressed texture formats {formats:?} is supported")
+      })?;
+
+    let format = image.format();
+    let target = if image.layers() > 1 {
+      sys::TextureTarget::Texture2DArray
+    } else {
+      sys::TextureTarget::Texture2D
+    };
+    let levels = image.levels().len() as u32;
+    let texture = Texture {
+      context: self.context.clone(),
+      texture: self
+        .context
+        .create_texture()
+        .context("failed to generate texture ID")?,
+      target,
+      info: decompressed_info(format, image.width(), image.height()),
+      layers: image.layers(),
+      levels,
+      compressed: Some(format),
+    };
+    let () = texture.bind();
+    // The image may not contain the full mipmap chain, in which case
+    // we have to limit access to the levels present. An explicitly
+    // configured level range takes precedence, though.
+    let () = self.context.set_texture_max_level(target, levels - 1);
+    let () = self.apply_pre_texture_state(&texture);
+
+    let result = image
+      .levels()
+      .iter()
+      .enumerate()
+      .try_for_each(|(level, data)| {
+        let width = (image.width() >> level).max(1);
+        let height = (image.height() >> level).max(1);
+        if let sys::TextureTarget::Texture2DArray = target {
+          self.context.set_compressed_texture_image_3d(
+            target,
+            level as _,
+            format,
+            width,
+            height,
+            image.layers(),
+            data,
+          )
+        } else {
+          self
+            .context
+            .set_compressed_texture_image_2d(target, level as _, format, width, height, data)
+        }
+        .with_context(|| format!("failed to populate compressed texture level {level}"))
+      });
+    let () = texture.unbind();
+
+    let () = result?;
+    Ok(texture)
+  }
 }
 
 /// Instantiate a "default" [`Builder`].
@@ -312,13 +1185,36 @@ impl Builder<sys::Context> {
 /// The defaults are as follows:
 /// - wrap mode: [`Repeat`][sys::TextureWrap::Repeat]
 /// - mipmaps: `false`
+/// - minification filter: [`LinearMipmapLinear`][sys::TextureFilter::LinearMipmapLinear]
+///   with mipmaps, [`Linear`][sys::TextureFilter::Linear] otherwise
+/// - magnification filter: [`Linear`][sys::TextureFilter::Linear]
+/// - anisotropy: disabled
+/// - level of detail range: `-1000.0` to `1000.0`
+/// - level range: all levels
+/// - level of detail bias (OpenGL only): `0.0`
+/// - border color (OpenGL only): transparent black
+/// - swizzle (OpenGL only): identity
+/// - seamless cube map filtering (OpenGL only): `false`
 impl Default for Builder<()> {
   #[inline]
   fn default() -> Self {
     Self {
       context: (),
-      wrap: sys::TextureWrap::Repeat,
+      wrap: [sys::TextureWrap::Repeat; 3],
       mipmaps: false,
+      min_filter: None,
+      mag_filter: sys::TextureFilter::Linear,
+      anisotropy: None,
+      lod_range: (-1000.0, 1000.0),
+      level_range: None,
+      #[cfg(not(target_arch = "wasm32"))]
+      lod_bias: 0.0,
+      #[cfg(not(target_arch = "wasm32"))]
+      border_color: [0.0; 4],
+      #[cfg(not(target_arch = "wasm32"))]
+      swizzle: None,
+      #[cfg(not(target_arch = "wasm32"))]
+      seamless_cube_maps: false,
     }
   }
 }
@@ -333,6 +1229,15 @@ pub struct Texture {
   texture: sys::Texture,
   /// The "target" to bind to.
   target: sys::TextureTarget,
+  /// Information about the texture's base level.
+  info: TextureInfo,
+  /// The number of layers (`1` for non-array textures, `6` for cube
+  /// maps, and the depth for 3D textures).
+  layers: u32,
+  /// The number of mipmap levels.
+  levels: u32,
+  /// The compressed format, if the texture is compressed.
+  compressed: Option<sys::CompressedTextureFormat>,
 }
 
 impl Texture {
@@ -357,6 +1262,301 @@ impl Texture {
   pub fn target(&self) -> sys::TextureTarget {
     self.target
   }
+
+  /// Retrieve information about the texture's base level.
+  ///
+  /// For compressed textures, the information describes the
+  /// uncompressed data produced by [`Texture::read`].
+  #[inline]
+  pub fn info(&self) -> &TextureInfo {
+    &self.info
+  }
+
+  /// Retrieve the texture's compressed format, if it is compressed.
+  #[inline]
+  pub fn compressed_format(&self) -> Option<sys::CompressedTextureFormat> {
+    self.compressed
+  }
+
+  /// Retrieve the texture's number of layers (`1` for non-array
+  /// textures, `6` for cube maps, and the depth for 3D textures).
+  #[inline]
+  pub fn layers(&self) -> u32 {
+    self.layers
+  }
+
+  /// Regenerate mipmaps after the base level got modified, if the
+  /// texture has any.
+  fn update_mipmaps(&self) {
+    if self.levels > 1 {
+      let () = self.context.generate_mipmaps(self.target);
+    }
+  }
+
+  /// Update a rectangular region of a 2D texture.
+  ///
+  /// `data` has to contain exactly `w * h` pixels in the pixel format
+  /// and color format described by `info`, whose dimensions are
+  /// ignored. If `info` sets a row stride or offsets, the region is read
+  /// from the corresponding sub-rectangle of a larger image, which
+  /// `data` has to be large enough to hold. Mipmaps, if any, are
+  /// regenerated.
+  pub fn update_region(
+    &self,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+    data: &[u8],
+    info: &TextureInfo,
+  ) -> Result<()> {
+    ensure!(
+      matches!(self.target, sys::TextureTarget::Texture2D),
+      "only 2D textures can be updated by region"
+    );
+    ensure!(
+      self.compressed.is_none(),
+      "compressed textures cannot be updated"
+    );
+    ensure!(
+      x.checked_add(w)
+        .is_some_and(|right| right <= self.info.width)
+        && y.checked_add(h).is_some_and(|top| top <= self.info.height),
+      "region {w}x{h} at ({x}, {y}) exceeds texture bounds of {}x{}",
+      self.info.width,
+      self.info.height
+    );
+    let region = TextureInfo {
+      width: w,
+      height: h,
+      intern_format: self.info.intern_format,
+      ..*info
+    };
+    let () = region.validate_data(data, 1)?;
+
+    let () = region.apply_unpack_state(&self.context)?;
+    let () = self.bind();
+    let result = self
+      .context
+      .set_texture_sub_image_2d(
+        self.target,
+        info.pixel_format,
+        info.color_format,
+        x,
+        y,
+        w,
+        h,
+        data,
+      )
+      .context("failed to update texture region");
+    if let Ok(()) = result {
+      let () = self.update_mipmaps();
+    }
+    let () = self.unbind();
+    result
+  }
+
+  /// Update a layer of an array texture, a face of a cube map, or a
+  /// slice of a 3D texture.
+  ///
+  /// The image described by `info` is placed at the layer's origin and
+  /// may be smaller than the texture. Mipmaps, if any, are regenerated.
+  pub fn update_layer(&self, layer: u32, data: &[u8], info: &TextureInfo) -> Result<()> {
+    ensure!(
+      matches!(
+        self.target,
+        sys::TextureTarget::Texture2DArray
+          | sys::TextureTarget::CubeMap
+          | sys::TextureTarget::Texture3D
+      ),
+      "only array, cube map, and 3D textures can be updated by layer"
+    );
+    ensure!(
+      self.compressed.is_none(),
+      "compressed textures cannot be updated"
+    );
+    ensure!(
+      layer < self.layers,
+      "layer {layer} is out of bounds (texture has {} layers)",
+      self.layers
+    );
+    ensure!(
+      info.width <= self.info.width && info.height <= self.info.height,
+      "image of size {}x{} exceeds texture bounds of {}x{}",
+      info.width,
+      info.height,
+      self.info.width,
+      self.info.height
+    );
+    let info = &TextureInfo {
+      intern_format: self.info.intern_format,
+      ..*info
+    };
+    let () = info.validate_data(data, 1)?;
+
+    let () = info.apply_unpack_state(&self.context)?;
+    let () = self.bind();
+    let result = if let sys::TextureTarget::CubeMap = self.target {
+      let face = sys::TextureTarget::CUBE_MAP_FACES[layer as usize];
+      self
+        .context
+        .set_texture_sub_image_2d(
+          face,
+          info.pixel_format,
+          info.color_format,
+          0,
+          0,
+          info.width,
+          info.height,
+          data,
+        )
+        .with_context(|| format!("failed to update cube map face {layer}"))
+    } else {
+      populate_3d_texture(&self.context, layer, self.target, data, info)
+    };
+    if let Ok(()) = result {
+      let () = self.update_mipmaps();
+    }
+    let () = self.unbind();
+    result
+  }
+
+  /// Read back the pixels of the given mipmap level.
+  ///
+  /// The returned data contains rows from bottom to top, as is the GL
+  /// convention. For array, cube map, and 3D textures, all layers (or
+  /// faces or slices) are returned back-to-back, with the returned
+  /// [`TextureInfo`] describing a single one.
+  ///
+  /// On WebGL, only textures usable as color attachment can be read
+  /// back, which excludes compressed and depth ones. Floating point
+  /// textures furthermore have to be read back as `Float`. On OpenGL,
+  /// compressed textures are decompressed.
+  pub fn read(&self, level: u32) -> Result<(Vec<u8>, TextureInfo)> {
+    ensure!(
+      level < self.levels,
+      "mipmap level {level} is out of bounds (texture has {} levels)",
+      self.levels
+    );
+
+    let info = TextureInfo {
+      width: (self.info.width >> level).max(1),
+      height: (self.info.height >> level).max(1),
+      ..self.info
+    };
+    // Only 3D textures shrink along the third axis.
+    let layers = if let sys::TextureTarget::Texture3D = self.target {
+      (self.layers >> level).max(1)
+    } else {
+      self.layers
+    };
+    let mut data = vec![0; info.image_size() * layers as usize];
+
+    let () = self.context.set_pixel_pack_alignment(ROW_ALIGNMENT);
+    let () = self
+      .read_level(level, &info, &mut data)
+      .context("failed to read texture")?;
+    Ok((data, info))
+  }
+
+  #[cfg(not(target_arch = "wasm32"))]
+  fn read_level(&self, level: u32, info: &TextureInfo, data: &mut [u8]) -> Result<()> {
+    let () = self.bind();
+    let result = if let sys::TextureTarget::CubeMap = self.target {
+      sys::TextureTarget::CUBE_MAP_FACES
+        .into_iter()
+        .zip(data.chunks_mut(info.image_size()))
+        .try_for_each(|(face, pixels)| {
+          self
+            .context
+            .texture_image(face, level, info.pixel_format, info.color_format, pixels)
+        })
+    } else {
+      self.context.texture_image(
+        self.target,
+        level,
+        info.pixel_format,
+        info.color_format,
+        data,
+      )
+    };
+    let () = self.unbind();
+    let () = result?;
+    Ok(())
+  }
+
+  /// WebGL can't read textures directly, so we attach each layer to a
+  /// temporary framebuffer and read from that instead.
+  #[cfg(target_arch = "wasm32")]
+  fn read_level(&self, level: u32, info: &TextureInfo, data: &mut [u8]) -> Result<()> {
+    let (pixel_format, color_format) = read_format(info).ok_or_else(|| {
+      anyhow!(
+        "reading back textures of format {:?} as {:?} is unsupported on WebGL",
+        info.intern_format,
+        info.color_format
+      )
+    })?;
+    let read_info = TextureInfo {
+      pixel_format,
+      color_format,
+      row_stride: None,
+      ..*info
+    };
+    let mut buffer = vec![0; read_info.image_size()];
+
+    let fbo = self
+      .context
+      .create_framebuffer()
+      .context("failed to create framebuffer object")?;
+    let previous = self.context.framebuffer_binding();
+    let () = self.context.bind_framebuffer(Some(&fbo));
+
+    let result = data
+      .chunks_mut(info.image_size())
+      .enumerate()
+      .try_for_each(|(layer, pixels)| {
+        if matches!(
+          self.target,
+          sys::TextureTarget::Texture2DArray | sys::TextureTarget::Texture3D
+        ) {
+          let () =
+            self
+              .context
+              .set_framebuffer_color_texture_layer(0, &self.texture, level, layer as _);
+        } else if let sys::TextureTarget::CubeMap = self.target {
+          let face = sys::TextureTarget::CUBE_MAP_FACES[layer];
+          let () = self
+            .context
+            .set_framebuffer_color_texture(0, face, &self.texture, level);
+        } else {
+          let () = self
+            .context
+            .set_framebuffer_color_texture(0, self.target, &self.texture, level);
+        }
+
+        let status = self.context.check_framebuffer_status();
+        ensure!(
+          status == sys::FramebufferStatus::Complete,
+          "failed to complete framebuffer: status: {status}"
+        );
+
+        let () = self.context.read_pixels(
+          0,
+          0,
+          info.width,
+          info.height,
+          pixel_format,
+          color_format,
+          &mut buffer,
+        )?;
+        let () = repack(&read_info, &buffer, info, pixels);
+        Ok(())
+      });
+
+    let () = self.context.bind_framebuffer(previous.as_ref());
+    let () = self.context.delete_framebuffer(&fbo);
+    result
+  }
 }
 
 impl Deref for Texture {
@@ -374,3 +1574,548 @@ impl Drop for Texture {
     let () = self.context.delete_texture(&self.texture);
   }
 }
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use test_fork::fork;
+
+  use crate::winit::with_opengl_context;
+
+
+  /// Check that we compute pixel and image sizes correctly.
+  #[test]
+  fn image_sizes() {
+    let info = TextureInfo::new(
+      3,
+      2,
+      sys::TextureInternalFormat::RGB8,
+      sys::TexturePixelFormat::RGB,
+      sys::Type::UnsignedByte,
+    );
+    assert_eq!(info.pixel_size(), 3);
+    assert_eq!(info.row_size(), 9);
+    assert_eq!(info.image_size(), 18);
+
+    let info = TextureInfo {
+      intern_format: sys::TextureInternalFormat::RGBA16F,
+      pixel_format: sys::TexturePixelFormat::RGBA,
+      color_format: sys::Type::HalfFloat,
+      ..info
+    };
+    assert_eq!(info.pixel_size(), 8);
+    assert_eq!(info.image_size(), 48);
+
+    let info = TextureInfo {
+      intern_format: sys::TextureInternalFormat::Depth24Stencil8,
+      pixel_format: sys::TexturePixelFormat::DepthStencil,
+      color_format: sys::Type::UnsignedInt248,
+      ..info
+    };
+    assert_eq!(info.pixel_size(), 4);
+  }
+
+  /// Make sure that invalid format combinations and data of the wrong
+  /// size are rejected.
+  #[test]
+  fn format_validation() {
+    let info = TextureInfo::new(
+      2,
+      2,
+      sys::TextureInternalFormat::RGBA8,
+      sys::TexturePixelFormat::RGBA,
+      sys::Type::UnsignedByte,
+    );
+    let () = info.validate_data(&[0; 16], 1).unwrap();
+    let () = info.validate_data(&[0; 32], 2).unwrap();
+
+    let rgb = TextureInfo {
+      intern_format: sys::TextureInternalFormat::RGB32I,
+      pixel_format: sys::TexturePixelFormat::RGBInteger,
+      color_format: sys::Type::Int,
+      ..info
+    };
+    let () = rgb.validate_data(&[0; 48], 1).unwrap();
+
+    let err = info.validate_data(&[0; 15], 1).unwrap_err();
+    assert!(err.to_string().contains("does not match"), "{err:#}");
+    let err = info.validate_data(&[0; 16], 2).unwrap_err();
+    assert!(err.to_string().contains("does not match"), "{err:#}");
+
+    let invalid = [
+      TextureInfo {
+        color_format: sys::Type::Float,
+        ..info
+      },
+      TextureInfo {
+        pixel_format: sys::TexturePixelFormat::RGB,
+        ..info
+      },
+      TextureInfo {
+        intern_format: sys::TextureInternalFormat::RGBA8UI,
+        ..info
+      },
+      TextureInfo {
+        intern_format: sys::TextureInternalFormat::Depth,
+        pixel_format: sys::TexturePixelFormat::Depth,
+        color_format: sys::Type::UnsignedShort,
+        ..info
+      },
+      TextureInfo {
+        intern_format: sys::TextureInternalFormat::R32F,
+        pixel_format: sys::TexturePixelFormat::Gray,
+        color_format: sys::Type::HalfFloat,
+        ..info
+      },
+    ];
+    for info in invalid {
+      let err = info.validate().unwrap_err();
+      assert!(err.to_string().contains("cannot be specified"), "{err:#}");
+    }
+  }
+
+  /// Check that row strides and offsets are mapped to the correct
+  /// unpack state and taken into account when validating data.
+  #[test]
+  fn strided_layout() {
+    let info = TextureInfo::new(
+      3,
+      2,
+      sys::TextureInternalFormat::RGB8,
+      sys::TexturePixelFormat::RGB,
+      sys::Type::UnsignedByte,
+    );
+    assert_eq!(info.unpack_layout().unwrap(), (ROW_ALIGNMENT, 0));
+
+    // Rows padded to four bytes, as is common for decoders.
+    let padded = info.with_row_stride(12);
+    assert_eq!(padded.unpack_layout().unwrap(), (1, 4));
+    let padded = info.with_row_stride(10);
+    assert_eq!(padded.unpack_layout().unwrap(), (2, 3));
+    assert_eq!(padded.row_size(), 10);
+    // The last row does not have to be padded.
+    let () = padded.validate_data(&[0; 19], 1).unwrap();
+    let err = padded.validate_data(&[0; 18], 1).unwrap_err();
+    assert!(err.to_string().contains("too small"), "{err:#}");
+
+    let err = info.with_row_stride(8).unpack_layout().unwrap_err();
+    assert!(err.to_string().contains("smaller than"), "{err:#}");
+    let err = info.with_row_stride(11).unpack_layout().unwrap_err();
+    assert!(err.to_string().contains("cannot be expressed"), "{err:#}");
+
+    // A 3x2 region at (1, 1) of a 5x4 image.
+    let region = info.with_row_stride(15).with_offset(1, 1);
+    assert_eq!(region.unpack_layout().unwrap(), (1, 5));
+    let () = region.validate_data(&[0; 60], 1).unwrap();
+    let () = region.validate_data(&[0; 42], 1).unwrap();
+    assert!(region.validate_data(&[0; 41], 1).is_err());
+    assert!(region.packed().is_packed());
+  }
+
+  /// Check that we can read back texture data.
+  #[fork]
+  #[test]
+  fn texture_readback() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let info = TextureInfo::new(
+        3,
+        2,
+        sys::TextureInternalFormat::RGBA8,
+        sys::TexturePixelFormat::RGBA,
+        sys::Type::UnsignedByte,
+      );
+      let data = (0..info.image_size() as u8).collect::<Vec<_>>();
+      let texture = Texture::builder()
+        .set_mipmaps(true)
+        .set_context(&gl_context)
+        .from_image(&data, &info)
+        .unwrap();
+
+      let (pixels, read_info) = texture.read(0).unwrap();
+      assert_eq!(pixels, data);
+      assert_eq!((read_info.width, read_info.height), (3, 2));
+
+      let (pixels, read_info) = texture.read(1).unwrap();
+      assert_eq!((read_info.width, read_info.height), (1, 1));
+      assert_eq!(pixels.len(), 4);
+      assert!(texture.read(2).is_err());
+
+      // Buffers too small to hold the level should be rejected.
+      let () = texture.bind();
+      let mut pixels = vec![0; info.image_size() - 1];
+      let result = gl_context.texture_image(
+        texture.target(),
+        0,
+        info.pixel_format,
+        info.color_format,
+        &mut pixels,
+      );
+      assert!(result.is_err());
+      let () = texture.unbind();
+      let mut pixels = [0; 3];
+      let result = gl_context.read_pixels(
+        0,
+        0,
+        1,
+        1,
+        sys::TexturePixelFormat::RGBA,
+        sys::Type::UnsignedByte,
+        &mut pixels,
+      );
+      assert!(result.is_err());
+
+      let layers = [vec![0x11; info.image_size()], vec![0x22; info.image_size()]];
+      let texture = Texture::builder()
+        .set_context(&gl_context)
+        .from_images(
+          info.width,
+          info.height,
+          layers.iter().map(|layer| Ok((layer, info))),
+        )
+        .unwrap();
+      let (pixels, _info) = texture.read(0).unwrap();
+      assert_eq!(pixels, layers.concat());
+    })
+  }
+
+  /// Check that we can create textures with float, integer, and
+  /// depth-stencil formats.
+  #[fork]
+  #[test]
+  fn format_creation() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let formats = [
+        (
+          sys::TextureInternalFormat::R32F,
+          sys::TexturePixelFormat::Gray,
+          sys::Type::Float,
+        ),
+        (
+          sys::TextureInternalFormat::RGBA16F,
+          sys::TexturePixelFormat::RGBA,
+          sys::Type::HalfFloat,
+        ),
+        (
+          sys::TextureInternalFormat::R32UI,
+          sys::TexturePixelFormat::GrayInteger,
+          sys::Type::UnsignedInt,
+        ),
+        (
+          sys::TextureInternalFormat::RG8I,
+          sys::TexturePixelFormat::RGInteger,
+          sys::Type::Byte,
+        ),
+        (
+          sys::TextureInternalFormat::RGB16UI,
+          sys::TexturePixelFormat::RGBInteger,
+          sys::Type::UnsignedShort,
+        ),
+        (
+          sys::TextureInternalFormat::Depth24Stencil8,
+          sys::TexturePixelFormat::DepthStencil,
+          sys::Type::UnsignedInt248,
+        ),
+      ];
+
+      for (intern_format, pixel_format, color_format) in formats {
+        let info = TextureInfo::new(2, 2, intern_format, pixel_format, color_format);
+        let data = (0..info.image_size() as u8).collect::<Vec<_>>();
+        let texture = Texture::builder()
+          .set_context(&gl_context)
+          .from_image(&data, &info)
+          .unwrap();
+
+        let (pixels, _info) = texture.read(0).unwrap();
+        assert_eq!(pixels.len(), data.len());
+      }
+    })
+  }
+
+  /// Check that we can update parts of textures.
+  #[fork]
+  #[test]
+  fn texture_updating() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let info = TextureInfo::new(
+        4,
+        4,
+        sys::TextureInternalFormat::Gray8,
+        sys::TexturePixelFormat::Gray,
+        sys::Type::UnsignedByte,
+      );
+      let texture = Texture::builder()
+        .set_context(&gl_context)
+        .from_image(&[0; 16], &info)
+        .unwrap();
+
+      let () = texture.update_region(1, 2, 2, 1, &[7, 8], &info).unwrap();
+      let (pixels, _info) = texture.read(0).unwrap();
+      let mut expected = [0; 16];
+      expected[9] = 7;
+      expected[10] = 8;
+      assert_eq!(pixels, expected);
+
+      let err = texture
+        .update_region(3, 3, 2, 1, &[7, 8], &info)
+        .unwrap_err();
+      assert!(
+        err.to_string().contains("exceeds texture bounds"),
+        "{err:#}"
+      );
+      let err = texture
+        .update_region(0, 0, 2, 2, &[7, 8], &info)
+        .unwrap_err();
+      assert!(err.to_string().contains("does not match"), "{err:#}");
+      let err = texture.update_layer(0, &[0; 16], &info).unwrap_err();
+      assert!(
+        err
+          .to_string()
+          .contains("only array, cube map, and 3D textures"),
+        "{err:#}"
+      );
+
+      let layers = [[1; 16], [2; 16]];
+      let texture = Texture::builder()
+        .set_context(&gl_context)
+        .from_images(
+          info.width,
+          info.height,
+          layers.iter().map(|layer| Ok((layer, info))),
+        )
+        .unwrap();
+      let () = texture.update_layer(1, &[3; 16], &info).unwrap();
+      let (pixels, _info) = texture.read(0).unwrap();
+      assert_eq!(pixels, [[1; 16], [3; 16]].concat());
+      assert!(texture.update_layer(2, &[3; 16], &info).is_err());
+    })
+  }
+
+  /// Check that we can upload a sub-rectangle of a larger image.
+  #[fork]
+  #[test]
+  fn strided_upload() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      // A 4x3 gray image, with rows padded to eight bytes.
+      #[rustfmt::skip]
+      let data = [
+         0,  1,  2,  3, 0xff, 0xff, 0xff, 0xff,
+        10, 11, 12, 13, 0xff, 0xff, 0xff, 0xff,
+        20, 21, 22, 23,
+      ];
+      let info = TextureInfo::new(
+        2,
+        2,
+        sys::TextureInternalFormat::Gray8,
+        sys::TexturePixelFormat::Gray,
+        sys::Type::UnsignedByte,
+      )
+      .with_row_stride(8)
+      .with_offset(1, 1);
+      let texture = Texture::builder()
+        .set_context(&gl_context)
+        .from_image(&data, &info)
+        .unwrap();
+      assert!(texture.info().row_stride.is_none());
+
+      let (pixels, _info) = texture.read(0).unwrap();
+      assert_eq!(pixels, [11, 12, 21, 22]);
+
+      let info = TextureInfo {
+        width: 1,
+        x_offset: 3,
+        y_offset: 0,
+        ..info
+      };
+      let () = texture.update_region(0, 0, 1, 2, &data, &info).unwrap();
+      let (pixels, _info) = texture.read(0).unwrap();
+      assert_eq!(pixels, [3, 12, 13, 22]);
+    })
+  }
+
+  /// Check that we can create textures from compressed images.
+  #[fork]
+  #[test]
+  fn compressed_creation() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let format = sys::CompressedTextureFormat::BC4;
+      assert!(gl_context.is_compressed_format_supported(format));
+
+      let image = CompressedImage::new(format, 8, 8, 1, vec![vec![0; 32], vec![0; 8]]).unwrap();
+      let texture = Texture::builder()
+        .set_context(&gl_context)
+        .from_compressed(&[image])
+        .unwrap();
+      assert!(matches!(
+        texture.compressed_format(),
+        Some(sys::CompressedTextureFormat::BC4)
+      ));
+
+      let (pixels, info) = texture.read(1).unwrap();
+      assert_eq!((info.width, info.height), (4, 4));
+      assert_eq!(pixels, [0; 16]);
+
+      let err = texture.update_region(0, 0, 1, 1, &[0], &info).unwrap_err();
+      assert!(err.to_string().contains("cannot be updated"), "{err:#}");
+
+      let err = Texture::builder()
+        .set_context(&gl_context)
+        .from_compressed(&[])
+        .unwrap_err();
+      assert!(err.to_string().contains("is supported"), "{err:#}");
+    })
+  }
+
+  /// Check that we can create 3D textures.
+  #[fork]
+  #[test]
+  fn volume_creation() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let info = TextureInfo::new(
+        2,
+        2,
+        sys::TextureInternalFormat::Gray8,
+        sys::TexturePixelFormat::Gray,
+        sys::Type::UnsignedByte,
+      );
+      let data = (0..16).collect::<Vec<u8>>();
+      let texture = Texture::builder()
+        .set_mipmaps(true)
+        .set_context(&gl_context)
+        .from_volume(&data, &info, 4)
+        .unwrap();
+      assert!(matches!(texture.target(), sys::TextureTarget::Texture3D));
+      assert_eq!(texture.layers(), 4);
+
+      let (pixels, _info) = texture.read(0).unwrap();
+      assert_eq!(pixels, data);
+      // The second level is 1x1x2 in size.
+      let (pixels, _info) = texture.read(1).unwrap();
+      assert_eq!(pixels.len(), 2);
+
+      let err = Texture::builder()
+        .set_context(&gl_context)
+        .from_volume(&data, &info, 3)
+        .unwrap_err();
+      assert!(err.to_string().contains("does not match"), "{err:#}");
+
+      let slices = [[1; 4], [2; 4]];
+      let texture = Texture::builder()
+        .set_context(&gl_context)
+        .from_slices(
+          info.width,
+          info.height,
+          slices.iter().map(|slice| Ok((slice, info))),
+        )
+        .unwrap();
+      let () = texture.update_layer(0, &[3; 4], &info).unwrap();
+      let (pixels, _info) = texture.read(0).unwrap();
+      assert_eq!(pixels, [[3; 4], [2; 4]].concat());
+    })
+  }
+
+  /// Check that we can create and update cube maps.
+  #[fork]
+  #[test]
+  fn cube_map_creation() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let info = TextureInfo::new(
+        2,
+        2,
+        sys::TextureInternalFormat::Gray8,
+        sys::TexturePixelFormat::Gray,
+        sys::Type::UnsignedByte,
+      );
+      let faces = [0, 1, 2, 3, 4, 5].map(|face| ([face; 4], info));
+      let texture = Texture::builder()
+        .set_mipmaps(true)
+        .set_context(&gl_context)
+        .from_cube_faces(faces)
+        .unwrap();
+      assert_eq!(texture.layers(), 6);
+
+      let () = texture.update_layer(4, &[9; 4], &info).unwrap();
+      let (pixels, _info) = texture.read(0).unwrap();
+      let expected = [[0; 4], [1; 4], [2; 4], [3; 4], [9; 4], [5; 4]].concat();
+      assert_eq!(pixels, expected);
+
+      let mut faces = [0, 1, 2, 3, 4, 5].map(|face| (vec![face; 4], info));
+      faces[3].1.height = 1;
+      faces[3].0.truncate(2);
+      let err = Texture::builder()
+        .set_context(&gl_context)
+        .from_cube_faces(faces)
+        .unwrap_err();
+      assert!(err.to_string().contains("cube map face 3"), "{err:#}");
+
+      let mut faces = [0, 1, 2, 3, 4, 5].map(|face| (vec![face; 4], info));
+      faces[5].1.intern_format = sys::TextureInternalFormat::R8UI;
+      faces[5].1.pixel_format = sys::TexturePixelFormat::GrayInteger;
+      let err = Texture::builder()
+        .set_context(&gl_context)
+        .from_cube_faces(faces)
+        .unwrap_err();
+      assert!(
+        err.to_string().contains("cube map face 5 is of format"),
+        "{err:#}"
+      );
+    })
+  }
+
+  /// Check that we can create textures with custom sampling
+  /// parameters.
+  #[fork]
+  #[test]
+  fn texture_parameters() {
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let info = TextureInfo::new(
+        4,
+        4,
+        sys::TextureInternalFormat::Gray8,
+        sys::TexturePixelFormat::Gray,
+        sys::Type::UnsignedByte,
+      );
+      let data = [0x7f; 16];
+      let _texture = Texture::builder()
+        .set_mipmaps(true)
+        .set_min_filter(sys::TextureFilter::NearestMipmapNearest)
+        .set_mag_filter(sys::TextureFilter::Nearest)
+        .set_wrap_mode(sys::TextureWrap::MirroredRepeat)
+        .set_axis_wrap_mode(sys::TextureWrapAxis::T, sys::TextureWrap::ClampToBorder)
+        .set_anisotropy(16.0)
+        .set_lod_range(0.0, 2.0)
+        .set_level_range(0, 1)
+        .set_lod_bias(0.5)
+        .set_border_color([1.0, 0.0, 0.0, 1.0])
+        .set_swizzle([
+          sys::TextureSwizzle::Red,
+          sys::TextureSwizzle::Red,
+          sys::TextureSwizzle::Red,
+          sys::TextureSwizzle::One,
+        ])
+        .set_context(&gl_context)
+        .from_image(&data, &info)
+        .unwrap();
+      assert_eq!(gl_context.error(), Ok(()));
+
+      let err = Texture::builder()
+        .set_mag_filter(sys::TextureFilter::LinearMipmapLinear)
+        .set_context(&gl_context)
+        .from_image(&data, &info)
+        .unwrap_err();
+      assert_eq!(
+        err.to_string(),
+        "LinearMipmapLinear is not a valid magnification filter"
+      );
+    })
+  }
+}
@@ -0,0 +1,352 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::any::type_name;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use anyhow::bail;
+use anyhow::ensure;
+use anyhow::Result;
+
+use crate::sys;
+use crate::sys::BuiltinType;
+use crate::vertex::Attrib;
+use crate::vertex::AttribType;
+use crate::vertex::Attribs;
+use crate::Mesh;
+use crate::Program;
+use crate::Uniforms;
+use crate::VertexBuffer;
+
+
+/// Check whether a vertex attribute is compatible with a shader input
+/// of the given GLSL type.
+///
+/// Shader inputs may have more components than provided by the vertex
+/// attribute, in which case missing components are filled in with
+/// their defaults.
+fn is_compatible(attrib: &Attrib, type_: sys::GlslType) -> bool {
+  let components = if type_ == sys::GlslType::Float {
+    1
+  } else if type_ == sys::GlslType::Vec2 {
+    2
+  } else if type_ == sys::GlslType::Vec3 {
+    3
+  } else if type_ == sys::GlslType::Vec4 {
+    4
+  } else {
+    return false
+  };
+  attrib.size <= components
+}
+
+
+/// A [`Program`] whose interface has been checked against the vertex
+/// type `V` and the uniforms `U`.
+///
+/// The program's active attributes have to match `V::ATTRIBS` by name
+/// (e.g., `position` for [`AttribType::Position`]) and type, while
+/// vertex attributes not used by the program are ignored. All
+/// uniforms represented by `U` have to be present with compatible
+/// types.
+pub struct TypedProgram<V, U>
+where
+  U: Uniforms,
+{
+  /// The checked program.
+  program: Program,
+  /// The shader attribute index of each of the vertex type's attributes.
+  attrib_indices: Vec<(u32, AttribType)>,
+  /// The resolved uniform handles.
+  handles: U::Handles,
+  /// Phantom data for `V`.
+  _phantom: PhantomData<fn(&V)>,
+}
+
+impl<V, U> TypedProgram<V, U>
+where
+  V: Attribs,
+  U: Uniforms,
+{
+  /// Check the interface of `program` and wrap it.
+  pub fn new(program: Program) -> Result<Self> {
+    let attribs = program
+      .attributes()
+      .into_iter()
+      // Built-in inputs such as `gl_VertexID` may be reported as well.
+      .filter(|attrib| !attrib.name.starts_with("gl_"))
+      .collect::<Vec<_>>();
+
+    for attrib in &attribs {
+      let Some((_attrib_type, vertex_attrib)) = V::ATTRIBS
+        .iter()
+        .find(|(attrib_type, _)| attrib_type.to_string() == attrib.name)
+      else {
+        bail!(
+          "attribute `{}` is not provided by vertex type `{}`",
+          attrib.name,
+          type_name::<V>()
+        )
+      };
+
+      ensure!(
+        attrib.size == 1 && is_compatible(vertex_attrib, attrib.type_),
+        "attribute `{}` of type `{}` is incompatible with {} component vertex attribute of `{}`",
+        attrib.name,
+        attrib.type_,
+        vertex_attrib.size,
+        type_name::<V>()
+      );
+    }
+
+    // Vertex attributes not used by the program (e.g., because they
+    // got optimized out) are simply not bound.
+    let attrib_indices = V::ATTRIBS
+      .iter()
+      .filter_map(|(attrib_type, _)| {
+        let name = attrib_type.to_string();
+        let attrib = attribs.iter().find(|attrib| attrib.name == name)?;
+        Some((attrib.location, *attrib_type))
+      })
+      .collect::<Vec<_>>();
+
+    let handles = U::resolve(&program)?;
+
+    let slf = Self {
+      program,
+      attrib_indices,
+      handles,
+      _phantom: PhantomData,
+    };
+    Ok(slf)
+  }
+
+  /// Create a [`Mesh`] to draw with this program from the provided
+  /// vertex and (optional) index buffers.
+  pub fn mesh<I>(
+    &self,
+    vertices: VertexBuffer<V>,
+    indices: Option<VertexBuffer<I>>,
+    primitive: sys::Primitive,
+  ) -> Result<Mesh<V, I>>
+  where
+    I: BuiltinType<sys::Context>,
+  {
+    Mesh::new(
+      vertices,
+      indices,
+      primitive,
+      &self.attrib_indices,
+      self.program.context(),
+    )
+  }
+
+  /// Retrieve the shader attribute index of each of the vertex type's
+  /// attributes, as required for creating a [`Mesh`] to draw with this
+  /// program.
+  #[inline]
+  pub fn attrib_indices(&self) -> &[(u32, AttribType)] {
+    &self.attrib_indices
+  }
+
+  /// Upload `uniforms` to the program.
+  ///
+  /// The program has to be bound.
+  #[inline]
+  pub fn apply(&self, uniforms: &U) {
+    let () = uniforms.upload(&self.handles);
+  }
+
+  /// Draw `mesh` using this program and the provided uniforms.
+  ///
+  /// `mesh` has to have been created using [`TypedProgram::mesh`] or
+  /// with the attribute indices reported by
+  /// [`TypedProgram::attrib_indices`], or an error is reported.
+  pub fn draw<I>(&self, mesh: &Mesh<V, I>, uniforms: &U) -> Result<()>
+  where
+    I: BuiltinType<sys::Context>,
+  {
+    let mesh_indices = mesh.attrib_indices();
+    ensure!(
+      mesh_indices.len() == self.attrib_indices.len()
+        && self
+          .attrib_indices
+          .iter()
+          .all(|indices| mesh_indices.contains(indices)),
+      "mesh attribute indices {mesh_indices:?} do not match program attribute indices {:?}",
+      self.attrib_indices
+    );
+
+    let () = self.program.bind();
+    let () = self.apply(uniforms);
+    let () = mesh.draw()?;
+    Ok(())
+  }
+
+  /// Destroy the object, returning the wrapped program.
+  #[inline]
+  pub fn into_inner(self) -> Program {
+    self.program
+  }
+}
+
+impl<V, U> Debug for TypedProgram<V, U>
+where
+  U: Uniforms,
+{
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+    f.debug_struct("TypedProgram")
+      .field("program", &self.program)
+      .field("attrib_indices", &self.attrib_indices)
+      .finish_non_exhaustive()
+  }
+}
+
+impl<V, U> Deref for TypedProgram<V, U>
+where
+  U: Uniforms,
+{
+  type Target = Program;
+
+  #[inline]
+  fn deref(&self) -> &Self::Target {
+    &self.program
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::mem::swap;
+
+  use test_fork::fork;
+
+  use crate::vertex;
+  use crate::winit::with_opengl_context;
+  use crate::Shader;
+
+
+  #[derive(xgl_derive::Uniforms)]
+  struct Transform {
+    transform: [[f32; 4]; 4],
+  }
+
+
+  /// Check that we validate a program's interface against vertex and
+  /// uniform types.
+  #[fork]
+  #[test]
+  fn typed_program() {
+    let vertex_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec4 position;
+in vec2 texture;
+
+uniform mat4 transform;
+
+out vec2 uv;
+
+void main() {{
+   uv = texture;
+   gl_Position = transform * position;
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    let fragment_shader = format!(
+      r#"
+#version {glsl_version}
+
+in vec2 uv;
+out vec4 color;
+
+void main() {{
+   color = vec4(uv, 0.0f, 1.0f);
+}}
+"#,
+      glsl_version = Shader::glsl_version()
+    );
+
+    with_opengl_context(|| {
+      let gl_context = sys::Context::default();
+      let create_program = || {
+        let vertex_shader =
+          Shader::new(sys::ShaderType::Vertex, &vertex_shader, &gl_context).unwrap();
+        let fragment_shader =
+          Shader::new(sys::ShaderType::Fragment, &fragment_shader, &gl_context).unwrap();
+        Program::new(&[vertex_shader, fragment_shader], &gl_context).unwrap()
+      };
+
+      let program = TypedProgram::<vertex::P3fT2f, Transform>::new(create_program()).unwrap();
+      let vertices = [
+        vertex::P3fT2f::default(),
+        vertex::P3fT2f::default(),
+        vertex::P3fT2f::default(),
+      ];
+      let usage = sys::VertexBufferUsage::StaticDraw;
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let mesh = program
+        .mesh::<u16>(buffer, None, sys::Primitive::Triangles)
+        .unwrap();
+      let transform = Transform {
+        transform: [
+          [1.0, 0.0, 0.0, 0.0],
+          [0.0, 1.0, 0.0, 0.0],
+          [0.0, 0.0, 1.0, 0.0],
+          [0.0, 0.0, 0.0, 1.0],
+        ],
+      };
+      let () = program.draw(&mesh, &transform).unwrap();
+
+      // A mesh with attributes bound to different indices must be
+      // rejected.
+      let mut attrib_indices = program.attrib_indices().to_vec();
+      let [(first, _), (second, _)] = attrib_indices.as_mut_slice() else {
+        panic!("unexpected attribute indices: {attrib_indices:?}")
+      };
+      let () = swap(first, second);
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let mesh = Mesh::<_, u16>::new(
+        buffer,
+        None,
+        sys::Primitive::Triangles,
+        &attrib_indices,
+        &gl_context,
+      )
+      .unwrap();
+      let err = program.draw(&mesh, &transform).unwrap_err();
+      assert!(err.to_string().contains("do not match"), "{err:#}");
+
+      let err = TypedProgram::<vertex::P3f, Transform>::new(program.into_inner()).unwrap_err();
+      assert!(
+        err
+          .to_string()
+          .contains("attribute `texture` is not provided"),
+        "{err:#}"
+      );
+
+      // Vertex attributes not used by the program should be ignored.
+      let program = TypedProgram::<vertex::P3fT2fN3f, Transform>::new(create_program()).unwrap();
+      assert_eq!(program.attrib_indices().len(), 2);
+      let vertices = [
+        vertex::P3fT2fN3f::default(),
+        vertex::P3fT2fN3f::default(),
+        vertex::P3fT2fN3f::default(),
+      ];
+      let buffer = VertexBuffer::from_vertices(&vertices, usage, &gl_context).unwrap();
+      let mesh = program
+        .mesh::<u16>(buffer, None, sys::Primitive::Triangles)
+        .unwrap();
+      let () = program.draw(&mesh, &transform).unwrap();
+    })
+  }
+}
@@ -0,0 +1,510 @@
+// Copyright (C) 2026 Daniel Mueller <deso@posteo.net>
+// SPDX-License-Identifier: (Apache-2.0 OR MIT)
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::slice;
+
+use anyhow::Result;
+
+use crate::sys;
+use crate::sys::Gl as _;
+use crate::Program;
+
+
+/// A trait for types that can be used as (array) elements of a
+/// uniform.
+pub trait UniformElement: Sized {
+  /// Check whether the given GLSL type is compatible with `Self`.
+  fn is_compatible(type_: sys::GlslType) -> bool;
+
+  /// Set the uniform at `location` to the provided elements.
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context);
+
+  /// Retrieve the value of the (non-array) uniform at `location` of
+  /// `program`.
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self>;
+}
+
+macro_rules! impl_uniform_element {
+  ($method:ident, $getter:ident, { $($ty:ty => $($glsl:ident)|+),+ $(,)? }) => {
+    $(impl UniformElement for $ty {
+      #[inline]
+      fn is_compatible(type_: sys::GlslType) -> bool {
+        matches!(type_, $(sys::GlslType::$glsl)|+)
+      }
+
+      #[inline]
+      fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+        context.$method(location, data)
+      }
+
+      #[inline]
+      fn uniform(
+        program: &sys::Program,
+        location: &sys::UniformLocation,
+        context: &sys::Context,
+      ) -> Result<Self> {
+        let value = context.$getter(program, location)?;
+        Ok(value)
+      }
+    })+
+  };
+}
+
+impl_uniform_element!(set_uniform_fv, uniform_fv, {
+  [f32; 1] => Float,
+  [f32; 2] => Vec2,
+  [f32; 3] => Vec3,
+  [f32; 4] => Vec4,
+});
+impl_uniform_element!(set_uniform_iv, uniform_iv, {
+  [i32; 2] => IVec2,
+  [i32; 3] => IVec3,
+  [i32; 4] => IVec4,
+});
+impl_uniform_element!(set_uniform_uiv, uniform_uiv, {
+  [u32; 1] => UInt,
+  [u32; 2] => UVec2,
+  [u32; 3] => UVec3,
+  [u32; 4] => UVec4,
+});
+impl_uniform_element!(set_uniform_matrix_fv, uniform_matrix_fv, {
+  [[f32; 2]; 2] => Mat2,
+  [[f32; 3]; 2] => Mat2x3,
+  [[f32; 4]; 2] => Mat2x4,
+  [[f32; 2]; 3] => Mat3x2,
+  [[f32; 3]; 3] => Mat3,
+  [[f32; 4]; 3] => Mat3x4,
+  [[f32; 2]; 4] => Mat4x2,
+  [[f32; 3]; 4] => Mat4x3,
+  [[f32; 4]; 4] => Mat4,
+});
+
+impl UniformElement for f32 {
+  #[inline]
+  fn is_compatible(type_: sys::GlslType) -> bool {
+    type_ == sys::GlslType::Float
+  }
+
+  #[inline]
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+    context.set_uniform_fv(location, data.as_chunks::<1>().0)
+  }
+
+  #[inline]
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let [value] = context.uniform_fv(program, location)?;
+    Ok(value)
+  }
+}
+
+impl UniformElement for [i32; 1] {
+  #[inline]
+  fn is_compatible(type_: sys::GlslType) -> bool {
+    <i32 as UniformElement>::is_compatible(type_)
+  }
+
+  #[inline]
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+    context.set_uniform_iv(location, data)
+  }
+
+  #[inline]
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let value = context.uniform_iv(program, location)?;
+    Ok(value)
+  }
+}
+
+/// `i32` values are used for `int` uniforms as well as for samplers,
+/// where they represent the texture unit to sample from.
+impl UniformElement for i32 {
+  #[inline]
+  fn is_compatible(type_: sys::GlslType) -> bool {
+    type_ == sys::GlslType::Int || type_.is_sampler()
+  }
+
+  #[inline]
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+    context.set_uniform_iv(location, data.as_chunks::<1>().0)
+  }
+
+  #[inline]
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let [value] = context.uniform_iv(program, location)?;
+    Ok(value)
+  }
+}
+
+impl UniformElement for u32 {
+  #[inline]
+  fn is_compatible(type_: sys::GlslType) -> bool {
+    type_ == sys::GlslType::UInt
+  }
+
+  #[inline]
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+    context.set_uniform_uiv(location, data.as_chunks::<1>().0)
+  }
+
+  #[inline]
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let [value] = context.uniform_uiv(program, location)?;
+    Ok(value)
+  }
+}
+
+impl UniformElement for bool {
+  #[inline]
+  fn is_compatible(type_: sys::GlslType) -> bool {
+    type_ == sys::GlslType::Bool
+  }
+
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+    let data = data
+      .iter()
+      .map(|value| [i32::from(*value)])
+      .collect::<Vec<_>>();
+    context.set_uniform_iv(location, &data)
+  }
+
+  #[inline]
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let [value] = context.uniform_iv(program, location)?;
+    Ok(value != 0)
+  }
+}
+
+macro_rules! impl_bool_vec_uniform_element {
+  ($($n:literal => $glsl:ident),+ $(,)?) => {
+    $(impl UniformElement for [bool; $n] {
+      #[inline]
+      fn is_compatible(type_: sys::GlslType) -> bool {
+        type_ == sys::GlslType::$glsl
+      }
+
+      fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+        let data = data
+          .iter()
+          .map(|value| value.map(i32::from))
+          .collect::<Vec<_>>();
+        context.set_uniform_iv(location, &data)
+      }
+
+      #[inline]
+      fn uniform(
+        program: &sys::Program,
+        location: &sys::UniformLocation,
+        context: &sys::Context,
+      ) -> Result<Self> {
+        let value = context.uniform_iv::<$n>(program, location)?;
+        Ok(value.map(|value| value != 0))
+      }
+    })+
+  };
+}
+
+impl_bool_vec_uniform_element!(
+  2 => BVec2,
+  3 => BVec3,
+  4 => BVec4,
+);
+
+/// A flat, column-major 4x4 matrix, as used by
+/// [`MatrixStack`][crate::MatrixStack].
+impl UniformElement for [f32; 16] {
+  #[inline]
+  fn is_compatible(type_: sys::GlslType) -> bool {
+    type_ == sys::GlslType::Mat4
+  }
+
+  #[inline]
+  fn set_uniform(data: &[Self], location: &sys::UniformLocation, context: &sys::Context) {
+    context.set_uniform_matrices(location, data)
+  }
+
+  #[inline]
+  fn uniform(
+    program: &sys::Program,
+    location: &sys::UniformLocation,
+    context: &sys::Context,
+  ) -> Result<Self> {
+    let value = context.uniform_fv(program, location)?;
+    Ok(value)
+  }
+}
+
+
+/// A trait for types that can be set as the value of a uniform.
+///
+/// This trait is implemented for all [`UniformElement`] types, which
+/// map to non-array uniforms, and for slices thereof, which map to
+/// uniform arrays.
+pub trait UniformData {
+  /// Check whether a uniform of the given GLSL type and array size is
+  /// compatible with `Self`.
+  fn is_compatible(type_: sys::GlslType, size: usize) -> bool;
+
+  /// Retrieve the number of array elements represented by `self`.
+  fn count(&self) -> usize;
+
+  /// Set the uniform at `location` to `self`.
+  fn set_uniform(&self, location: &sys::UniformLocation, context: &sys::Context);
+}
+
+impl<T> UniformData for T
+where
+  T: UniformElement,
+{
+  #[inline]
+  fn is_compatible(type_: sys::GlslType, size: usize) -> bool {
+    size == 1 && <T as UniformElement>::is_compatible(type_)
+  }
+
+  #[inline]
+  fn count(&self) -> usize {
+    1
+  }
+
+  #[inline]
+  fn set_uniform(&self, location: &sys::UniformLocation, context: &sys::Context) {
+    <T as UniformElement>::set_uniform(slice::from_ref(self), location, context)
+  }
+}
+
+impl<T> UniformData for [T]
+where
+  T: UniformElement,
+{
+  #[inline]
+  fn is_compatible(type_: sys::GlslType, _size: usize) -> bool {
+    <T as UniformElement>::is_compatible(type_)
+  }
+
+  #[inline]
+  fn count(&self) -> usize {
+    self.len()
+  }
+
+  #[inline]
+  fn set_uniform(&self, location: &sys::UniformLocation, context: &sys::Context) {
+    <T as UniformElement>::set_uniform(self, location, context)
+  }
+}
+
+
+/// A handle to a uniform of a [`Program`][crate::Program], type checked
+/// against the uniform's GLSL type.
+///
+/// Objects of this type are created using
+/// [`Program::uniform`][crate::Program::uniform].
+#[derive(Debug)]
+pub struct Uniform<T: ?Sized> {
+  /// The GL context.
+  context: sys::Context,
+  /// The ID of the program the uniform belongs to and the uniform's
+  /// location in it, if it is still present.
+  ///
+  /// The location is shared with the [`ReloadableProgram`][crate::ReloadableProgram]
+  /// the uniform may have been retrieved from, which updates it when
+  /// rebuilding the program.
+  location: Rc<RefCell<Option<(u64, sys::UniformLocation)>>>,
+  /// The uniform's array size.
+  size: usize,
+  /// Phantom data for `T`.
+  _phantom: PhantomData<fn(&T)>,
+}
+
+impl<T> Uniform<T>
+where
+  T: UniformData + ?Sized,
+{
+  pub(crate) fn new(
+    program: u64,
+    location: sys::UniformLocation,
+    size: usize,
+    context: &sys::Context,
+  ) -> Self {
+    Self {
+      context: context.clone(),
+      location: Rc::new(RefCell::new(Some((program, location)))),
+      size,
+      _phantom: PhantomData,
+    }
+  }
+
+  /// Set the uniform's value.
+  ///
+  /// The program the uniform belongs to has to be bound. Setting a
+  /// uniform that is no longer present in a reloaded program has no
+  /// effect. Neither has setting more array elements than the uniform
+  /// holds, which debug builds flag with a panic.
+  pub fn set(&self, value: &T) {
+    let count = value.count();
+    if count > self.size {
+      debug_assert!(
+        false,
+        "value of {count} elements exceeds uniform array of size {}",
+        self.size
+      );
+      return
+    }
+
+    if let Some((_program, location)) = &*self.location.borrow() {
+      let () = value.set_uniform(location, &self.context);
+    }
+  }
+
+  /// Retrieve the uniform's location.
+  ///
+  /// `None` is returned if the uniform is no longer present (or no
+  /// longer compatible with `T`) after the
+  /// [`ReloadableProgram`][crate::ReloadableProgram] it was retrieved
+  /// from got rebuilt.
+  #[inline]
+  pub fn location(&self) -> Option<sys::UniformLocation> {
+    self
+      .location
+      .borrow()
+      .as_ref()
+      .map(|(_program, location)| location.clone())
+  }
+
+  /// Retrieve the uniform's location, if it belongs to the program with
+  /// the given ID.
+  pub(crate) fn location_in(&self, program: u64) -> Option<sys::UniformLocation> {
+    self
+      .location
+      .borrow()
+      .as_ref()
+      .filter(|(program_, _location)| *program_ == program)
+      .map(|(_program, location)| location.clone())
+  }
+
+  /// Retrieve the shared location of the uniform.
+  #[inline]
+  pub(crate) fn shared_location(&self) -> &Rc<RefCell<Option<(u64, sys::UniformLocation)>>> {
+    &self.location
+  }
+
+  /// Retrieve the uniform's array size (`1` for non-array uniforms).
+  #[inline]
+  pub fn size(&self) -> usize {
+    self.size
+  }
+}
+
+
+/// A trait for types whose fields map to the uniforms of a
+/// [`Program`].
+///
+/// This trait is usually implemented using `#[derive(Uniforms)]`, which
+/// is available with the `derive` feature. Each field is mapped to the
+/// uniform of the same name. `#[uniform(name = "...")]` maps a field to
+/// a differently named uniform and `#[uniform(array)]` maps an array or
+/// `Vec` field to a uniform array.
+pub trait Uniforms: 'static {
+  /// The resolved uniform handles.
+  type Handles: 'static;
+
+  /// Resolve the handles of all uniforms in `program`.
+  fn resolve(program: &Program) -> Result<Self::Handles>;
+
+  /// Upload all uniform values using the provided handles.
+  fn upload(&self, handles: &Self::Handles);
+
+  /// Upload all uniform values to `program`.
+  ///
+  /// Handles are resolved only once per program. The program has to
+  /// be bound.
+  fn apply(&self, program: &Program) -> Result<()>
+  where
+    Self: Sized,
+  {
+    let handles = program.uniform_handles::<Self>()?;
+    let () = self.upload(&handles);
+    Ok(())
+  }
+}
+
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+
+  /// Check that Rust types are matched against the expected GLSL
+  /// types.
+  #[test]
+  fn type_compatibility() {
+    assert!(<f32 as UniformData>::is_compatible(sys::GlslType::Float, 1));
+    assert!(!<f32 as UniformData>::is_compatible(
+      sys::GlslType::Float,
+      2
+    ));
+    assert!(!<f32 as UniformData>::is_compatible(sys::GlslType::Int, 1));
+    assert!(<[f32; 3] as UniformData>::is_compatible(
+      sys::GlslType::Vec3,
+      1
+    ));
+    assert!(!<[f32; 3] as UniformData>::is_compatible(
+      sys::GlslType::IVec3,
+      1
+    ));
+    assert!(<[[f32; 3]] as UniformData>::is_compatible(
+      sys::GlslType::Vec3,
+      4
+    ));
+    assert!(<i32 as UniformData>::is_compatible(
+      sys::GlslType::Sampler2D,
+      1
+    ));
+    assert!(<[i32] as UniformData>::is_compatible(
+      sys::GlslType::USamplerCube,
+      2
+    ));
+    assert!(<[u32; 2] as UniformData>::is_compatible(
+      sys::GlslType::UVec2,
+      1
+    ));
+    assert!(<[[f32; 3]; 2] as UniformData>::is_compatible(
+      sys::GlslType::Mat2x3,
+      1
+    ));
+    assert!(!<[[f32; 3]; 2] as UniformData>::is_compatible(
+      sys::GlslType::Mat3x2,
+      1
+    ));
+    assert!(<[f32; 16] as UniformData>::is_compatible(
+      sys::GlslType::Mat4,
+      1
+    ));
+    assert!(<bool as UniformData>::is_compatible(sys::GlslType::Bool, 1));
+  }
+}
@@ -14,7 +14,7 @@ use crate::sys;
 use crate::sys::Gl as _;
 
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AttribType {
   Position,
   Normal,
@@ -22,6 +22,20 @@ pub enum AttribType {
   Color,
 }
 
+impl AttribType {
+  /// Retrieve the attribute location conventionally used for this
+  /// attribute type, as bound by
+  /// [`ProgramBuilder::set_attrib_type_locations`][crate::ProgramBuilder::set_attrib_type_locations].
+  pub const fn location(&self) -> u32 {
+    match self {
+      Self::Position => 0,
+      Self::Normal => 1,
+      Self::Texture => 2,
+      Self::Color => 3,
+    }
+  }
+}
+
 impl Display for AttribType {
   fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
     let ty = match self {
@@ -368,7 +382,7 @@ impl VertexArray {
   }
 
   #[inline]
-  fn unbind(&self) {
+  pub(crate) fn unbind(&self) {
     let () = self.context.bind_vertex_array(None);
   }
 }